@@ -67,7 +67,7 @@ STD_VECDEQUE_FIELD_NAMES = [STD_VECDEQUE_FIELD_NAME_TAIL,
 STD_BTREESET_FIELD_NAMES = ["map"]
 
 # std::collections::BTreeMap<> related constants
-STD_BTREEMAP_FIELD_NAMES = ["root", "length"]
+STD_BTREEMAP_FIELD_NAMES = ["root", "length", "alloc"]
 
 # std::String related constants
 STD_STRING_FIELD_NAMES = ["vec"]
@@ -5,9 +5,11 @@ use core::hash::{Hash, Hasher};
 use core::iter::{FromIterator, Peekable, FusedIterator};
 use core::marker::PhantomData;
 use core::ops::Bound::{Excluded, Included, Unbounded};
-use core::ops::{Index, RangeBounds};
+use core::ops::{Bound, Index, RangeBounds};
 use core::{fmt, intrinsics, mem, ptr};
 
+use crate::alloc::{Alloc, Global};
+
 use super::node::{self, Handle, NodeRef, marker, InsertResult::*, ForceResult::*};
 use super::search::{self, SearchResult::*};
 
@@ -49,6 +51,13 @@ use Entry::*;
 /// [`Cell`]: ../../std/cell/struct.Cell.html
 /// [`RefCell`]: ../../std/cell/struct.RefCell.html
 ///
+/// Nodes are allocated from the allocator `A`, which defaults to [`Global`]. Entries, cursors and
+/// owning iterators that add or remove nodes use the map's allocator, and the operations that
+/// build a second tree (`clone`, `split_off`, `append` and `clear`) work with clones of it, so
+/// clones of `A` must be able to free each other's memory.
+///
+/// [`Global`]: ../../std/alloc/struct.Global.html
+///
 /// # Examples
 ///
 /// ```
@@ -120,33 +129,37 @@ use Entry::*;
 /// *stat += random_stat_buff();
 /// ```
 #[stable(feature = "rust1", since = "1.0.0")]
-pub struct BTreeMap<K, V> {
+pub struct BTreeMap<K, V, A: Alloc + Clone = Global> {
     root: node::Root<K, V>,
     length: usize,
+    alloc: A,
 }
 
 #[stable(feature = "btree_drop", since = "1.7.0")]
-unsafe impl<#[may_dangle] K, #[may_dangle] V> Drop for BTreeMap<K, V> {
+unsafe impl<#[may_dangle] K, #[may_dangle] V, A: Alloc + Clone> Drop for BTreeMap<K, V, A> {
     fn drop(&mut self) {
         unsafe {
-            drop(ptr::read(self).into_iter());
+            drop(IntoIter::new(ptr::read(&self.root), self.length, self.alloc.clone()));
         }
     }
 }
 
 #[stable(feature = "rust1", since = "1.0.0")]
-impl<K: Clone, V: Clone> Clone for BTreeMap<K, V> {
-    fn clone(&self) -> BTreeMap<K, V> {
-        fn clone_subtree<'a, K: Clone, V: Clone>(
-            node: node::NodeRef<marker::Immut<'a>, K, V, marker::LeafOrInternal>
-        ) -> BTreeMap<K, V>
+impl<K: Clone, V: Clone, A: Alloc + Clone> Clone for BTreeMap<K, V, A> {
+    fn clone(&self) -> BTreeMap<K, V, A> {
+        fn clone_subtree<'a, K: Clone, V: Clone, A: Alloc + Clone>(
+            node: node::NodeRef<marker::Immut<'a>, K, V, marker::LeafOrInternal>,
+            alloc: &A,
+        ) -> BTreeMap<K, V, A>
         where K: 'a, V: 'a,
         {
             match node.force() {
                 Leaf(leaf) => {
+                    let mut alloc = alloc.clone();
                     let mut out_tree = BTreeMap {
-                        root: node::Root::new_leaf(),
+                        root: node::Root::new_leaf(&mut alloc),
                         length: 0,
+                        alloc,
                     };
 
                     {
@@ -168,10 +181,10 @@ impl<K: Clone, V: Clone> Clone for BTreeMap<K, V> {
                     out_tree
                 }
                 Internal(internal) => {
-                    let mut out_tree = clone_subtree(internal.first_edge().descend());
+                    let mut out_tree = clone_subtree(internal.first_edge().descend(), alloc);
 
                     {
-                        let mut out_node = out_tree.root.push_level();
+                        let mut out_node = out_tree.root.push_level(&mut out_tree.alloc);
                         let mut in_edge = internal.first_edge();
                         while let Ok(kv) = in_edge.right_kv() {
                             let (k, v) = kv.into_kv();
@@ -179,14 +192,16 @@ impl<K: Clone, V: Clone> Clone for BTreeMap<K, V> {
 
                             let k = (*k).clone();
                             let v = (*v).clone();
-                            let subtree = clone_subtree(in_edge.descend());
+                            let subtree = clone_subtree(in_edge.descend(), alloc);
 
                             // We can't destructure subtree directly
                             // because BTreeMap implements Drop
                             let (subroot, sublength) = unsafe {
                                 let root = ptr::read(&subtree.root);
                                 let length = subtree.length;
+                                let suballoc = ptr::read(&subtree.alloc);
                                 mem::forget(subtree);
+                                drop(suballoc);
                                 (root, length)
                             };
 
@@ -206,14 +221,15 @@ impl<K: Clone, V: Clone> Clone for BTreeMap<K, V> {
             BTreeMap {
                 root: node::Root::shared_empty_root(),
                 length: 0,
+                alloc: self.alloc.clone(),
             }
         } else {
-            clone_subtree(self.root.as_ref())
+            clone_subtree(self.root.as_ref(), &self.alloc)
         }
     }
 }
 
-impl<K, Q: ?Sized> super::Recover<Q> for BTreeMap<K, ()>
+impl<K, Q: ?Sized, A: Alloc + Clone> super::Recover<Q> for BTreeMap<K, (), A>
     where K: Borrow<Q> + Ord,
           Q: Ord
 {
@@ -232,6 +248,7 @@ impl<K, Q: ?Sized> super::Recover<Q> for BTreeMap<K, ()>
                 Some(OccupiedEntry {
                          handle,
                          length: &mut self.length,
+                         alloc: &mut self.alloc,
                          _marker: PhantomData,
                      }
                      .remove_kv()
@@ -250,6 +267,7 @@ impl<K, Q: ?Sized> super::Recover<Q> for BTreeMap<K, ()>
                     key,
                     handle,
                     length: &mut self.length,
+                    alloc: &mut self.alloc,
                     _marker: PhantomData,
                 }
                 .insert(());
@@ -301,14 +319,15 @@ pub struct IterMut<'a, K: 'a, V: 'a> {
 /// [`into_iter`]: struct.BTreeMap.html#method.into_iter
 /// [`BTreeMap`]: struct.BTreeMap.html
 #[stable(feature = "rust1", since = "1.0.0")]
-pub struct IntoIter<K, V> {
+pub struct IntoIter<K, V, A: Alloc = Global> {
     front: Handle<NodeRef<marker::Owned, K, V, marker::Leaf>, marker::Edge>,
     back: Handle<NodeRef<marker::Owned, K, V, marker::Leaf>, marker::Edge>,
     length: usize,
+    alloc: A,
 }
 
 #[stable(feature = "collection_debug", since = "1.17.0")]
-impl<K: fmt::Debug, V: fmt::Debug> fmt::Debug for IntoIter<K, V> {
+impl<K: fmt::Debug, V: fmt::Debug, A: Alloc> fmt::Debug for IntoIter<K, V, A> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         let range = Range {
             front: self.front.reborrow(),
@@ -416,6 +435,109 @@ impl<K: fmt::Debug, V: fmt::Debug> fmt::Debug for RangeMut<'_, K, V> {
     }
 }
 
+/// A cursor over a `BTreeMap`.
+///
+/// A `Cursor` is like an iterator, except that it can freely seek back-and-forth.
+///
+/// Cursors always rest on an entry in the map, or on a "ghost" non-entry that is
+/// logically located after the last entry and before the first one.
+///
+/// This `struct` is created by the [`lower_bound`] and [`upper_bound`] methods on
+/// [`BTreeMap`]. See their documentation for more.
+///
+/// [`lower_bound`]: struct.BTreeMap.html#method.lower_bound
+/// [`upper_bound`]: struct.BTreeMap.html#method.upper_bound
+/// [`BTreeMap`]: struct.BTreeMap.html
+#[unstable(feature = "btree_cursors", issue = "0")]
+pub struct Cursor<'a, K: 'a, V: 'a> {
+    current: Option<Handle<NodeRef<marker::Immut<'a>, K, V, marker::LeafOrInternal>, marker::KV>>,
+    root: NodeRef<marker::Immut<'a>, K, V, marker::LeafOrInternal>,
+}
+
+#[unstable(feature = "btree_cursors", issue = "0")]
+impl<K, V> Clone for Cursor<'_, K, V> {
+    fn clone(&self) -> Self {
+        Cursor {
+            current: self.current,
+            root: self.root,
+        }
+    }
+}
+
+#[unstable(feature = "btree_cursors", issue = "0")]
+impl<K: fmt::Debug, V: fmt::Debug> fmt::Debug for Cursor<'_, K, V> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Cursor")
+         .field(&self.key_value())
+         .finish()
+    }
+}
+
+/// A cursor over a `BTreeMap` with editing operations.
+///
+/// A `CursorMut` is like an iterator, except that it can freely seek back-and-forth,
+/// and can safely mutate the map during iteration. Keys cannot be modified, as that
+/// could break the ordering of the map, but values can, and the current entry can
+/// be removed.
+///
+/// Cursors always rest on an entry in the map, or on a "ghost" non-entry that is
+/// logically located after the last entry and before the first one.
+///
+/// This `struct` is created by the [`lower_bound_mut`] and [`upper_bound_mut`]
+/// methods on [`BTreeMap`]. See their documentation for more.
+///
+/// [`lower_bound_mut`]: struct.BTreeMap.html#method.lower_bound_mut
+/// [`upper_bound_mut`]: struct.BTreeMap.html#method.upper_bound_mut
+/// [`BTreeMap`]: struct.BTreeMap.html
+#[unstable(feature = "btree_cursors", issue = "0")]
+pub struct CursorMut<'a, K: 'a, V: 'a, A: Alloc = Global> {
+    current: Option<Handle<NodeRef<marker::Mut<'a>, K, V, marker::LeafOrInternal>, marker::KV>>,
+
+    // Removing an entry may replace the root node, so we keep a pointer to the
+    // `Root` itself rather than a `NodeRef` to find our way back into the tree.
+    root: *mut node::Root<K, V>,
+    length: &'a mut usize,
+    alloc: &'a mut A,
+
+    // Be invariant in `K` and `V`
+    _marker: PhantomData<&'a mut (K, V)>,
+}
+
+#[unstable(feature = "btree_cursors", issue = "0")]
+unsafe impl<K: Send, V: Send, A: Alloc + Send> Send for CursorMut<'_, K, V, A> {}
+
+#[unstable(feature = "btree_cursors", issue = "0")]
+unsafe impl<K: Sync, V: Sync, A: Alloc + Sync> Sync for CursorMut<'_, K, V, A> {}
+
+#[unstable(feature = "btree_cursors", issue = "0")]
+impl<K: fmt::Debug, V: fmt::Debug, A: Alloc> fmt::Debug for CursorMut<'_, K, V, A> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("CursorMut")
+         .field(&self.key_value())
+         .finish()
+    }
+}
+
+/// An iterator produced by calling `drain_filter` on BTreeMap.
+#[unstable(feature = "btree_drain_filter", issue = "0")]
+pub struct DrainFilter<'a, K: 'a, V: 'a, F: 'a, A: Alloc = Global>
+    where K: Ord, F: FnMut(&K, &mut V) -> bool,
+{
+    cursor: CursorMut<'a, K, V, A>,
+    pred: F,
+}
+
+#[unstable(feature = "btree_drain_filter", issue = "0")]
+impl<K: Ord + fmt::Debug, V: fmt::Debug, F, A: Alloc> fmt::Debug for DrainFilter<'_, K, V, F, A>
+    where F: FnMut(&K, &mut V) -> bool,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("DrainFilter")
+         .field(&self.cursor.key_value())
+         .finish()
+    }
+}
+
 /// A view into a single entry in a map, which may either be vacant or occupied.
 ///
 /// This `enum` is constructed from the [`entry`] method on [`BTreeMap`].
@@ -423,20 +545,20 @@ impl<K: fmt::Debug, V: fmt::Debug> fmt::Debug for RangeMut<'_, K, V> {
 /// [`BTreeMap`]: struct.BTreeMap.html
 /// [`entry`]: struct.BTreeMap.html#method.entry
 #[stable(feature = "rust1", since = "1.0.0")]
-pub enum Entry<'a, K: 'a, V: 'a> {
+pub enum Entry<'a, K: 'a, V: 'a, A: Alloc = Global> {
     /// A vacant entry.
     #[stable(feature = "rust1", since = "1.0.0")]
     Vacant(#[stable(feature = "rust1", since = "1.0.0")]
-           VacantEntry<'a, K, V>),
+           VacantEntry<'a, K, V, A>),
 
     /// An occupied entry.
     #[stable(feature = "rust1", since = "1.0.0")]
     Occupied(#[stable(feature = "rust1", since = "1.0.0")]
-             OccupiedEntry<'a, K, V>),
+             OccupiedEntry<'a, K, V, A>),
 }
 
 #[stable(feature= "debug_btree_map", since = "1.12.0")]
-impl<K: Debug + Ord, V: Debug> Debug for Entry<'_, K, V> {
+impl<K: Debug + Ord, V: Debug, A: Alloc> Debug for Entry<'_, K, V, A> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match *self {
             Vacant(ref v) => f.debug_tuple("Entry")
@@ -454,17 +576,18 @@ impl<K: Debug + Ord, V: Debug> Debug for Entry<'_, K, V> {
 ///
 /// [`Entry`]: enum.Entry.html
 #[stable(feature = "rust1", since = "1.0.0")]
-pub struct VacantEntry<'a, K: 'a, V: 'a> {
+pub struct VacantEntry<'a, K: 'a, V: 'a, A: Alloc = Global> {
     key: K,
     handle: Handle<NodeRef<marker::Mut<'a>, K, V, marker::Leaf>, marker::Edge>,
     length: &'a mut usize,
+    alloc: &'a mut A,
 
     // Be invariant in `K` and `V`
     _marker: PhantomData<&'a mut (K, V)>,
 }
 
 #[stable(feature= "debug_btree_map", since = "1.12.0")]
-impl<K: Debug + Ord, V> Debug for VacantEntry<'_, K, V> {
+impl<K: Debug + Ord, V, A: Alloc> Debug for VacantEntry<'_, K, V, A> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_tuple("VacantEntry")
          .field(self.key())
@@ -477,17 +600,18 @@ impl<K: Debug + Ord, V> Debug for VacantEntry<'_, K, V> {
 ///
 /// [`Entry`]: enum.Entry.html
 #[stable(feature = "rust1", since = "1.0.0")]
-pub struct OccupiedEntry<'a, K: 'a, V: 'a> {
+pub struct OccupiedEntry<'a, K: 'a, V: 'a, A: Alloc = Global> {
     handle: Handle<NodeRef<marker::Mut<'a>, K, V, marker::LeafOrInternal>, marker::KV>,
 
     length: &'a mut usize,
+    alloc: &'a mut A,
 
     // Be invariant in `K` and `V`
     _marker: PhantomData<&'a mut (K, V)>,
 }
 
 #[stable(feature= "debug_btree_map", since = "1.12.0")]
-impl<K: Debug + Ord, V: Debug> Debug for OccupiedEntry<'_, K, V> {
+impl<K: Debug + Ord, V: Debug, A: Alloc> Debug for OccupiedEntry<'_, K, V, A> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_struct("OccupiedEntry")
          .field("key", self.key())
@@ -519,9 +643,33 @@ impl<K: Ord, V> BTreeMap<K, V> {
     /// ```
     #[stable(feature = "rust1", since = "1.0.0")]
     pub fn new() -> BTreeMap<K, V> {
+        BTreeMap::new_in(Global)
+    }
+}
+
+impl<K: Ord, V, A: Alloc + Clone> BTreeMap<K, V, A> {
+    /// Makes a new empty BTreeMap whose nodes will be allocated from `alloc`.
+    ///
+    /// The map does not allocate until the first entry is inserted.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(allocator_api)]
+    ///
+    /// use std::alloc::Global;
+    /// use std::collections::BTreeMap;
+    ///
+    /// let mut map: BTreeMap<_, _, _> = BTreeMap::new_in(Global);
+    /// map.insert(1, "a");
+    /// assert_eq!(map[&1], "a");
+    /// ```
+    #[unstable(feature = "allocator_api", issue = "32838")]
+    pub fn new_in(alloc: A) -> BTreeMap<K, V, A> {
         BTreeMap {
             root: node::Root::shared_empty_root(),
             length: 0,
+            alloc,
         }
     }
 
@@ -541,7 +689,7 @@ impl<K: Ord, V> BTreeMap<K, V> {
     /// ```
     #[stable(feature = "rust1", since = "1.0.0")]
     pub fn clear(&mut self) {
-        *self = BTreeMap::new();
+        *self = BTreeMap::new_in(self.alloc.clone());
     }
 
     /// Returns a reference to the value corresponding to the key.
@@ -599,6 +747,159 @@ impl<K: Ord, V> BTreeMap<K, V> {
         }
     }
 
+    /// Returns the first key-value pair in the map.
+    /// The key in this pair is the minimum key in the map.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// #![feature(map_first_last)]
+    /// use std::collections::BTreeMap;
+    ///
+    /// let mut map = BTreeMap::new();
+    /// assert_eq!(map.first_key_value(), None);
+    /// map.insert(1, "b");
+    /// map.insert(2, "a");
+    /// assert_eq!(map.first_key_value(), Some((&1, &"b")));
+    /// ```
+    #[unstable(feature = "map_first_last", issue = "0")]
+    pub fn first_key_value(&self) -> Option<(&K, &V)> {
+        first_leaf_edge(self.root.as_ref()).right_kv().ok().map(Handle::into_kv)
+    }
+
+    /// Returns the first entry in the map for in-place manipulation.
+    /// The key of this entry is the minimum key in the map.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(map_first_last)]
+    /// use std::collections::BTreeMap;
+    ///
+    /// let mut map = BTreeMap::new();
+    /// map.insert(1, "a");
+    /// map.insert(2, "b");
+    /// if let Some(mut entry) = map.first_entry() {
+    ///     if *entry.key() > 0 {
+    ///         entry.insert("first");
+    ///     }
+    /// }
+    /// assert_eq!(*map.get(&1).unwrap(), "first");
+    /// assert_eq!(*map.get(&2).unwrap(), "b");
+    /// ```
+    #[unstable(feature = "map_first_last", issue = "0")]
+    pub fn first_entry(&mut self) -> Option<OccupiedEntry<'_, K, V, A>> {
+        let handle = first_leaf_edge(self.root.as_mut()).right_kv().ok()?;
+        Some(OccupiedEntry {
+            handle: handle.forget_node_type(),
+            length: &mut self.length,
+            alloc: &mut self.alloc,
+            _marker: PhantomData,
+        })
+    }
+
+    /// Removes and returns the first element in the map.
+    /// The key of this element is the minimum key that was in the map.
+    ///
+    /// # Examples
+    ///
+    /// Draining elements in ascending order, while keeping a usable map each iteration.
+    ///
+    /// ```
+    /// #![feature(map_first_last)]
+    /// use std::collections::BTreeMap;
+    ///
+    /// let mut map = BTreeMap::new();
+    /// map.insert(1, "a");
+    /// map.insert(2, "b");
+    /// while let Some((key, _val)) = map.pop_first() {
+    ///     assert!(map.iter().all(|(k, _v)| *k > key));
+    /// }
+    /// assert!(map.is_empty());
+    /// ```
+    #[unstable(feature = "map_first_last", issue = "0")]
+    pub fn pop_first(&mut self) -> Option<(K, V)> {
+        self.first_entry().map(|entry| entry.remove_entry())
+    }
+
+    /// Returns the last key-value pair in the map.
+    /// The key in this pair is the maximum key in the map.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// #![feature(map_first_last)]
+    /// use std::collections::BTreeMap;
+    ///
+    /// let mut map = BTreeMap::new();
+    /// map.insert(1, "b");
+    /// map.insert(2, "a");
+    /// assert_eq!(map.last_key_value(), Some((&2, &"a")));
+    /// ```
+    #[unstable(feature = "map_first_last", issue = "0")]
+    pub fn last_key_value(&self) -> Option<(&K, &V)> {
+        last_leaf_edge(self.root.as_ref()).left_kv().ok().map(Handle::into_kv)
+    }
+
+    /// Returns the last entry in the map for in-place manipulation.
+    /// The key of this entry is the maximum key in the map.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(map_first_last)]
+    /// use std::collections::BTreeMap;
+    ///
+    /// let mut map = BTreeMap::new();
+    /// map.insert(1, "a");
+    /// map.insert(2, "b");
+    /// if let Some(mut entry) = map.last_entry() {
+    ///     if *entry.key() > 0 {
+    ///         entry.insert("last");
+    ///     }
+    /// }
+    /// assert_eq!(*map.get(&1).unwrap(), "a");
+    /// assert_eq!(*map.get(&2).unwrap(), "last");
+    /// ```
+    #[unstable(feature = "map_first_last", issue = "0")]
+    pub fn last_entry(&mut self) -> Option<OccupiedEntry<'_, K, V, A>> {
+        let handle = last_leaf_edge(self.root.as_mut()).left_kv().ok()?;
+        Some(OccupiedEntry {
+            handle: handle.forget_node_type(),
+            length: &mut self.length,
+            alloc: &mut self.alloc,
+            _marker: PhantomData,
+        })
+    }
+
+    /// Removes and returns the last element in the map.
+    /// The key of this element is the maximum key that was in the map.
+    ///
+    /// # Examples
+    ///
+    /// Draining elements in descending order, while keeping a usable map each iteration.
+    ///
+    /// ```
+    /// #![feature(map_first_last)]
+    /// use std::collections::BTreeMap;
+    ///
+    /// let mut map = BTreeMap::new();
+    /// map.insert(1, "a");
+    /// map.insert(2, "b");
+    /// while let Some((key, _val)) = map.pop_last() {
+    ///     assert!(map.iter().all(|(k, _v)| *k < key));
+    /// }
+    /// assert!(map.is_empty());
+    /// ```
+    #[unstable(feature = "map_first_last", issue = "0")]
+    pub fn pop_last(&mut self) -> Option<(K, V)> {
+        self.last_entry().map(|entry| entry.remove_entry())
+    }
+
     /// Returns `true` if the map contains a value for the specified key.
     ///
     /// The key may be any borrowed form of the map's key type, but the ordering
@@ -720,6 +1021,7 @@ impl<K: Ord, V> BTreeMap<K, V> {
                 Some(OccupiedEntry {
                          handle,
                          length: &mut self.length,
+                         alloc: &mut self.alloc,
                          _marker: PhantomData,
                      }
                      .remove())
@@ -770,8 +1072,10 @@ impl<K: Ord, V> BTreeMap<K, V> {
         }
 
         // First, we merge `self` and `other` into a sorted sequence in linear time.
-        let self_iter = mem::take(self).into_iter();
-        let other_iter = mem::take(other).into_iter();
+        let self_alloc = self.alloc.clone();
+        let self_iter = mem::replace(self, BTreeMap::new_in(self_alloc)).into_iter();
+        let other_alloc = other.alloc.clone();
+        let other_iter = mem::replace(other, BTreeMap::new_in(other_alloc)).into_iter();
         let iter = MergeIter {
             left: self_iter.peekable(),
             right: other_iter.peekable(),
@@ -867,6 +1171,181 @@ impl<K: Ord, V> BTreeMap<K, V> {
         }
     }
 
+    /// Returns a [`Cursor`] pointing at the first element that is above the
+    /// given bound.
+    ///
+    /// If no such element exists then a cursor pointing at the "ghost"
+    /// non-element is returned.
+    ///
+    /// Passing `Bound::Unbounded` will return a cursor pointing at the first
+    /// element of the map.
+    ///
+    /// [`Cursor`]: struct.Cursor.html
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// #![feature(btree_cursors)]
+    /// use std::collections::BTreeMap;
+    /// use std::ops::Bound;
+    ///
+    /// let mut map = BTreeMap::new();
+    /// map.insert(1, "a");
+    /// map.insert(2, "b");
+    /// map.insert(3, "c");
+    /// map.insert(4, "d");
+    /// let cursor = map.lower_bound(Bound::Included(&2));
+    /// assert_eq!(cursor.key(), Some(&2));
+    /// let cursor = map.lower_bound(Bound::Excluded(&2));
+    /// assert_eq!(cursor.key(), Some(&3));
+    /// ```
+    #[unstable(feature = "btree_cursors", issue = "0")]
+    pub fn lower_bound<Q: ?Sized>(&self, bound: Bound<&Q>) -> Cursor<'_, K, V>
+        where K: Borrow<Q>,
+              Q: Ord
+    {
+        let root = self.root.as_ref();
+        Cursor {
+            current: lower_bound_kv(root, bound),
+            root,
+        }
+    }
+
+    /// Returns a [`CursorMut`] pointing at the first element that is above the
+    /// given bound.
+    ///
+    /// If no such element exists then a cursor pointing at the "ghost"
+    /// non-element is returned.
+    ///
+    /// Passing `Bound::Unbounded` will return a cursor pointing at the first
+    /// element of the map.
+    ///
+    /// [`CursorMut`]: struct.CursorMut.html
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// #![feature(btree_cursors)]
+    /// use std::collections::BTreeMap;
+    /// use std::ops::Bound;
+    ///
+    /// let mut map = BTreeMap::new();
+    /// map.insert(1, "a");
+    /// map.insert(2, "b");
+    /// map.insert(3, "c");
+    /// map.insert(4, "d");
+    /// let mut cursor = map.lower_bound_mut(Bound::Included(&2));
+    /// assert_eq!(cursor.remove_current(), Some((2, "b")));
+    /// assert_eq!(cursor.key(), Some(&3));
+    /// *cursor.value_mut().unwrap() = "C";
+    /// assert_eq!(map[&3], "C");
+    /// ```
+    #[unstable(feature = "btree_cursors", issue = "0")]
+    pub fn lower_bound_mut<Q: ?Sized>(&mut self, bound: Bound<&Q>) -> CursorMut<'_, K, V, A>
+        where K: Borrow<Q>,
+              Q: Ord
+    {
+        let root = &mut self.root as *mut node::Root<K, V>;
+        CursorMut {
+            current: lower_bound_kv(unsafe { (*root).as_mut() }, bound),
+            root,
+            length: &mut self.length,
+            alloc: &mut self.alloc,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Returns a [`Cursor`] pointing at the last element that is below the
+    /// given bound.
+    ///
+    /// If no such element exists then a cursor pointing at the "ghost"
+    /// non-element is returned.
+    ///
+    /// Passing `Bound::Unbounded` will return a cursor pointing at the last
+    /// element of the map.
+    ///
+    /// [`Cursor`]: struct.Cursor.html
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// #![feature(btree_cursors)]
+    /// use std::collections::BTreeMap;
+    /// use std::ops::Bound;
+    ///
+    /// let mut map = BTreeMap::new();
+    /// map.insert(1, "a");
+    /// map.insert(2, "b");
+    /// map.insert(3, "c");
+    /// map.insert(4, "d");
+    /// let cursor = map.upper_bound(Bound::Included(&3));
+    /// assert_eq!(cursor.key(), Some(&3));
+    /// let cursor = map.upper_bound(Bound::Excluded(&3));
+    /// assert_eq!(cursor.key(), Some(&2));
+    /// ```
+    #[unstable(feature = "btree_cursors", issue = "0")]
+    pub fn upper_bound<Q: ?Sized>(&self, bound: Bound<&Q>) -> Cursor<'_, K, V>
+        where K: Borrow<Q>,
+              Q: Ord
+    {
+        let root = self.root.as_ref();
+        Cursor {
+            current: upper_bound_kv(root, bound),
+            root,
+        }
+    }
+
+    /// Returns a [`CursorMut`] pointing at the last element that is below the
+    /// given bound.
+    ///
+    /// If no such element exists then a cursor pointing at the "ghost"
+    /// non-element is returned.
+    ///
+    /// Passing `Bound::Unbounded` will return a cursor pointing at the last
+    /// element of the map.
+    ///
+    /// [`CursorMut`]: struct.CursorMut.html
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// #![feature(btree_cursors)]
+    /// use std::collections::BTreeMap;
+    /// use std::ops::Bound;
+    ///
+    /// let mut map = BTreeMap::new();
+    /// map.insert(1, "a");
+    /// map.insert(2, "b");
+    /// map.insert(3, "c");
+    /// map.insert(4, "d");
+    /// let mut cursor = map.upper_bound_mut(Bound::Excluded(&3));
+    /// assert_eq!(cursor.key(), Some(&2));
+    /// *cursor.peek_prev().unwrap().1 = "A";
+    /// assert_eq!(map[&1], "A");
+    /// ```
+    #[unstable(feature = "btree_cursors", issue = "0")]
+    pub fn upper_bound_mut<Q: ?Sized>(&mut self, bound: Bound<&Q>) -> CursorMut<'_, K, V, A>
+        where K: Borrow<Q>,
+              Q: Ord
+    {
+        let root = &mut self.root as *mut node::Root<K, V>;
+        CursorMut {
+            current: upper_bound_kv(unsafe { (*root).as_mut() }, bound),
+            root,
+            length: &mut self.length,
+            alloc: &mut self.alloc,
+            _marker: PhantomData,
+        }
+    }
+
     /// Gets the given key's corresponding entry in the map for in-place manipulation.
     ///
     /// # Examples
@@ -886,7 +1365,7 @@ impl<K: Ord, V> BTreeMap<K, V> {
     /// assert_eq!(count["a"], 3);
     /// ```
     #[stable(feature = "rust1", since = "1.0.0")]
-    pub fn entry(&mut self, key: K) -> Entry<'_, K, V> {
+    pub fn entry(&mut self, key: K) -> Entry<'_, K, V, A> {
         // FIXME(@porglezomp) Avoid allocating if we don't insert
         self.ensure_root_is_owned();
         match search::search_tree(self.root.as_mut(), &key) {
@@ -894,6 +1373,7 @@ impl<K: Ord, V> BTreeMap<K, V> {
                 Occupied(OccupiedEntry {
                     handle,
                     length: &mut self.length,
+                    alloc: &mut self.alloc,
                     _marker: PhantomData,
                 })
             }
@@ -902,6 +1382,7 @@ impl<K: Ord, V> BTreeMap<K, V> {
                     key,
                     handle,
                     length: &mut self.length,
+                    alloc: &mut self.alloc,
                     _marker: PhantomData,
                 })
             }
@@ -935,7 +1416,7 @@ impl<K: Ord, V> BTreeMap<K, V> {
                         }
                         Err(node) => {
                             // We are at the top, create a new root node and push there.
-                            open_node = node.into_root_mut().push_level();
+                            open_node = node.into_root_mut().push_level(&mut self.alloc);
                             break;
                         }
                     }
@@ -943,9 +1424,9 @@ impl<K: Ord, V> BTreeMap<K, V> {
 
                 // Push key-value pair and new right subtree.
                 let tree_height = open_node.height() - 1;
-                let mut right_tree = node::Root::new_leaf();
+                let mut right_tree = node::Root::new_leaf(&mut self.alloc);
                 for _ in 0..tree_height {
-                    right_tree.push_level();
+                    right_tree.push_level(&mut self.alloc);
                 }
                 open_node.push(key, value, right_tree);
 
@@ -1013,15 +1494,15 @@ impl<K: Ord, V> BTreeMap<K, V> {
         where K: Borrow<Q>
     {
         if self.is_empty() {
-            return Self::new();
+            return Self::new_in(self.alloc.clone());
         }
 
         let total_num = self.len();
 
-        let mut right = Self::new();
-        right.root = node::Root::new_leaf();
+        let mut right = Self::new_in(self.alloc.clone());
+        right.root = node::Root::new_leaf(&mut right.alloc);
         for _ in 0..(self.root.as_ref().height()) {
-            right.root.push_level();
+            right.root.push_level(&mut right.alloc);
         }
 
         {
@@ -1066,6 +1547,65 @@ impl<K: Ord, V> BTreeMap<K, V> {
         right
     }
 
+    /// Retains only the elements specified by the predicate.
+    ///
+    /// In other words, remove all pairs `(k, v)` such that `f(&k, &mut v)` returns `false`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(btree_retain)]
+    /// use std::collections::BTreeMap;
+    ///
+    /// let mut map: BTreeMap<i32, i32> = (0..8).map(|x| (x, x * 10)).collect();
+    /// // Keep only the elements with even-numbered keys.
+    /// map.retain(|&k, _| k % 2 == 0);
+    /// assert!(map.into_iter().eq(vec![(0, 0), (2, 20), (4, 40), (6, 60)]));
+    /// ```
+    #[unstable(feature = "btree_retain", issue = "0")]
+    pub fn retain<F>(&mut self, mut f: F)
+        where F: FnMut(&K, &mut V) -> bool,
+    {
+        self.drain_filter(|k, v| !f(k, v));
+    }
+
+    /// Creates an iterator which uses a closure to determine if an element should be removed.
+    ///
+    /// If the closure returns true, then the element is removed and yielded.
+    /// If the closure returns false, the element will remain in the map and will not be
+    /// yielded by the iterator. Elements are visited in ascending key order.
+    ///
+    /// If the iterator is only partially consumed or not consumed at all, each of the
+    /// remaining elements will still be subjected to the closure and removed and dropped
+    /// if it returns true.
+    ///
+    /// Note that `drain_filter` lets you mutate every value in the filter closure, regardless
+    /// of whether you choose to keep or remove it.
+    ///
+    /// # Examples
+    ///
+    /// Splitting a map into even and odd keys, reusing the original map:
+    ///
+    /// ```
+    /// #![feature(btree_drain_filter)]
+    /// use std::collections::BTreeMap;
+    ///
+    /// let mut map: BTreeMap<i32, i32> = (0..8).map(|x| (x, x)).collect();
+    /// let evens: BTreeMap<_, _> = map.drain_filter(|k, _v| k % 2 == 0).collect();
+    /// let odds = map;
+    /// assert_eq!(evens.keys().copied().collect::<Vec<_>>(), vec![0, 2, 4, 6]);
+    /// assert_eq!(odds.keys().copied().collect::<Vec<_>>(), vec![1, 3, 5, 7]);
+    /// ```
+    #[unstable(feature = "btree_drain_filter", issue = "0")]
+    pub fn drain_filter<F>(&mut self, pred: F) -> DrainFilter<'_, K, V, F, A>
+        where F: FnMut(&K, &mut V) -> bool,
+    {
+        DrainFilter {
+            cursor: self.lower_bound_mut::<K>(Unbounded),
+            pred,
+        }
+    }
+
     /// Calculates the number of elements if it is incorrect.
     fn recalc_length(&mut self) {
         fn dfs<'a, K, V>(
@@ -1105,7 +1645,7 @@ impl<K: Ord, V> BTreeMap<K, V> {
                     break;
                 }
             }
-            self.root.pop_level();
+            self.root.pop_level(&mut self.alloc);
         }
     }
 
@@ -1119,7 +1659,7 @@ impl<K: Ord, V> BTreeMap<K, V> {
                 let mut last_kv = node.last_kv();
 
                 if last_kv.can_merge() {
-                    cur_node = last_kv.merge().descend();
+                    cur_node = last_kv.merge(&mut self.alloc).descend();
                 } else {
                     let right_len = last_kv.reborrow().right_edge().descend().len();
                     // `MINLEN + 1` to avoid readjust if merge happens on the next level.
@@ -1145,7 +1685,7 @@ impl<K: Ord, V> BTreeMap<K, V> {
                 let mut first_kv = node.first_kv();
 
                 if first_kv.can_merge() {
-                    cur_node = first_kv.merge().descend();
+                    cur_node = first_kv.merge(&mut self.alloc).descend();
                 } else {
                     let left_len = first_kv.reborrow().left_edge().descend().len();
                     if left_len < node::MIN_LEN + 1 {
@@ -1162,13 +1702,13 @@ impl<K: Ord, V> BTreeMap<K, V> {
     /// If the root node is the shared root node, allocate our own node.
     fn ensure_root_is_owned(&mut self) {
         if self.root.is_shared_root() {
-            self.root = node::Root::new_leaf();
+            self.root = node::Root::new_leaf(&mut self.alloc);
         }
     }
 }
 
 #[stable(feature = "rust1", since = "1.0.0")]
-impl<'a, K: 'a, V: 'a> IntoIterator for &'a BTreeMap<K, V> {
+impl<'a, K: 'a, V: 'a, A: Alloc + Clone> IntoIterator for &'a BTreeMap<K, V, A> {
     type Item = (&'a K, &'a V);
     type IntoIter = Iter<'a, K, V>;
 
@@ -1232,7 +1772,7 @@ impl<K, V> Clone for Iter<'_, K, V> {
 }
 
 #[stable(feature = "rust1", since = "1.0.0")]
-impl<'a, K: 'a, V: 'a> IntoIterator for &'a mut BTreeMap<K, V> {
+impl<'a, K: 'a, V: 'a, A: Alloc + Clone> IntoIterator for &'a mut BTreeMap<K, V, A> {
     type Item = (&'a K, &'a mut V);
     type IntoIter = IterMut<'a, K, V>;
 
@@ -1286,26 +1826,38 @@ impl<K, V> ExactSizeIterator for IterMut<'_, K, V> {
 impl<K, V> FusedIterator for IterMut<'_, K, V> {}
 
 #[stable(feature = "rust1", since = "1.0.0")]
-impl<K, V> IntoIterator for BTreeMap<K, V> {
+impl<K, V, A: Alloc + Clone> IntoIterator for BTreeMap<K, V, A> {
     type Item = (K, V);
-    type IntoIter = IntoIter<K, V>;
+    type IntoIter = IntoIter<K, V, A>;
 
-    fn into_iter(self) -> IntoIter<K, V> {
-        let root1 = unsafe { ptr::read(&self.root).into_ref() };
-        let root2 = unsafe { ptr::read(&self.root).into_ref() };
+    fn into_iter(self) -> IntoIter<K, V, A> {
+        let root = unsafe { ptr::read(&self.root) };
+        let alloc = unsafe { ptr::read(&self.alloc) };
         let len = self.length;
         mem::forget(self);
 
+        unsafe { IntoIter::new(root, len, alloc) }
+    }
+}
+
+impl<K, V, A: Alloc> IntoIter<K, V, A> {
+    // Takes over the tree at `root`, which holds `length` elements and whose nodes were
+    // allocated by `alloc`.
+    unsafe fn new(root: node::Root<K, V>, length: usize, alloc: A) -> Self {
+        let root1 = ptr::read(&root).into_ref();
+        let root2 = root.into_ref();
+
         IntoIter {
             front: first_leaf_edge(root1),
             back: last_leaf_edge(root2),
-            length: len,
+            length,
+            alloc,
         }
     }
 }
 
 #[stable(feature = "btree_drop", since = "1.7.0")]
-impl<K, V> Drop for IntoIter<K, V> {
+impl<K, V, A: Alloc> Drop for IntoIter<K, V, A> {
     fn drop(&mut self) {
         self.for_each(drop);
         unsafe {
@@ -1314,9 +1866,9 @@ impl<K, V> Drop for IntoIter<K, V> {
                 return;
             }
 
-            if let Some(first_parent) = leaf_node.deallocate_and_ascend() {
+            if let Some(first_parent) = leaf_node.deallocate_and_ascend(&mut self.alloc) {
                 let mut cur_node = first_parent.into_node();
-                while let Some(parent) = cur_node.deallocate_and_ascend() {
+                while let Some(parent) = cur_node.deallocate_and_ascend(&mut self.alloc) {
                     cur_node = parent.into_node()
                 }
             }
@@ -1325,7 +1877,7 @@ impl<K, V> Drop for IntoIter<K, V> {
 }
 
 #[stable(feature = "rust1", since = "1.0.0")]
-impl<K, V> Iterator for IntoIter<K, V> {
+impl<K, V, A: Alloc> Iterator for IntoIter<K, V, A> {
     type Item = (K, V);
 
     fn next(&mut self) -> Option<(K, V)> {
@@ -1345,7 +1897,7 @@ impl<K, V> Iterator for IntoIter<K, V> {
                 return Some((k, v));
             }
             Err(last_edge) => unsafe {
-                unwrap_unchecked(last_edge.into_node().deallocate_and_ascend())
+                unwrap_unchecked(last_edge.into_node().deallocate_and_ascend(&mut self.alloc))
             },
         };
 
@@ -1358,7 +1910,9 @@ impl<K, V> Iterator for IntoIter<K, V> {
                     return Some((k, v));
                 }
                 Err(last_edge) => unsafe {
-                    cur_handle = unwrap_unchecked(last_edge.into_node().deallocate_and_ascend());
+                    cur_handle = unwrap_unchecked(
+                        last_edge.into_node().deallocate_and_ascend(&mut self.alloc)
+                    );
                 },
             }
         }
@@ -1370,7 +1924,7 @@ impl<K, V> Iterator for IntoIter<K, V> {
 }
 
 #[stable(feature = "rust1", since = "1.0.0")]
-impl<K, V> DoubleEndedIterator for IntoIter<K, V> {
+impl<K, V, A: Alloc> DoubleEndedIterator for IntoIter<K, V, A> {
     fn next_back(&mut self) -> Option<(K, V)> {
         if self.length == 0 {
             return None;
@@ -1388,7 +1942,7 @@ impl<K, V> DoubleEndedIterator for IntoIter<K, V> {
                 return Some((k, v));
             }
             Err(last_edge) => unsafe {
-                unwrap_unchecked(last_edge.into_node().deallocate_and_ascend())
+                unwrap_unchecked(last_edge.into_node().deallocate_and_ascend(&mut self.alloc))
             },
         };
 
@@ -1401,7 +1955,9 @@ impl<K, V> DoubleEndedIterator for IntoIter<K, V> {
                     return Some((k, v));
                 }
                 Err(last_edge) => unsafe {
-                    cur_handle = unwrap_unchecked(last_edge.into_node().deallocate_and_ascend());
+                    cur_handle = unwrap_unchecked(
+                        last_edge.into_node().deallocate_and_ascend(&mut self.alloc)
+                    );
                 },
             }
         }
@@ -1409,14 +1965,14 @@ impl<K, V> DoubleEndedIterator for IntoIter<K, V> {
 }
 
 #[stable(feature = "rust1", since = "1.0.0")]
-impl<K, V> ExactSizeIterator for IntoIter<K, V> {
+impl<K, V, A: Alloc> ExactSizeIterator for IntoIter<K, V, A> {
     fn len(&self) -> usize {
         self.length
     }
 }
 
 #[stable(feature = "fused", since = "1.26.0")]
-impl<K, V> FusedIterator for IntoIter<K, V> {}
+impl<K, V, A: Alloc> FusedIterator for IntoIter<K, V, A> {}
 
 #[stable(feature = "rust1", since = "1.0.0")]
 impl<'a, K, V> Iterator for Keys<'a, K, V> {
@@ -1742,6 +2298,300 @@ impl<'a, K, V> RangeMut<'a, K, V> {
     }
 }
 
+impl<'a, K, V> Cursor<'a, K, V> {
+    /// Moves the cursor to the next element of the `BTreeMap`.
+    ///
+    /// If the cursor is pointing to the "ghost" non-element then this will move it to
+    /// the first element of the `BTreeMap`. If it is pointing to the last
+    /// element of the `BTreeMap` then this will move it to the "ghost" non-element.
+    #[unstable(feature = "btree_cursors", issue = "0")]
+    pub fn move_next(&mut self) {
+        self.current = match self.current {
+            Some(kv) => next_kv(kv),
+            None => kv_after_leaf_edge(first_leaf_edge(self.root)),
+        };
+    }
+
+    /// Moves the cursor to the previous element of the `BTreeMap`.
+    ///
+    /// If the cursor is pointing to the "ghost" non-element then this will move it to
+    /// the last element of the `BTreeMap`. If it is pointing to the first
+    /// element of the `BTreeMap` then this will move it to the "ghost" non-element.
+    #[unstable(feature = "btree_cursors", issue = "0")]
+    pub fn move_prev(&mut self) {
+        self.current = match self.current {
+            Some(kv) => prev_kv(kv),
+            None => kv_before_leaf_edge(last_leaf_edge(self.root)),
+        };
+    }
+
+    /// Returns a reference to the key of the element that the cursor is
+    /// currently pointing to.
+    ///
+    /// This returns `None` if the cursor is currently pointing to the
+    /// "ghost" non-element.
+    #[unstable(feature = "btree_cursors", issue = "0")]
+    pub fn key(&self) -> Option<&'a K> {
+        self.key_value().map(|(k, _)| k)
+    }
+
+    /// Returns a reference to the value of the element that the cursor is
+    /// currently pointing to.
+    ///
+    /// This returns `None` if the cursor is currently pointing to the
+    /// "ghost" non-element.
+    #[unstable(feature = "btree_cursors", issue = "0")]
+    pub fn value(&self) -> Option<&'a V> {
+        self.key_value().map(|(_, v)| v)
+    }
+
+    /// Returns a reference to the key and value of the element that the cursor
+    /// is currently pointing to.
+    ///
+    /// This returns `None` if the cursor is currently pointing to the
+    /// "ghost" non-element.
+    #[unstable(feature = "btree_cursors", issue = "0")]
+    pub fn key_value(&self) -> Option<(&'a K, &'a V)> {
+        self.current.map(Handle::into_kv)
+    }
+
+    /// Returns a reference to the next element.
+    ///
+    /// If the cursor is pointing to the "ghost" non-element then this returns
+    /// the first element of the `BTreeMap`. If it is pointing to the last
+    /// element of the `BTreeMap` then this returns `None`.
+    #[unstable(feature = "btree_cursors", issue = "0")]
+    pub fn peek_next(&self) -> Option<(&'a K, &'a V)> {
+        let mut next = self.clone();
+        next.move_next();
+        next.key_value()
+    }
+
+    /// Returns a reference to the previous element.
+    ///
+    /// If the cursor is pointing to the "ghost" non-element then this returns
+    /// the last element of the `BTreeMap`. If it is pointing to the first
+    /// element of the `BTreeMap` then this returns `None`.
+    #[unstable(feature = "btree_cursors", issue = "0")]
+    pub fn peek_prev(&self) -> Option<(&'a K, &'a V)> {
+        let mut prev = self.clone();
+        prev.move_prev();
+        prev.key_value()
+    }
+}
+
+impl<'a, K, V, A: Alloc> CursorMut<'a, K, V, A> {
+    // Only sound to use while `self.current` is `None`, or for navigation that
+    // does not outlive the current handle.
+    unsafe fn root_mut(&self) -> NodeRef<marker::Mut<'a>, K, V, marker::LeafOrInternal> {
+        (*self.root).as_mut()
+    }
+
+    /// Moves the cursor to the next element of the `BTreeMap`.
+    ///
+    /// If the cursor is pointing to the "ghost" non-element then this will move it to
+    /// the first element of the `BTreeMap`. If it is pointing to the last
+    /// element of the `BTreeMap` then this will move it to the "ghost" non-element.
+    #[unstable(feature = "btree_cursors", issue = "0")]
+    pub fn move_next(&mut self) {
+        self.current = match self.current.take() {
+            Some(kv) => next_kv(kv),
+            None => kv_after_leaf_edge(first_leaf_edge(unsafe { self.root_mut() })),
+        };
+    }
+
+    /// Moves the cursor to the previous element of the `BTreeMap`.
+    ///
+    /// If the cursor is pointing to the "ghost" non-element then this will move it to
+    /// the last element of the `BTreeMap`. If it is pointing to the first
+    /// element of the `BTreeMap` then this will move it to the "ghost" non-element.
+    #[unstable(feature = "btree_cursors", issue = "0")]
+    pub fn move_prev(&mut self) {
+        self.current = match self.current.take() {
+            Some(kv) => prev_kv(kv),
+            None => kv_before_leaf_edge(last_leaf_edge(unsafe { self.root_mut() })),
+        };
+    }
+
+    /// Returns a reference to the key of the element that the cursor is
+    /// currently pointing to.
+    ///
+    /// This returns `None` if the cursor is currently pointing to the
+    /// "ghost" non-element.
+    #[unstable(feature = "btree_cursors", issue = "0")]
+    pub fn key(&self) -> Option<&K> {
+        self.key_value().map(|(k, _)| k)
+    }
+
+    /// Returns a reference to the value of the element that the cursor is
+    /// currently pointing to.
+    ///
+    /// This returns `None` if the cursor is currently pointing to the
+    /// "ghost" non-element.
+    #[unstable(feature = "btree_cursors", issue = "0")]
+    pub fn value(&self) -> Option<&V> {
+        self.key_value().map(|(_, v)| v)
+    }
+
+    /// Returns a mutable reference to the value of the element that the cursor
+    /// is currently pointing to.
+    ///
+    /// This returns `None` if the cursor is currently pointing to the
+    /// "ghost" non-element.
+    #[unstable(feature = "btree_cursors", issue = "0")]
+    pub fn value_mut(&mut self) -> Option<&mut V> {
+        self.key_value_mut().map(|(_, v)| v)
+    }
+
+    /// Returns a reference to the key and value of the element that the cursor
+    /// is currently pointing to.
+    ///
+    /// This returns `None` if the cursor is currently pointing to the
+    /// "ghost" non-element.
+    #[unstable(feature = "btree_cursors", issue = "0")]
+    pub fn key_value(&self) -> Option<(&K, &V)> {
+        self.current.as_ref().map(|kv| kv.reborrow().into_kv())
+    }
+
+    /// Returns a reference to the key and a mutable reference to the value of
+    /// the element that the cursor is currently pointing to.
+    ///
+    /// This returns `None` if the cursor is currently pointing to the
+    /// "ghost" non-element.
+    #[unstable(feature = "btree_cursors", issue = "0")]
+    pub fn key_value_mut(&mut self) -> Option<(&K, &mut V)> {
+        self.current.as_mut().map(|kv| {
+            let (k, v) = kv.kv_mut();
+            (&*k, v)
+        })
+    }
+
+    /// Returns a reference to the key and a mutable reference to the value of
+    /// the next element.
+    ///
+    /// If the cursor is pointing to the "ghost" non-element then this returns
+    /// the first element of the `BTreeMap`. If it is pointing to the last
+    /// element of the `BTreeMap` then this returns `None`.
+    #[unstable(feature = "btree_cursors", issue = "0")]
+    pub fn peek_next(&mut self) -> Option<(&K, &mut V)> {
+        let next = match self.current {
+            Some(ref mut kv) => next_kv(unsafe { kv.reborrow_mut() }),
+            None => kv_after_leaf_edge(first_leaf_edge(unsafe { self.root_mut() })),
+        };
+        next.map(|kv| {
+            let (k, v) = kv.into_kv_mut();
+            (&*k, v)
+        })
+    }
+
+    /// Returns a reference to the key and a mutable reference to the value of
+    /// the previous element.
+    ///
+    /// If the cursor is pointing to the "ghost" non-element then this returns
+    /// the last element of the `BTreeMap`. If it is pointing to the first
+    /// element of the `BTreeMap` then this returns `None`.
+    #[unstable(feature = "btree_cursors", issue = "0")]
+    pub fn peek_prev(&mut self) -> Option<(&K, &mut V)> {
+        let prev = match self.current {
+            Some(ref mut kv) => prev_kv(unsafe { kv.reborrow_mut() }),
+            None => kv_before_leaf_edge(last_leaf_edge(unsafe { self.root_mut() })),
+        };
+        prev.map(|kv| {
+            let (k, v) = kv.into_kv_mut();
+            (&*k, v)
+        })
+    }
+
+    /// Returns a read-only cursor pointing to the current element.
+    ///
+    /// The lifetime of the returned `Cursor` is bound to that of the
+    /// `CursorMut`, which means it cannot outlive the `CursorMut` and that the
+    /// `CursorMut` is frozen for the lifetime of the `Cursor`.
+    #[unstable(feature = "btree_cursors", issue = "0")]
+    pub fn as_cursor(&self) -> Cursor<'_, K, V> {
+        Cursor {
+            current: self.current.as_ref().map(|kv| kv.reborrow()),
+            root: unsafe { (*self.root).as_ref() },
+        }
+    }
+}
+
+impl<'a, K: Ord, V, A: Alloc> CursorMut<'a, K, V, A> {
+    /// Removes the current element from the `BTreeMap`.
+    ///
+    /// The element that was removed is returned, and the cursor is
+    /// moved to point to the next element in the `BTreeMap`.
+    ///
+    /// If the cursor is currently pointing to the "ghost" non-element then no element
+    /// is removed and `None` is returned.
+    #[unstable(feature = "btree_cursors", issue = "0")]
+    pub fn remove_current(&mut self) -> Option<(K, V)> {
+        let handle = self.current.take()?;
+        let (key, val) = OccupiedEntry {
+            handle,
+            length: &mut *self.length,
+            alloc: &mut *self.alloc,
+            _marker: PhantomData,
+        }.remove_kv();
+
+        // Removal may have merged or rebalanced any of the nodes on the way to the
+        // root, so the only reliable way back to our position is to search for the
+        // key we just took out: it lands on the edge right before its successor.
+        self.current = match search::search_tree(unsafe { self.root_mut() }, &key) {
+            Found(kv) => Some(kv),
+            GoDown(edge) => kv_after_leaf_edge(edge),
+        };
+        Some((key, val))
+    }
+
+    // Advances to the next element for which `pred` returns true, starting from and
+    // including the current one, and removes it. Leaves the cursor on the "ghost"
+    // non-element if there is no such element.
+    pub(super) fn remove_next_matching<F>(&mut self, pred: &mut F) -> Option<(K, V)>
+        where F: FnMut(&K, &mut V) -> bool,
+    {
+        loop {
+            let matches = {
+                let (k, v) = self.key_value_mut()?;
+                pred(k, v)
+            };
+            if matches {
+                return self.remove_current();
+            }
+            self.move_next();
+        }
+    }
+}
+
+#[unstable(feature = "btree_drain_filter", issue = "0")]
+impl<K: Ord, V, F, A: Alloc> Iterator for DrainFilter<'_, K, V, F, A>
+    where F: FnMut(&K, &mut V) -> bool,
+{
+    type Item = (K, V);
+
+    fn next(&mut self) -> Option<(K, V)> {
+        self.cursor.remove_next_matching(&mut self.pred)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, Some(*self.cursor.length))
+    }
+}
+
+#[unstable(feature = "btree_drain_filter", issue = "0")]
+impl<K: Ord, V, F, A: Alloc> Drop for DrainFilter<'_, K, V, F, A>
+    where F: FnMut(&K, &mut V) -> bool,
+{
+    fn drop(&mut self) {
+        self.for_each(drop);
+    }
+}
+
+#[unstable(feature = "btree_drain_filter", issue = "0")]
+impl<K: Ord, V, F, A: Alloc> FusedIterator for DrainFilter<'_, K, V, F, A>
+    where F: FnMut(&K, &mut V) -> bool,
+{}
+
 #[stable(feature = "rust1", since = "1.0.0")]
 impl<K: Ord, V> FromIterator<(K, V)> for BTreeMap<K, V> {
     fn from_iter<T: IntoIterator<Item = (K, V)>>(iter: T) -> BTreeMap<K, V> {
@@ -1752,7 +2602,7 @@ impl<K: Ord, V> FromIterator<(K, V)> for BTreeMap<K, V> {
 }
 
 #[stable(feature = "rust1", since = "1.0.0")]
-impl<K: Ord, V> Extend<(K, V)> for BTreeMap<K, V> {
+impl<K: Ord, V, A: Alloc + Clone> Extend<(K, V)> for BTreeMap<K, V, A> {
     #[inline]
     fn extend<T: IntoIterator<Item = (K, V)>>(&mut self, iter: T) {
         iter.into_iter().for_each(move |(k, v)| {
@@ -1762,14 +2612,16 @@ impl<K: Ord, V> Extend<(K, V)> for BTreeMap<K, V> {
 }
 
 #[stable(feature = "extend_ref", since = "1.2.0")]
-impl<'a, K: Ord + Copy, V: Copy> Extend<(&'a K, &'a V)> for BTreeMap<K, V> {
+impl<'a, K: Ord + Copy, V: Copy, A: Alloc + Clone> Extend<(&'a K, &'a V)>
+    for BTreeMap<K, V, A>
+{
     fn extend<I: IntoIterator<Item = (&'a K, &'a V)>>(&mut self, iter: I) {
         self.extend(iter.into_iter().map(|(&key, &value)| (key, value)));
     }
 }
 
 #[stable(feature = "rust1", since = "1.0.0")]
-impl<K: Hash, V: Hash> Hash for BTreeMap<K, V> {
+impl<K: Hash, V: Hash, A: Alloc + Clone> Hash for BTreeMap<K, V, A> {
     fn hash<H: Hasher>(&self, state: &mut H) {
         for elt in self {
             elt.hash(state);
@@ -1786,40 +2638,40 @@ impl<K: Ord, V> Default for BTreeMap<K, V> {
 }
 
 #[stable(feature = "rust1", since = "1.0.0")]
-impl<K: PartialEq, V: PartialEq> PartialEq for BTreeMap<K, V> {
-    fn eq(&self, other: &BTreeMap<K, V>) -> bool {
+impl<K: PartialEq, V: PartialEq, A: Alloc + Clone> PartialEq for BTreeMap<K, V, A> {
+    fn eq(&self, other: &BTreeMap<K, V, A>) -> bool {
         self.len() == other.len() && self.iter().zip(other).all(|(a, b)| a == b)
     }
 }
 
 #[stable(feature = "rust1", since = "1.0.0")]
-impl<K: Eq, V: Eq> Eq for BTreeMap<K, V> {}
+impl<K: Eq, V: Eq, A: Alloc + Clone> Eq for BTreeMap<K, V, A> {}
 
 #[stable(feature = "rust1", since = "1.0.0")]
-impl<K: PartialOrd, V: PartialOrd> PartialOrd for BTreeMap<K, V> {
+impl<K: PartialOrd, V: PartialOrd, A: Alloc + Clone> PartialOrd for BTreeMap<K, V, A> {
     #[inline]
-    fn partial_cmp(&self, other: &BTreeMap<K, V>) -> Option<Ordering> {
+    fn partial_cmp(&self, other: &BTreeMap<K, V, A>) -> Option<Ordering> {
         self.iter().partial_cmp(other.iter())
     }
 }
 
 #[stable(feature = "rust1", since = "1.0.0")]
-impl<K: Ord, V: Ord> Ord for BTreeMap<K, V> {
+impl<K: Ord, V: Ord, A: Alloc + Clone> Ord for BTreeMap<K, V, A> {
     #[inline]
-    fn cmp(&self, other: &BTreeMap<K, V>) -> Ordering {
+    fn cmp(&self, other: &BTreeMap<K, V, A>) -> Ordering {
         self.iter().cmp(other.iter())
     }
 }
 
 #[stable(feature = "rust1", since = "1.0.0")]
-impl<K: Debug, V: Debug> Debug for BTreeMap<K, V> {
+impl<K: Debug, V: Debug, A: Alloc + Clone> Debug for BTreeMap<K, V, A> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_map().entries(self.iter()).finish()
     }
 }
 
 #[stable(feature = "rust1", since = "1.0.0")]
-impl<K: Ord, Q: ?Sized, V> Index<&Q> for BTreeMap<K, V>
+impl<K: Ord, Q: ?Sized, V, A: Alloc + Clone> Index<&Q> for BTreeMap<K, V, A>
     where K: Borrow<Q>,
           Q: Ord
 {
@@ -1862,6 +2714,94 @@ fn last_leaf_edge<BorrowType, K, V>
     }
 }
 
+// Walks up from a leaf edge to the first key/value pair to its right, if any.
+fn kv_after_leaf_edge<BorrowType, K, V>
+    (edge: Handle<NodeRef<BorrowType, K, V, marker::Leaf>, marker::Edge>)
+     -> Option<Handle<NodeRef<BorrowType, K, V, marker::LeafOrInternal>, marker::KV>> {
+    let mut edge = edge.forget_node_type();
+    loop {
+        match edge.right_kv() {
+            Ok(kv) => return Some(kv),
+            Err(last_edge) => match last_edge.into_node().ascend() {
+                Ok(parent_edge) => edge = parent_edge.forget_node_type(),
+                Err(_) => return None,
+            }
+        }
+    }
+}
+
+// Walks up from a leaf edge to the first key/value pair to its left, if any.
+fn kv_before_leaf_edge<BorrowType, K, V>
+    (edge: Handle<NodeRef<BorrowType, K, V, marker::Leaf>, marker::Edge>)
+     -> Option<Handle<NodeRef<BorrowType, K, V, marker::LeafOrInternal>, marker::KV>> {
+    let mut edge = edge.forget_node_type();
+    loop {
+        match edge.left_kv() {
+            Ok(kv) => return Some(kv),
+            Err(first_edge) => match first_edge.into_node().ascend() {
+                Ok(parent_edge) => edge = parent_edge.forget_node_type(),
+                Err(_) => return None,
+            }
+        }
+    }
+}
+
+fn next_kv<BorrowType, K, V>
+    (kv: Handle<NodeRef<BorrowType, K, V, marker::LeafOrInternal>, marker::KV>)
+     -> Option<Handle<NodeRef<BorrowType, K, V, marker::LeafOrInternal>, marker::KV>> {
+    match kv.right_edge().force() {
+        Leaf(edge) => kv_after_leaf_edge(edge),
+        Internal(edge) => kv_after_leaf_edge(first_leaf_edge(edge.descend())),
+    }
+}
+
+fn prev_kv<BorrowType, K, V>
+    (kv: Handle<NodeRef<BorrowType, K, V, marker::LeafOrInternal>, marker::KV>)
+     -> Option<Handle<NodeRef<BorrowType, K, V, marker::LeafOrInternal>, marker::KV>> {
+    match kv.left_edge().force() {
+        Leaf(edge) => kv_before_leaf_edge(edge),
+        Internal(edge) => kv_before_leaf_edge(last_leaf_edge(edge.descend())),
+    }
+}
+
+fn lower_bound_kv<BorrowType, K, V, Q: ?Sized>(
+    root: NodeRef<BorrowType, K, V, marker::LeafOrInternal>,
+    bound: Bound<&Q>
+) -> Option<Handle<NodeRef<BorrowType, K, V, marker::LeafOrInternal>, marker::KV>>
+        where Q: Ord, K: Borrow<Q>
+{
+    match bound {
+        Included(key) => match search::search_tree(root, key) {
+            Found(kv) => Some(kv),
+            GoDown(edge) => kv_after_leaf_edge(edge),
+        },
+        Excluded(key) => match search::search_tree(root, key) {
+            Found(kv) => next_kv(kv),
+            GoDown(edge) => kv_after_leaf_edge(edge),
+        },
+        Unbounded => kv_after_leaf_edge(first_leaf_edge(root)),
+    }
+}
+
+fn upper_bound_kv<BorrowType, K, V, Q: ?Sized>(
+    root: NodeRef<BorrowType, K, V, marker::LeafOrInternal>,
+    bound: Bound<&Q>
+) -> Option<Handle<NodeRef<BorrowType, K, V, marker::LeafOrInternal>, marker::KV>>
+        where Q: Ord, K: Borrow<Q>
+{
+    match bound {
+        Included(key) => match search::search_tree(root, key) {
+            Found(kv) => Some(kv),
+            GoDown(edge) => kv_before_leaf_edge(edge),
+        },
+        Excluded(key) => match search::search_tree(root, key) {
+            Found(kv) => prev_kv(kv),
+            GoDown(edge) => kv_before_leaf_edge(edge),
+        },
+        Unbounded => kv_before_leaf_edge(last_leaf_edge(root)),
+    }
+}
+
 fn range_search<BorrowType, K, V, Q: ?Sized, R: RangeBounds<Q>>(
     root1: NodeRef<BorrowType, K, V, marker::LeafOrInternal>,
     root2: NodeRef<BorrowType, K, V, marker::LeafOrInternal>,
@@ -1947,7 +2887,7 @@ unsafe fn unwrap_unchecked<T>(val: Option<T>) -> T {
     })
 }
 
-impl<K, V> BTreeMap<K, V> {
+impl<K, V, A: Alloc + Clone> BTreeMap<K, V, A> {
     /// Gets an iterator over the entries of the map, sorted by key.
     ///
     /// # Examples
@@ -2120,9 +3060,16 @@ impl<K, V> BTreeMap<K, V> {
     pub fn is_empty(&self) -> bool {
         self.len() == 0
     }
+
+    /// Returns a reference to the allocator backing this map.
+    #[inline]
+    #[unstable(feature = "allocator_api", issue = "32838")]
+    pub fn alloc(&self) -> &A {
+        &self.alloc
+    }
 }
 
-impl<'a, K: Ord, V> Entry<'a, K, V> {
+impl<'a, K: Ord, V, A: Alloc> Entry<'a, K, V, A> {
     /// Ensures a value is in the entry by inserting the default if empty, and returns
     /// a mutable reference to the value in the entry.
     ///
@@ -2219,7 +3166,7 @@ impl<'a, K: Ord, V> Entry<'a, K, V> {
     }
 }
 
-impl<'a, K: Ord, V: Default> Entry<'a, K, V> {
+impl<'a, K: Ord, V: Default, A: Alloc> Entry<'a, K, V, A> {
     #[stable(feature = "entry_or_default", since = "1.28.0")]
     /// Ensures a value is in the entry by inserting the default value if empty,
     /// and returns a mutable reference to the value in the entry.
@@ -2245,7 +3192,7 @@ impl<'a, K: Ord, V: Default> Entry<'a, K, V> {
 
 }
 
-impl<'a, K: Ord, V> VacantEntry<'a, K, V> {
+impl<'a, K: Ord, V, A: Alloc> VacantEntry<'a, K, V, A> {
     /// Gets a reference to the key that would be used when inserting a value
     /// through the VacantEntry.
     ///
@@ -2308,7 +3255,7 @@ impl<'a, K: Ord, V> VacantEntry<'a, K, V> {
         let mut ins_v;
         let mut ins_edge;
 
-        let mut cur_parent = match self.handle.insert(self.key, value) {
+        let mut cur_parent = match self.handle.insert(self.key, value, self.alloc) {
             (Fit(handle), _) => return handle.into_kv_mut().1,
             (Split(left, k, v, right), ptr) => {
                 ins_k = k;
@@ -2322,7 +3269,7 @@ impl<'a, K: Ord, V> VacantEntry<'a, K, V> {
         loop {
             match cur_parent {
                 Ok(parent) => {
-                    match parent.insert(ins_k, ins_v, ins_edge) {
+                    match parent.insert(ins_k, ins_v, ins_edge, self.alloc) {
                         Fit(_) => return unsafe { &mut *out_ptr },
                         Split(left, k, v, right) => {
                             ins_k = k;
@@ -2333,7 +3280,7 @@ impl<'a, K: Ord, V> VacantEntry<'a, K, V> {
                     }
                 }
                 Err(root) => {
-                    root.push_level().push(ins_k, ins_v, ins_edge);
+                    root.push_level(self.alloc).push(ins_k, ins_v, ins_edge);
                     return unsafe { &mut *out_ptr };
                 }
             }
@@ -2341,7 +3288,7 @@ impl<'a, K: Ord, V> VacantEntry<'a, K, V> {
     }
 }
 
-impl<'a, K: Ord, V> OccupiedEntry<'a, K, V> {
+impl<'a, K: Ord, V, A: Alloc> OccupiedEntry<'a, K, V, A> {
     /// Gets a reference to the key in the entry.
     ///
     /// # Examples
@@ -2530,13 +3477,13 @@ impl<'a, K: Ord, V> OccupiedEntry<'a, K, V> {
         // Handle underflow
         let mut cur_node = small_leaf.forget_type();
         while cur_node.len() < node::CAPACITY / 2 {
-            match handle_underfull_node(cur_node) {
+            match handle_underfull_node(cur_node, self.alloc) {
                 AtRoot => break,
                 EmptyParent(_) => unreachable!(),
                 Merged(parent) => {
                     if parent.len() == 0 {
                         // We must be at the root
-                        parent.into_root_mut().pop_level();
+                        parent.into_root_mut().pop_level(self.alloc);
                         break;
                     } else {
                         cur_node = parent.forget_type();
@@ -2557,8 +3504,10 @@ enum UnderflowResult<'a, K, V> {
     Stole(NodeRef<marker::Mut<'a>, K, V, marker::Internal>),
 }
 
-fn handle_underfull_node<K, V>(node: NodeRef<marker::Mut<'_>, K, V, marker::LeafOrInternal>)
-                               -> UnderflowResult<'_, K, V> {
+fn handle_underfull_node<'a, K, V, A: Alloc>(
+    node: NodeRef<marker::Mut<'a>, K, V, marker::LeafOrInternal>,
+    alloc: &mut A,
+) -> UnderflowResult<'a, K, V> {
     let parent = if let Ok(parent) = node.ascend() {
         parent
     } else {
@@ -2578,7 +3527,7 @@ fn handle_underfull_node<K, V>(node: NodeRef<marker::Mut<'_>, K, V, marker::Leaf
     };
 
     if handle.can_merge() {
-        Merged(handle.merge().into_node())
+        Merged(handle.merge(alloc).into_node())
     } else {
         if is_left {
             handle.steal_left();
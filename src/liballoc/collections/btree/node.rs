@@ -36,8 +36,7 @@ use core::mem::{self, MaybeUninit};
 use core::ptr::{self, Unique, NonNull};
 use core::slice;
 
-use crate::alloc::{Global, Alloc, Layout};
-use crate::boxed::Box;
+use crate::alloc::{Alloc, Layout, handle_alloc_error};
 
 const B: usize = 6;
 pub const MIN_LEN: usize = B - 1;
@@ -172,15 +171,25 @@ struct BoxedNode<K, V> {
     ptr: Unique<LeafNode<K, V>>
 }
 
+/// Moves `node` into memory allocated by `alloc`. This is `Box::new_in` for an allocator that
+/// is only borrowed, since the tree keeps its allocator and lends it out for every operation
+/// that allocates or frees nodes.
+fn alloc_node<N, A: Alloc>(node: N, alloc: &mut A) -> NonNull<N> {
+    let layout = Layout::new::<N>();
+    unsafe {
+        let ptr = alloc.alloc(layout).unwrap_or_else(|_| handle_alloc_error(layout)).cast::<N>();
+        ptr::write(ptr.as_ptr(), node);
+        ptr
+    }
+}
+
 impl<K, V> BoxedNode<K, V> {
-    fn from_leaf(node: Box<LeafNode<K, V>>) -> Self {
-        BoxedNode { ptr: Box::into_unique(node) }
+    fn from_leaf(node: NonNull<LeafNode<K, V>>) -> Self {
+        BoxedNode { ptr: Unique::from(node) }
     }
 
-    fn from_internal(node: Box<InternalNode<K, V>>) -> Self {
-        unsafe {
-            BoxedNode { ptr: Unique::new_unchecked(Box::into_raw(node) as *mut LeafNode<K, V>) }
-        }
+    fn from_internal(node: NonNull<InternalNode<K, V>>) -> Self {
+        BoxedNode { ptr: Unique::from(node.cast()) }
     }
 
     unsafe fn from_ptr(ptr: NonNull<LeafNode<K, V>>) -> Self {
@@ -218,9 +227,9 @@ impl<K, V> Root<K, V> {
         }
     }
 
-    pub fn new_leaf() -> Self {
+    pub fn new_leaf<A: Alloc>(alloc: &mut A) -> Self {
         Root {
-            node: BoxedNode::from_leaf(Box::new(unsafe { LeafNode::new() })),
+            node: BoxedNode::from_leaf(alloc_node(unsafe { LeafNode::new() }, alloc)),
             height: 0
         }
     }
@@ -257,11 +266,13 @@ impl<K, V> Root<K, V> {
 
     /// Adds a new internal node with a single edge, pointing to the previous root, and make that
     /// new node the root. This increases the height by 1 and is the opposite of `pop_level`.
-    pub fn push_level(&mut self)
+    pub fn push_level<A: Alloc>(&mut self, alloc: &mut A)
             -> NodeRef<marker::Mut<'_>, K, V, marker::Internal> {
         debug_assert!(!self.is_shared_root());
-        let mut new_node = Box::new(unsafe { InternalNode::new() });
-        new_node.edges[0].write(unsafe { BoxedNode::from_ptr(self.node.as_ptr()) });
+        let mut new_node = alloc_node(unsafe { InternalNode::new() }, alloc);
+        unsafe {
+            new_node.as_mut().edges[0].write(BoxedNode::from_ptr(self.node.as_ptr()));
+        }
 
         self.node = BoxedNode::from_internal(new_node);
         self.height += 1;
@@ -284,7 +295,7 @@ impl<K, V> Root<K, V> {
     /// the tree consists only of a leaf node. As it is intended only to be called when the root
     /// has only one edge, no cleanup is done on any of the other children are elements of the root.
     /// This decreases the height by 1 and is the opposite of `push_level`.
-    pub fn pop_level(&mut self) {
+    pub fn pop_level<A: Alloc>(&mut self, alloc: &mut A) {
         debug_assert!(self.height > 0);
 
         let top = self.node.ptr;
@@ -300,7 +311,7 @@ impl<K, V> Root<K, V> {
         unsafe { (*self.as_mut().as_leaf_mut()).parent = ptr::null(); }
 
         unsafe {
-            Global.dealloc(NonNull::from(top).cast(), Layout::new::<InternalNode<K, V>>());
+            alloc.dealloc(NonNull::from(top).cast(), Layout::new::<InternalNode<K, V>>());
         }
     }
 }
@@ -488,7 +499,7 @@ impl<K, V> NodeRef<marker::Owned, K, V, marker::Leaf> {
     /// Similar to `ascend`, gets a reference to a node's parent node, but also
     /// deallocate the current node in the process. This is unsafe because the
     /// current node will still be accessible despite being deallocated.
-    pub unsafe fn deallocate_and_ascend(self) -> Option<
+    pub unsafe fn deallocate_and_ascend<A: Alloc>(self, alloc: &mut A) -> Option<
         Handle<
             NodeRef<
                 marker::Owned,
@@ -501,7 +512,7 @@ impl<K, V> NodeRef<marker::Owned, K, V, marker::Leaf> {
         debug_assert!(!self.is_shared_root());
         let node = self.node;
         let ret = self.ascend().ok();
-        Global.dealloc(node.cast(), Layout::new::<LeafNode<K, V>>());
+        alloc.dealloc(node.cast(), Layout::new::<LeafNode<K, V>>());
         ret
     }
 }
@@ -510,7 +521,7 @@ impl<K, V> NodeRef<marker::Owned, K, V, marker::Internal> {
     /// Similar to `ascend`, gets a reference to a node's parent node, but also
     /// deallocate the current node in the process. This is unsafe because the
     /// current node will still be accessible despite being deallocated.
-    pub unsafe fn deallocate_and_ascend(self) -> Option<
+    pub unsafe fn deallocate_and_ascend<A: Alloc>(self, alloc: &mut A) -> Option<
         Handle<
             NodeRef<
                 marker::Owned,
@@ -522,7 +533,7 @@ impl<K, V> NodeRef<marker::Owned, K, V, marker::Internal> {
     > {
         let node = self.node;
         let ret = self.ascend().ok();
-        Global.dealloc(node.cast(), Layout::new::<InternalNode<K, V>>());
+        alloc.dealloc(node.cast(), Layout::new::<InternalNode<K, V>>());
         ret
     }
 }
@@ -956,6 +967,19 @@ impl<BorrowType, K, V, NodeType, HandleType>
             _marker: PhantomData
         }
     }
+
+    /// Removes any static information about whether the node this handle points into
+    /// is a `Leaf` or an `Internal` node.
+    pub fn forget_node_type(self)
+            -> Handle<NodeRef<BorrowType, K, V, marker::LeafOrInternal>, HandleType> {
+
+        // We can't use Handle::new_kv or Handle::new_edge because we don't know our type
+        Handle {
+            node: self.node.forget_type(),
+            idx: self.idx,
+            _marker: PhantomData
+        }
+    }
 }
 
 impl<'a, K, V, NodeType, HandleType>
@@ -1045,7 +1069,7 @@ impl<'a, K, V> Handle<NodeRef<marker::Mut<'a>, K, V, marker::Leaf>, marker::Edge
     /// this edge. This method splits the node if there isn't enough room.
     ///
     /// The returned pointer points to the inserted value.
-    pub fn insert(mut self, key: K, val: V)
+    pub fn insert<A: Alloc>(mut self, key: K, val: V, alloc: &mut A)
             -> (InsertResult<'a, K, V, marker::Leaf>, *mut V) {
 
         if self.node.len() < CAPACITY {
@@ -1053,7 +1077,7 @@ impl<'a, K, V> Handle<NodeRef<marker::Mut<'a>, K, V, marker::Leaf>, marker::Edge
             (InsertResult::Fit(Handle::new_kv(self.node, self.idx)), ptr)
         } else {
             let middle = Handle::new_kv(self.node, B);
-            let (mut left, k, v, mut right) = middle.split();
+            let (mut left, k, v, mut right) = middle.split(alloc);
             let ptr = if self.idx <= B {
                 unsafe {
                     Handle::new_edge(left.reborrow_mut(), self.idx).insert_fit(key, val)
@@ -1122,7 +1146,7 @@ impl<'a, K, V> Handle<NodeRef<marker::Mut<'a>, K, V, marker::Internal>, marker::
     /// Inserts a new key/value pair and an edge that will go to the right of that new pair
     /// between this edge and the key/value pair to the right of this edge. This method splits
     /// the node if there isn't enough room.
-    pub fn insert(mut self, key: K, val: V, edge: Root<K, V>)
+    pub fn insert<A: Alloc>(mut self, key: K, val: V, edge: Root<K, V>, alloc: &mut A)
             -> InsertResult<'a, K, V, marker::Internal> {
 
         // Necessary for correctness, but this is an internal module
@@ -1133,7 +1157,7 @@ impl<'a, K, V> Handle<NodeRef<marker::Mut<'a>, K, V, marker::Internal>, marker::
             InsertResult::Fit(Handle::new_kv(self.node, self.idx))
         } else {
             let middle = Handle::new_kv(self.node, B);
-            let (mut left, k, v, mut right) = middle.split();
+            let (mut left, k, v, mut right) = middle.split(alloc);
             if self.idx <= B {
                 unsafe {
                     Handle::new_edge(left.reborrow_mut(), self.idx).insert_fit(key, val, edge);
@@ -1209,11 +1233,11 @@ impl<'a, K, V> Handle<NodeRef<marker::Mut<'a>, K, V, marker::Leaf>, marker::KV>
     /// - The key and value pointed to by this handle and extracted.
     /// - All the key/value pairs to the right of this handle are put into a newly
     ///   allocated node.
-    pub fn split(mut self)
+    pub fn split<A: Alloc>(mut self, alloc: &mut A)
             -> (NodeRef<marker::Mut<'a>, K, V, marker::Leaf>, K, V, Root<K, V>) {
         debug_assert!(!self.node.is_shared_root());
         unsafe {
-            let mut new_node = Box::new(LeafNode::new());
+            let mut new_node = alloc_node(LeafNode::new(), alloc);
 
             let k = ptr::read(self.node.keys().get_unchecked(self.idx));
             let v = ptr::read(self.node.vals().get_unchecked(self.idx));
@@ -1222,17 +1246,17 @@ impl<'a, K, V> Handle<NodeRef<marker::Mut<'a>, K, V, marker::Leaf>, marker::KV>
 
             ptr::copy_nonoverlapping(
                 self.node.keys().as_ptr().add(self.idx + 1),
-                new_node.keys.as_mut_ptr() as *mut K,
+                new_node.as_mut().keys.as_mut_ptr() as *mut K,
                 new_len
             );
             ptr::copy_nonoverlapping(
                 self.node.vals().as_ptr().add(self.idx + 1),
-                new_node.vals.as_mut_ptr() as *mut V,
+                new_node.as_mut().vals.as_mut_ptr() as *mut V,
                 new_len
             );
 
             (*self.node.as_leaf_mut()).len = self.idx as u16;
-            new_node.len = new_len as u16;
+            new_node.as_mut().len = new_len as u16;
 
             (
                 self.node,
@@ -1267,10 +1291,10 @@ impl<'a, K, V> Handle<NodeRef<marker::Mut<'a>, K, V, marker::Internal>, marker::
     /// - The key and value pointed to by this handle and extracted.
     /// - All the edges and key/value pairs to the right of this handle are put into
     ///   a newly allocated node.
-    pub fn split(mut self)
+    pub fn split<A: Alloc>(mut self, alloc: &mut A)
             -> (NodeRef<marker::Mut<'a>, K, V, marker::Internal>, K, V, Root<K, V>) {
         unsafe {
-            let mut new_node = Box::new(InternalNode::new());
+            let mut new_node = alloc_node(InternalNode::new(), alloc);
 
             let k = ptr::read(self.node.keys().get_unchecked(self.idx));
             let v = ptr::read(self.node.vals().get_unchecked(self.idx));
@@ -1280,22 +1304,22 @@ impl<'a, K, V> Handle<NodeRef<marker::Mut<'a>, K, V, marker::Internal>, marker::
 
             ptr::copy_nonoverlapping(
                 self.node.keys().as_ptr().add(self.idx + 1),
-                new_node.data.keys.as_mut_ptr() as *mut K,
+                new_node.as_mut().data.keys.as_mut_ptr() as *mut K,
                 new_len
             );
             ptr::copy_nonoverlapping(
                 self.node.vals().as_ptr().add(self.idx + 1),
-                new_node.data.vals.as_mut_ptr() as *mut V,
+                new_node.as_mut().data.vals.as_mut_ptr() as *mut V,
                 new_len
             );
             ptr::copy_nonoverlapping(
                 self.node.as_internal().edges.as_ptr().add(self.idx + 1),
-                new_node.edges.as_mut_ptr(),
+                new_node.as_mut().edges.as_mut_ptr(),
                 new_len + 1
             );
 
             (*self.node.as_leaf_mut()).len = self.idx as u16;
-            new_node.data.len = new_len as u16;
+            new_node.as_mut().data.len = new_len as u16;
 
             let mut new_root = Root {
                 node: BoxedNode::from_internal(new_node),
@@ -1336,7 +1360,7 @@ impl<'a, K, V> Handle<NodeRef<marker::Mut<'a>, K, V, marker::Internal>, marker::
     /// child of the underlying node, returning an edge referencing that new child.
     ///
     /// Assumes that this edge `.can_merge()`.
-    pub fn merge(mut self)
+    pub fn merge<A: Alloc>(mut self, alloc: &mut A)
             -> Handle<NodeRef<marker::Mut<'a>, K, V, marker::Internal>, marker::Edge> {
         let self1 = unsafe { ptr::read(&self) };
         let self2 = unsafe { ptr::read(&self) };
@@ -1390,12 +1414,12 @@ impl<'a, K, V> Handle<NodeRef<marker::Mut<'a>, K, V, marker::Internal>, marker::
                     ).correct_parent_link();
                 }
 
-                Global.dealloc(
+                alloc.dealloc(
                     right_node.node.cast(),
                     Layout::new::<InternalNode<K, V>>(),
                 );
             } else {
-                Global.dealloc(
+                alloc.dealloc(
                     right_node.node.cast(),
                     Layout::new::<LeafNode<K, V>>(),
                 );
@@ -21,7 +21,7 @@ use core::mem;
 use core::ptr::NonNull;
 
 use crate::boxed::Box;
-use super::SpecExtend;
+use super::{CollectionAllocErr, SpecExtend};
 
 /// A doubly-linked list with owned nodes.
 ///
@@ -105,6 +105,40 @@ impl<T: fmt::Debug> fmt::Debug for IterMut<'_, T> {
     }
 }
 
+/// A cursor over a `LinkedList` with editing operations.
+///
+/// A `CursorMut` is like an iterator, except that it can freely seek back-and-forth, and can
+/// safely mutate the list during iteration. This is because the lifetime of its yielded
+/// references is tied to its own lifetime, instead of just the underlying list. This means
+/// cursors cannot yield multiple elements at once.
+///
+/// Cursors always point to an element in the list, or to a "ghost" non-element that is
+/// logically located between the tail and the head of the list, so moving past either end
+/// wraps around through it.
+///
+/// This `struct` is created by the [`cursor_front_mut`] and [`cursor_back_mut`] methods on
+/// [`LinkedList`]. See their documentation for more.
+///
+/// [`cursor_front_mut`]: struct.LinkedList.html#method.cursor_front_mut
+/// [`cursor_back_mut`]: struct.LinkedList.html#method.cursor_back_mut
+/// [`LinkedList`]: struct.LinkedList.html
+#[unstable(feature = "linked_list_cursors", issue = "0")]
+pub struct CursorMut<'a, T: 'a> {
+    index: usize,
+    current: Option<NonNull<Node<T>>>,
+    list: &'a mut LinkedList<T>,
+}
+
+#[unstable(feature = "linked_list_cursors", issue = "0")]
+impl<T: fmt::Debug> fmt::Debug for CursorMut<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("CursorMut")
+         .field(&self.list)
+         .field(&self.index())
+         .finish()
+    }
+}
+
 /// An owning iterator over the elements of a `LinkedList`.
 ///
 /// This `struct` is created by the [`into_iter`] method on [`LinkedList`][`LinkedList`]
@@ -250,6 +284,48 @@ impl<T> LinkedList<T> {
 
         self.len -= 1;
     }
+
+    /// Splices a series of nodes between two existing nodes.
+    ///
+    /// Warning: this will not check that the provided nodes belong to the current list, or
+    /// that `existing_prev` and `existing_next` are adjacent.
+    ///
+    /// This method takes care not to create mutable references to `element`, to
+    /// maintain validity of aliasing pointers.
+    #[inline]
+    unsafe fn splice_nodes(&mut self,
+                           existing_prev: Option<NonNull<Node<T>>>,
+                           existing_next: Option<NonNull<Node<T>>>,
+                           splice_start: NonNull<Node<T>>,
+                           splice_end: NonNull<Node<T>>,
+                           splice_length: usize) {
+        // Not creating new mutable (unique!) references overlapping `element`.
+        match existing_prev {
+            Some(prev) => (*prev.as_ptr()).next = Some(splice_start),
+            None => self.head = Some(splice_start),
+        }
+        match existing_next {
+            Some(next) => (*next.as_ptr()).prev = Some(splice_end),
+            None => self.tail = Some(splice_end),
+        }
+        (*splice_start.as_ptr()).prev = existing_prev;
+        (*splice_end.as_ptr()).next = existing_next;
+
+        self.len += splice_length;
+    }
+
+    /// Detaches all nodes from the list, returning the first node, the last node and the
+    /// number of nodes, or `None` if the list was empty. The list is left empty.
+    #[inline]
+    fn detach_all_nodes(mut self) -> Option<(NonNull<Node<T>>, NonNull<Node<T>>, usize)> {
+        let head = self.head.take();
+        let tail = self.tail.take();
+        let len = mem::replace(&mut self.len, 0);
+        match (head, tail) {
+            (Some(head), Some(tail)) => Some((head, tail, len)),
+            _ => None,
+        }
+    }
 }
 
 #[stable(feature = "rust1", since = "1.0.0")]
@@ -614,6 +690,30 @@ impl<T> LinkedList<T> {
         self.push_front_node(box Node::new(elt));
     }
 
+    /// Adds an element first in the list, returning an error instead of
+    /// aborting if allocating the new node fails.
+    ///
+    /// On error, `elt` is dropped and the list is left unchanged.
+    ///
+    /// This operation should compute in O(1) time.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(try_reserve)]
+    /// use std::collections::LinkedList;
+    ///
+    /// let mut dl = LinkedList::new();
+    ///
+    /// dl.try_push_front(2).expect("why is the test harness OOMing on one node?");
+    /// assert_eq!(dl.front().unwrap(), &2);
+    /// ```
+    #[unstable(feature = "try_reserve", reason = "new API", issue="48043")]
+    pub fn try_push_front(&mut self, elt: T) -> Result<(), CollectionAllocErr> {
+        self.push_front_node(Box::try_new(Node::new(elt))?);
+        Ok(())
+    }
+
     /// Removes the first element and returns it, or `None` if the list is
     /// empty.
     ///
@@ -657,6 +757,30 @@ impl<T> LinkedList<T> {
         self.push_back_node(box Node::new(elt));
     }
 
+    /// Appends an element to the back of a list, returning an error instead
+    /// of aborting if allocating the new node fails.
+    ///
+    /// On error, `elt` is dropped and the list is left unchanged.
+    ///
+    /// This operation should compute in O(1) time.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(try_reserve)]
+    /// use std::collections::LinkedList;
+    ///
+    /// let mut d = LinkedList::new();
+    /// d.try_push_back(1).expect("why is the test harness OOMing on one node?");
+    /// d.try_push_back(3).expect("why is the test harness OOMing on two nodes?");
+    /// assert_eq!(3, *d.back().unwrap());
+    /// ```
+    #[unstable(feature = "try_reserve", reason = "new API", issue="48043")]
+    pub fn try_push_back(&mut self, elt: T) -> Result<(), CollectionAllocErr> {
+        self.push_back_node(Box::try_new(Node::new(elt))?);
+        Ok(())
+    }
+
     /// Removes the last element from a list and returns it, or `None` if
     /// it is empty.
     ///
@@ -800,6 +924,62 @@ impl<T> LinkedList<T> {
             old_len: old_len,
         }
     }
+
+    /// Provides a cursor with editing operations at the front element.
+    ///
+    /// The cursor is pointing to the "ghost" non-element if the list is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(linked_list_cursors)]
+    /// use std::collections::LinkedList;
+    ///
+    /// let mut list: LinkedList<u32> = vec![1, 2, 4].into_iter().collect();
+    ///
+    /// let mut cursor = list.cursor_front_mut();
+    /// cursor.move_next();
+    /// cursor.insert_after(3);
+    ///
+    /// assert_eq!(list.into_iter().collect::<Vec<_>>(), vec![1, 2, 3, 4]);
+    /// ```
+    #[inline]
+    #[unstable(feature = "linked_list_cursors", issue = "0")]
+    pub fn cursor_front_mut(&mut self) -> CursorMut<'_, T> {
+        CursorMut {
+            index: 0,
+            current: self.head,
+            list: self,
+        }
+    }
+
+    /// Provides a cursor with editing operations at the back element.
+    ///
+    /// The cursor is pointing to the "ghost" non-element if the list is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(linked_list_cursors)]
+    /// use std::collections::LinkedList;
+    ///
+    /// let mut list: LinkedList<u32> = vec![1, 2, 3].into_iter().collect();
+    ///
+    /// let mut cursor = list.cursor_back_mut();
+    /// assert_eq!(cursor.remove_current(), Some(3));
+    /// assert_eq!(cursor.current(), None);
+    ///
+    /// assert_eq!(list.into_iter().collect::<Vec<_>>(), vec![1, 2]);
+    /// ```
+    #[inline]
+    #[unstable(feature = "linked_list_cursors", issue = "0")]
+    pub fn cursor_back_mut(&mut self) -> CursorMut<'_, T> {
+        CursorMut {
+            index: self.len.checked_sub(1).unwrap_or(0),
+            current: self.tail,
+            list: self,
+        }
+    }
 }
 
 #[stable(feature = "rust1", since = "1.0.0")]
@@ -1004,6 +1184,204 @@ impl<T> IterMut<'_, T> {
     }
 }
 
+impl<'a, T> CursorMut<'a, T> {
+    /// Returns the cursor position index within the `LinkedList`.
+    ///
+    /// This returns `None` if the cursor is currently pointing to the
+    /// "ghost" non-element.
+    #[unstable(feature = "linked_list_cursors", issue = "0")]
+    pub fn index(&self) -> Option<usize> {
+        let _ = self.current?;
+        Some(self.index)
+    }
+
+    /// Moves the cursor to the next element of the `LinkedList`.
+    ///
+    /// If the cursor is pointing to the "ghost" non-element then this will move it to
+    /// the first element of the `LinkedList`. If it is pointing to the last
+    /// element of the `LinkedList` then this will move it to the "ghost" non-element.
+    #[unstable(feature = "linked_list_cursors", issue = "0")]
+    pub fn move_next(&mut self) {
+        match self.current.take() {
+            // The "ghost" non-element wraps around to the head of the list.
+            None => {
+                self.current = self.list.head;
+                self.index = 0;
+            }
+            Some(current) => unsafe {
+                self.current = current.as_ref().next;
+                self.index += 1;
+            },
+        }
+    }
+
+    /// Moves the cursor to the previous element of the `LinkedList`.
+    ///
+    /// If the cursor is pointing to the "ghost" non-element then this will move it to
+    /// the last element of the `LinkedList`. If it is pointing to the first
+    /// element of the `LinkedList` then this will move it to the "ghost" non-element.
+    #[unstable(feature = "linked_list_cursors", issue = "0")]
+    pub fn move_prev(&mut self) {
+        match self.current.take() {
+            // The "ghost" non-element wraps around to the tail of the list.
+            None => {
+                self.current = self.list.tail;
+                self.index = self.list.len().checked_sub(1).unwrap_or(0);
+            }
+            Some(current) => unsafe {
+                self.current = current.as_ref().prev;
+                self.index = self.index.checked_sub(1).unwrap_or_else(|| self.list.len());
+            },
+        }
+    }
+
+    /// Returns a reference to the element that the cursor is currently
+    /// pointing to.
+    ///
+    /// This returns `None` if the cursor is currently pointing to the
+    /// "ghost" non-element.
+    #[unstable(feature = "linked_list_cursors", issue = "0")]
+    pub fn current(&mut self) -> Option<&mut T> {
+        unsafe {
+            self.current.map(|current| &mut (*current.as_ptr()).element)
+        }
+    }
+
+    /// Returns a reference to the next element.
+    ///
+    /// If the cursor is pointing to the "ghost" non-element then this returns
+    /// the first element of the `LinkedList`. If it is pointing to the last
+    /// element of the `LinkedList` then this returns `None`.
+    #[unstable(feature = "linked_list_cursors", issue = "0")]
+    pub fn peek_next(&mut self) -> Option<&mut T> {
+        unsafe {
+            let next = match self.current {
+                None => self.list.head,
+                Some(current) => current.as_ref().next,
+            };
+            next.map(|next| &mut (*next.as_ptr()).element)
+        }
+    }
+
+    /// Returns a reference to the previous element.
+    ///
+    /// If the cursor is pointing to the "ghost" non-element then this returns
+    /// the last element of the `LinkedList`. If it is pointing to the first
+    /// element of the `LinkedList` then this returns `None`.
+    #[unstable(feature = "linked_list_cursors", issue = "0")]
+    pub fn peek_prev(&mut self) -> Option<&mut T> {
+        unsafe {
+            let prev = match self.current {
+                None => self.list.tail,
+                Some(current) => current.as_ref().prev,
+            };
+            prev.map(|prev| &mut (*prev.as_ptr()).element)
+        }
+    }
+
+    /// Inserts a new element into the `LinkedList` after the current one.
+    ///
+    /// If the cursor is pointing at the "ghost" non-element then the new element is
+    /// inserted at the front of the `LinkedList`.
+    #[unstable(feature = "linked_list_cursors", issue = "0")]
+    pub fn insert_after(&mut self, item: T) {
+        unsafe {
+            let spliced_node = Box::into_raw_non_null(box Node::new(item));
+            let node_next = match self.current {
+                None => self.list.head,
+                Some(node) => node.as_ref().next,
+            };
+            self.list.splice_nodes(self.current, node_next, spliced_node, spliced_node, 1);
+            if self.current.is_none() {
+                // The "ghost" non-element's index has changed.
+                self.index = self.list.len;
+            }
+        }
+    }
+
+    /// Inserts a new element into the `LinkedList` before the current one.
+    ///
+    /// If the cursor is pointing at the "ghost" non-element then the new element is
+    /// inserted at the end of the `LinkedList`.
+    #[unstable(feature = "linked_list_cursors", issue = "0")]
+    pub fn insert_before(&mut self, item: T) {
+        unsafe {
+            let spliced_node = Box::into_raw_non_null(box Node::new(item));
+            let node_prev = match self.current {
+                None => self.list.tail,
+                Some(node) => node.as_ref().prev,
+            };
+            self.list.splice_nodes(node_prev, self.current, spliced_node, spliced_node, 1);
+            self.index += 1;
+        }
+    }
+
+    /// Removes the current element from the `LinkedList`.
+    ///
+    /// The element that was removed is returned, and the cursor is
+    /// moved to point to the next element in the `LinkedList`.
+    ///
+    /// If the cursor is currently pointing to the "ghost" non-element then no element
+    /// is removed and `None` is returned.
+    #[unstable(feature = "linked_list_cursors", issue = "0")]
+    pub fn remove_current(&mut self) -> Option<T> {
+        let unlinked_node = self.current?;
+        unsafe {
+            self.current = unlinked_node.as_ref().next;
+            self.list.unlink_node(unlinked_node);
+            let unlinked_node = Box::from_raw(unlinked_node.as_ptr());
+            Some(unlinked_node.into_element())
+        }
+    }
+
+    /// Inserts the elements from the given `LinkedList` after the current one.
+    ///
+    /// If the cursor is pointing at the "ghost" non-element then the new elements are
+    /// inserted at the start of the `LinkedList`.
+    ///
+    /// This operation should compute in O(1) time.
+    #[unstable(feature = "linked_list_cursors", issue = "0")]
+    pub fn splice_after(&mut self, list: LinkedList<T>) {
+        unsafe {
+            let (splice_head, splice_tail, splice_len) = match list.detach_all_nodes() {
+                Some(parts) => parts,
+                _ => return,
+            };
+            let node_next = match self.current {
+                None => self.list.head,
+                Some(node) => node.as_ref().next,
+            };
+            self.list.splice_nodes(self.current, node_next, splice_head, splice_tail, splice_len);
+            if self.current.is_none() {
+                // The "ghost" non-element's index has changed.
+                self.index = self.list.len;
+            }
+        }
+    }
+
+    /// Inserts the elements from the given `LinkedList` before the current one.
+    ///
+    /// If the cursor is pointing at the "ghost" non-element then the new elements are
+    /// inserted at the end of the `LinkedList`.
+    ///
+    /// This operation should compute in O(1) time.
+    #[unstable(feature = "linked_list_cursors", issue = "0")]
+    pub fn splice_before(&mut self, list: LinkedList<T>) {
+        unsafe {
+            let (splice_head, splice_tail, splice_len) = match list.detach_all_nodes() {
+                Some(parts) => parts,
+                _ => return,
+            };
+            let node_prev = match self.current {
+                None => self.list.tail,
+                Some(node) => node.as_ref().prev,
+            };
+            self.list.splice_nodes(node_prev, self.current, splice_head, splice_tail, splice_len);
+            self.index += splice_len;
+        }
+    }
+}
+
 /// An iterator produced by calling `drain_filter` on LinkedList.
 #[unstable(feature = "drain_filter", reason = "recently added", issue = "43244")]
 pub struct DrainFilter<'a, T: 'a, F: 'a>
@@ -1245,6 +1623,12 @@ unsafe impl<T: Send> Send for IterMut<'_, T> {}
 #[stable(feature = "rust1", since = "1.0.0")]
 unsafe impl<T: Sync> Sync for IterMut<'_, T> {}
 
+#[unstable(feature = "linked_list_cursors", issue = "0")]
+unsafe impl<T: Send> Send for CursorMut<'_, T> {}
+
+#[unstable(feature = "linked_list_cursors", issue = "0")]
+unsafe impl<T: Sync> Sync for CursorMut<'_, T> {}
+
 #[cfg(test)]
 mod tests {
     use std::thread;
@@ -1513,4 +1897,113 @@ mod tests {
         assert_eq!(deleted, &[1, 2, 3, 4, 5, 6]);
         assert_eq!(m.into_iter().collect::<Vec<_>>(), &[]);
     }
+
+    #[test]
+    fn test_cursor_move_peek() {
+        let mut m: LinkedList<u32> = list_from(&[1, 2, 3, 4, 5, 6]);
+        let mut cursor = m.cursor_front_mut();
+        assert_eq!(cursor.current(), Some(&mut 1));
+        assert_eq!(cursor.peek_next(), Some(&mut 2));
+        assert_eq!(cursor.peek_prev(), None);
+        assert_eq!(cursor.index(), Some(0));
+        cursor.move_prev();
+        assert_eq!(cursor.current(), None);
+        assert_eq!(cursor.peek_next(), Some(&mut 1));
+        assert_eq!(cursor.peek_prev(), Some(&mut 6));
+        assert_eq!(cursor.index(), None);
+        cursor.move_next();
+        cursor.move_next();
+        assert_eq!(cursor.current(), Some(&mut 2));
+        assert_eq!(cursor.peek_next(), Some(&mut 3));
+        assert_eq!(cursor.peek_prev(), Some(&mut 1));
+        assert_eq!(cursor.index(), Some(1));
+
+        let mut cursor = m.cursor_back_mut();
+        assert_eq!(cursor.current(), Some(&mut 6));
+        assert_eq!(cursor.index(), Some(5));
+        cursor.move_next();
+        assert_eq!(cursor.current(), None);
+        assert_eq!(cursor.index(), None);
+        cursor.move_prev();
+        cursor.move_prev();
+        assert_eq!(cursor.current(), Some(&mut 5));
+        assert_eq!(cursor.index(), Some(4));
+
+        let mut m: LinkedList<u32> = LinkedList::new();
+        let mut cursor = m.cursor_front_mut();
+        assert_eq!(cursor.current(), None);
+        assert_eq!(cursor.peek_next(), None);
+        assert_eq!(cursor.peek_prev(), None);
+        cursor.move_next();
+        assert_eq!(cursor.index(), None);
+        assert_eq!(cursor.remove_current(), None);
+    }
+
+    #[test]
+    fn test_cursor_mut_insert() {
+        let mut m: LinkedList<u32> = list_from(&[1, 2, 3, 4, 5, 6]);
+        let mut cursor = m.cursor_front_mut();
+        cursor.insert_before(7);
+        cursor.insert_after(8);
+        check_links(&m);
+        assert_eq!(m.iter().cloned().collect::<Vec<_>>(), &[7, 1, 8, 2, 3, 4, 5, 6]);
+
+        let mut cursor = m.cursor_front_mut();
+        cursor.move_prev();
+        cursor.insert_before(9);
+        cursor.insert_after(10);
+        assert_eq!(cursor.index(), None);
+        cursor.move_prev();
+        assert_eq!(cursor.current(), Some(&mut 9));
+        assert_eq!(cursor.index(), Some(9));
+        check_links(&m);
+        assert_eq!(m.iter().cloned().collect::<Vec<_>>(), &[10, 7, 1, 8, 2, 3, 4, 5, 6, 9]);
+
+        let mut cursor = m.cursor_front_mut();
+        cursor.move_prev();
+        assert_eq!(cursor.remove_current(), None);
+        cursor.move_next();
+        cursor.move_next();
+        assert_eq!(cursor.remove_current(), Some(7));
+        assert_eq!(cursor.current(), Some(&mut 1));
+        assert_eq!(cursor.index(), Some(1));
+        cursor.move_prev();
+        cursor.move_prev();
+        cursor.move_prev();
+        assert_eq!(cursor.remove_current(), Some(9));
+        assert_eq!(cursor.current(), None);
+        check_links(&m);
+        assert_eq!(m.iter().cloned().collect::<Vec<_>>(), &[10, 1, 8, 2, 3, 4, 5, 6]);
+    }
+
+    #[test]
+    fn test_cursor_mut_splice() {
+        let mut m: LinkedList<u32> = list_from(&[1, 2, 3, 4, 5, 6]);
+        let mut cursor = m.cursor_front_mut();
+        cursor.move_next();
+        cursor.splice_before(list_from(&[7, 8]));
+        cursor.splice_after(list_from(&[9, 10]));
+        assert_eq!(cursor.current(), Some(&mut 2));
+        assert_eq!(cursor.index(), Some(3));
+        cursor.splice_after(LinkedList::new());
+        cursor.splice_before(LinkedList::new());
+        check_links(&m);
+        assert_eq!(m.iter().cloned().collect::<Vec<_>>(), &[1, 7, 8, 2, 9, 10, 3, 4, 5, 6]);
+
+        let mut cursor = m.cursor_back_mut();
+        cursor.move_next();
+        cursor.splice_before(list_from(&[11]));
+        cursor.splice_after(list_from(&[12, 13]));
+        assert_eq!(cursor.index(), None);
+        cursor.move_next();
+        assert_eq!(cursor.current(), Some(&mut 12));
+        check_links(&m);
+        assert_eq!(m.iter().cloned().collect::<Vec<_>>(),
+                   &[12, 13, 1, 7, 8, 2, 9, 10, 3, 4, 5, 6, 11]);
+
+        let mut m: LinkedList<u32> = LinkedList::new();
+        m.cursor_front_mut().splice_after(list_from(&[1, 2]));
+        check_links(&m);
+        assert_eq!(m.iter().cloned().collect::<Vec<_>>(), &[1, 2]);
+    }
 }
@@ -72,6 +72,7 @@
 #![feature(allocator_api)]
 #![feature(allow_internal_unstable)]
 #![feature(arbitrary_self_types)]
+#![cfg_attr(not(bootstrap), feature(array_chunks, array_windows))]
 #![feature(box_into_raw_non_null)]
 #![feature(box_patterns)]
 #![feature(box_syntax)]
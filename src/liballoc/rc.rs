@@ -248,7 +248,7 @@ use core::slice::{self, from_raw_parts_mut};
 use core::convert::From;
 use core::usize;
 
-use crate::alloc::{Global, Alloc, Layout, box_free, handle_alloc_error};
+use crate::alloc::{Global, Alloc, AllocErr, Layout, box_free, handle_alloc_error};
 use crate::string::String;
 use crate::vec::Vec;
 
@@ -323,6 +323,31 @@ impl<T> Rc<T> {
         }))
     }
 
+    /// Constructs a new `Rc<T>`, returning an error instead of aborting if
+    /// the allocation fails.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(allocator_api)]
+    ///
+    /// use std::rc::Rc;
+    ///
+    /// let five = Rc::try_new(5)?;
+    /// assert_eq!(*five, 5);
+    /// # Ok::<(), std::alloc::AllocErr>(())
+    /// ```
+    #[unstable(feature = "allocator_api", issue = "32838")]
+    pub fn try_new(value: T) -> Result<Rc<T>, AllocErr> {
+        // See `new` for why the weak count starts at 1.
+        let inner = Box::try_new(RcBox {
+            strong: Cell::new(1),
+            weak: Cell::new(1),
+            value,
+        })?;
+        Ok(Self::from_inner(Box::into_raw_non_null(inner)))
+    }
+
     /// Constructs a new `Pin<Rc<T>>`. If `T` does not implement `Unpin`, then
     /// `value` will be pinned in memory and unable to be moved.
     #[stable(feature = "pin", since = "1.33.0")]
@@ -754,7 +779,7 @@ impl<T: ?Sized> Rc<T> {
                 value_size);
 
             // Free the allocation without dropping its contents
-            box_free(box_unique);
+            box_free(box_unique, Global);
 
             Self::from_ptr(ptr)
         }
@@ -117,6 +117,12 @@ pub use core::slice::SliceIndex;
 pub use core::slice::{ChunksExact, ChunksExactMut};
 #[stable(feature = "rchunks", since = "1.31.0")]
 pub use core::slice::{RChunks, RChunksMut, RChunksExact, RChunksExactMut};
+#[cfg(not(bootstrap))]
+#[unstable(feature = "array_chunks", issue = "0")]
+pub use core::slice::{ArrayChunks, ArrayChunksMut, ArrayRChunks, ArrayRChunksMut};
+#[cfg(not(bootstrap))]
+#[unstable(feature = "array_windows", issue = "0")]
+pub use core::slice::ArrayWindows;
 
 ////////////////////////////////////////////////////////////////////////////////
 // Basic slice extension methods
@@ -807,6 +807,35 @@ impl String {
         self.vec.extend_from_slice(string.as_bytes())
     }
 
+    /// Appends a given string slice onto the end of this `String`, returning
+    /// an error instead of aborting if the allocation fails.
+    ///
+    /// On error, the `String` is left unchanged.
+    ///
+    /// # Errors
+    ///
+    /// If the capacity overflows, or the allocator reports a failure, then an error
+    /// is returned.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// #![feature(try_reserve)]
+    ///
+    /// let mut s = String::from("foo");
+    ///
+    /// s.try_push_str("bar").expect("why is the test harness OOMing on 6 bytes?");
+    ///
+    /// assert_eq!("foobar", s);
+    /// ```
+    #[inline]
+    #[unstable(feature = "try_reserve", reason = "new API", issue="48043")]
+    pub fn try_push_str(&mut self, string: &str) -> Result<(), CollectionAllocErr> {
+        self.vec.try_extend_from_slice(string.as_bytes())
+    }
+
     /// Returns this `String`'s capacity, in bytes.
     ///
     /// # Examples
@@ -1070,6 +1099,41 @@ impl String {
         }
     }
 
+    /// Appends the given [`char`] to the end of this `String`, returning an
+    /// error instead of aborting if the allocation fails.
+    ///
+    /// On error, the `String` is left unchanged.
+    ///
+    /// [`char`]: ../../std/primitive.char.html
+    ///
+    /// # Errors
+    ///
+    /// If the capacity overflows, or the allocator reports a failure, then an error
+    /// is returned.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// #![feature(try_reserve)]
+    ///
+    /// let mut s = String::from("abc");
+    ///
+    /// s.try_push('1').expect("why is the test harness OOMing on 4 bytes?");
+    /// s.try_push('é').expect("why is the test harness OOMing on 6 bytes?");
+    ///
+    /// assert_eq!("abc1é", s);
+    /// ```
+    #[inline]
+    #[unstable(feature = "try_reserve", reason = "new API", issue="48043")]
+    pub fn try_push(&mut self, ch: char) -> Result<(), CollectionAllocErr> {
+        match ch.len_utf8() {
+            1 => self.vec.try_push(ch as u8),
+            _ => self.vec.try_extend_from_slice(ch.encode_utf8(&mut [0; 4]).as_bytes()),
+        }
+    }
+
     /// Returns a byte slice of this `String`'s contents.
     ///
     /// The inverse of this method is [`from_utf8`].
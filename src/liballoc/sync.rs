@@ -24,7 +24,7 @@ use core::{isize, usize};
 use core::convert::From;
 use core::slice::{self, from_raw_parts_mut};
 
-use crate::alloc::{Global, Alloc, Layout, box_free, handle_alloc_error};
+use crate::alloc::{Global, Alloc, AllocErr, Layout, box_free, handle_alloc_error};
 use crate::boxed::Box;
 use crate::rc::is_dangling;
 use crate::string::String;
@@ -307,6 +307,31 @@ impl<T> Arc<T> {
         Self::from_inner(Box::into_raw_non_null(x))
     }
 
+    /// Constructs a new `Arc<T>`, returning an error instead of aborting if
+    /// the allocation fails.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(allocator_api)]
+    ///
+    /// use std::sync::Arc;
+    ///
+    /// let five = Arc::try_new(5)?;
+    /// assert_eq!(*five, 5);
+    /// # Ok::<(), std::alloc::AllocErr>(())
+    /// ```
+    #[unstable(feature = "allocator_api", issue = "32838")]
+    pub fn try_new(data: T) -> Result<Arc<T>, AllocErr> {
+        // See `new` for why the weak count starts at 1.
+        let x = Box::try_new(ArcInner {
+            strong: atomic::AtomicUsize::new(1),
+            weak: atomic::AtomicUsize::new(1),
+            data,
+        })?;
+        Ok(Self::from_inner(Box::into_raw_non_null(x)))
+    }
+
     /// Constructs a new `Pin<Arc<T>>`. If `T` does not implement `Unpin`, then
     /// `data` will be pinned in memory and unable to be moved.
     #[stable(feature = "pin", since = "1.33.0")]
@@ -642,7 +667,7 @@ impl<T: ?Sized> Arc<T> {
                 value_size);
 
             // Free the allocation without dropping its contents
-            box_free(box_unique);
+            box_free(box_unique, Global);
 
             Self::from_ptr(ptr)
         }
@@ -206,3 +206,12 @@ fn shared_from_iter_trustedlen_no_fuse() {
         &*iter.collect::<Rc<[_]>>()
     );
 }
+
+#[test]
+fn try_new() {
+    let x = Arc::try_new(RefCell::new(5)).unwrap();
+    *x.borrow_mut() += 1;
+    assert_eq!(*x.borrow(), 6);
+    assert_eq!(Arc::strong_count(&x), 1);
+    assert_eq!(Arc::weak_count(&x), 0);
+}
@@ -352,3 +352,14 @@ fn panic_safe() {
         }
     }
 }
+
+#[test]
+fn test_try_push() {
+    let mut heap = BinaryHeap::new();
+    heap.try_reserve(4).unwrap();
+    assert!(heap.capacity() >= 4);
+    for &x in &[2, 4, 9, 1] {
+        heap.try_push(x).unwrap();
+    }
+    assert_eq!(heap.into_sorted_vec(), [1, 2, 4, 9]);
+}
@@ -0,0 +1,75 @@
+use std::alloc::Global;
+use std::cell::Cell;
+
+use crate::CountingAlloc;
+
+#[test]
+fn test_new_in() {
+    let live = Cell::new(0);
+    {
+        let b = Box::new_in(5, CountingAlloc { live: &live });
+        assert_eq!(live.get(), 1);
+        assert_eq!(*b, 5);
+
+        let c = b.clone();
+        assert_eq!(live.get(), 2);
+        assert_eq!(b, c);
+        assert!(&*b as *const i32 != &*c as *const i32);
+    }
+    assert_eq!(live.get(), 0);
+}
+
+#[test]
+fn test_new_in_zst() {
+    let live = Cell::new(0);
+    let b = Box::new_in((), CountingAlloc { live: &live });
+    assert_eq!(live.get(), 0);
+    drop(b);
+    assert_eq!(live.get(), 0);
+}
+
+#[test]
+fn test_move_out_of_box_in() {
+    let live = Cell::new(0);
+    let b = Box::new_in(String::from("hello"), CountingAlloc { live: &live });
+    assert_eq!(live.get(), 1);
+    let s = *b;
+    assert_eq!(live.get(), 0);
+    assert_eq!(s, "hello");
+}
+
+#[test]
+fn test_unsize_box_in() {
+    let live = Cell::new(0);
+    {
+        let slice: Box<[i32], _> = Box::new_in([1, 2, 3], CountingAlloc { live: &live });
+        assert_eq!(live.get(), 1);
+        assert_eq!(&*slice, [1, 2, 3]);
+
+        let x = 4;
+        let f: Box<dyn Fn(i32) -> i32, _> = Box::new_in(move |y| x + y, Box::alloc(&slice).clone());
+        assert_eq!(live.get(), 2);
+        assert_eq!(f(1), 5);
+    }
+    assert_eq!(live.get(), 0);
+}
+
+#[test]
+fn test_raw_with_alloc() {
+    let live = Cell::new(0);
+    let b = Box::new_in(7, CountingAlloc { live: &live });
+    let (ptr, alloc) = Box::into_raw_with_alloc(b);
+    assert_eq!(live.get(), 1);
+    unsafe {
+        assert_eq!(*ptr, 7);
+        drop(Box::from_raw_in(ptr, alloc));
+    }
+    assert_eq!(live.get(), 0);
+}
+
+#[test]
+fn test_try_new_in() {
+    let b = Box::try_new_in(5, Global).unwrap();
+    let b: Box<i32> = b;
+    assert_eq!(*b, 5);
+}
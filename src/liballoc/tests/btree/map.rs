@@ -1,3 +1,4 @@
+use std::cell::Cell;
 use std::collections::BTreeMap;
 use std::collections::btree_map::Entry::{Occupied, Vacant};
 use std::ops::Bound::{self, Excluded, Included, Unbounded};
@@ -5,6 +6,7 @@ use std::rc::Rc;
 use std::iter::FromIterator;
 
 use super::DeterministicRng;
+use crate::CountingAlloc;
 
 #[test]
 fn test_basic_large() {
@@ -700,3 +702,208 @@ fn test_split_off_large_random_sorted() {
     assert!(map.into_iter().eq(data.clone().into_iter().filter(|x| x.0 < key)));
     assert!(right.into_iter().eq(data.into_iter().filter(|x| x.0 >= key)));
 }
+
+#[test]
+fn test_first_last() {
+    let mut map = BTreeMap::new();
+    assert_eq!(map.first_key_value(), None);
+    assert_eq!(map.last_key_value(), None);
+    assert_eq!(map.pop_first(), None);
+    assert_eq!(map.pop_last(), None);
+
+    let size = 1000;
+    for i in 0..size {
+        map.insert(i, i * 10);
+    }
+    assert_eq!(map.first_key_value(), Some((&0, &0)));
+    assert_eq!(map.last_key_value(), Some((&(size - 1), &((size - 1) * 10))));
+
+    *map.first_entry().unwrap().get_mut() += 1;
+    *map.last_entry().unwrap().get_mut() += 1;
+    assert_eq!(map[&0], 1);
+    assert_eq!(map[&(size - 1)], (size - 1) * 10 + 1);
+
+    for i in 0..size / 2 {
+        assert_eq!(map.pop_first().map(|(k, _)| k), Some(i));
+        assert_eq!(map.pop_last().map(|(k, _)| k), Some(size - i - 1));
+        assert_eq!(map.len(), size - 2 * (i + 1));
+    }
+    assert_eq!(map.pop_first(), None);
+    assert_eq!(map.pop_last(), None);
+}
+
+#[test]
+fn test_retain() {
+    let mut map: BTreeMap<i32, i32> = (0..1000).map(|x| (x, x * 10)).collect();
+    map.retain(|&k, v| {
+        *v += 1;
+        k % 3 == 0
+    });
+    assert_eq!(map.len(), 334);
+    assert!(map.into_iter().eq((0..1000).filter(|x| x % 3 == 0).map(|x| (x, x * 10 + 1))));
+}
+
+#[test]
+fn test_drain_filter() {
+    let mut map: BTreeMap<i32, i32> = (0..1000).map(|x| (x, x)).collect();
+    let drained: Vec<_> = map.drain_filter(|&k, _| k % 2 == 0).collect();
+    assert!(drained.into_iter().eq((0..1000).step_by(2).map(|x| (x, x))));
+    assert!(map.keys().copied().eq((1..1000).step_by(2)));
+
+    // Dropping the iterator early still removes every matching element.
+    let mut map: BTreeMap<i32, i32> = (0..1000).map(|x| (x, x)).collect();
+    assert_eq!(map.drain_filter(|&k, _| k >= 500).next(), Some((500, 500)));
+    assert_eq!(map.len(), 500);
+    assert_eq!(map.last_key_value(), Some((&499, &499)));
+
+    let mut map: BTreeMap<i32, i32> = (0..1000).map(|x| (x, x)).collect();
+    assert_eq!(map.drain_filter(|_, _| true).count(), 1000);
+    assert!(map.is_empty());
+}
+
+#[test]
+fn test_cursor() {
+    let map: BTreeMap<i32, i32> = (0..1000).map(|x| (x * 2, x)).collect();
+
+    let mut cursor = map.lower_bound(Included(&10));
+    assert_eq!(cursor.key_value(), Some((&10, &5)));
+    assert_eq!(cursor.peek_prev(), Some((&8, &4)));
+    assert_eq!(cursor.peek_next(), Some((&12, &6)));
+    cursor.move_next();
+    assert_eq!(cursor.key(), Some(&12));
+
+    assert_eq!(map.lower_bound(Excluded(&10)).key(), Some(&12));
+    assert_eq!(map.lower_bound(Included(&11)).key(), Some(&12));
+    assert_eq!(map.upper_bound(Included(&11)).key(), Some(&10));
+    assert_eq!(map.upper_bound(Excluded(&10)).key(), Some(&8));
+    assert_eq!(map.lower_bound::<i32>(Unbounded).key(), Some(&0));
+    assert_eq!(map.upper_bound::<i32>(Unbounded).key(), Some(&1998));
+    assert_eq!(map.lower_bound(Excluded(&1998)).key(), None);
+    assert_eq!(map.upper_bound(Excluded(&0)).key(), None);
+
+    // Walking the whole map in both directions, through the ghost element.
+    let mut cursor = map.lower_bound::<i32>(Unbounded);
+    let mut forward = Vec::new();
+    while let Some(&k) = cursor.key() {
+        forward.push(k);
+        cursor.move_next();
+    }
+    assert!(forward.into_iter().eq((0..1000).map(|x| x * 2)));
+    cursor.move_next();
+    assert_eq!(cursor.key(), Some(&0));
+    cursor.move_prev();
+    assert_eq!(cursor.key(), None);
+    cursor.move_prev();
+    let mut backward = Vec::new();
+    while let Some(&k) = cursor.key() {
+        backward.push(k);
+        cursor.move_prev();
+    }
+    assert!(backward.into_iter().eq((0..1000).rev().map(|x| x * 2)));
+
+    let empty: BTreeMap<i32, i32> = BTreeMap::new();
+    let mut cursor = empty.lower_bound::<i32>(Unbounded);
+    assert_eq!(cursor.key(), None);
+    cursor.move_next();
+    assert_eq!(cursor.key(), None);
+    assert_eq!(cursor.peek_prev(), None);
+}
+
+#[test]
+fn test_cursor_mut() {
+    let mut map: BTreeMap<i32, i32> = (0..1000).map(|x| (x, x)).collect();
+
+    let mut cursor = map.lower_bound_mut(Included(&100));
+    *cursor.value_mut().unwrap() = -100;
+    *cursor.peek_next().unwrap().1 = -101;
+    *cursor.peek_prev().unwrap().1 = -99;
+    assert_eq!(cursor.as_cursor().key(), Some(&100));
+    assert_eq!(map[&99], -99);
+    assert_eq!(map[&100], -100);
+    assert_eq!(map[&101], -101);
+
+    // Removing every other element through a cursor keeps it in step with the map.
+    let mut cursor = map.lower_bound_mut::<i32>(Unbounded);
+    while let Some((k, _)) = cursor.remove_current() {
+        assert_eq!(k % 2, 0);
+        cursor.move_next();
+    }
+    assert_eq!(map.len(), 500);
+    assert!(map.keys().copied().eq((1..1000).step_by(2)));
+
+    let mut cursor = map.upper_bound_mut::<i32>(Unbounded);
+    for i in (1..1000).step_by(2).rev() {
+        assert_eq!(cursor.remove_current().map(|(k, _)| k), Some(i));
+        assert_eq!(cursor.key(), None);
+        cursor.move_prev();
+    }
+    assert_eq!(cursor.remove_current(), None);
+    assert!(map.is_empty());
+}
+
+#[test]
+fn test_new_in() {
+    let live = Cell::new(0);
+    {
+        let mut map = BTreeMap::new_in(CountingAlloc { live: &live });
+        assert_eq!(live.get(), 0);
+
+        // Enough insertions to split leaves and grow the tree by a level.
+        for i in 0..1000 {
+            assert_eq!(map.insert(i, i * 2), None);
+        }
+        assert!(live.get() > 1);
+        assert_eq!(map[&500], 1000);
+
+        // Removing most of the entries merges nodes and shrinks the tree again.
+        for i in 0..990 {
+            assert_eq!(map.remove(&i), Some(i * 2));
+        }
+        assert_eq!(map.len(), 10);
+        assert!(map.keys().copied().eq(990..1000));
+
+        map.clear();
+        assert_eq!(live.get(), 0);
+        map.entry(1).or_insert(1);
+        assert_eq!(live.get(), 1);
+    }
+    assert_eq!(live.get(), 0);
+}
+
+#[test]
+fn test_into_iter_in() {
+    let live = Cell::new(0);
+    let mut map = BTreeMap::new_in(CountingAlloc { live: &live });
+    map.extend((0..1000).map(|i| (i, i.to_string())));
+
+    let mut iter = map.into_iter();
+    assert_eq!(iter.next(), Some((0, "0".to_string())));
+    assert_eq!(iter.next_back(), Some((999, "999".to_string())));
+    drop(iter);
+    assert_eq!(live.get(), 0);
+}
+
+#[test]
+fn test_clone_split_off_append_in() {
+    let live = Cell::new(0);
+    {
+        let mut a = BTreeMap::new_in(CountingAlloc { live: &live });
+        a.extend((0..1000).map(|i| (i, i)));
+
+        let b = a.clone();
+        assert_eq!(a, b);
+
+        let mut c = a.split_off(&500);
+        assert!(a.keys().copied().eq(0..500));
+        assert!(c.keys().copied().eq(500..1000));
+
+        c.append(&mut a);
+        assert!(a.is_empty());
+        assert_eq!(c, b);
+
+        let evens: Vec<_> = c.drain_filter(|k, _| k % 2 == 0).map(|(k, _)| k).collect();
+        assert_eq!(evens.len(), 500);
+        assert_eq!(c.len(), 500);
+    }
+    assert_eq!(live.get(), 0);
+}
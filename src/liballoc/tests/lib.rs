@@ -1,7 +1,11 @@
 #![feature(allocator_api)]
 #![feature(box_syntax)]
+#![feature(btree_cursors)]
+#![feature(btree_drain_filter)]
+#![feature(btree_retain)]
 #![feature(drain_filter)]
 #![feature(exact_size_is_empty)]
+#![feature(map_first_last)]
 #![feature(option_flattening)]
 #![feature(pattern)]
 #![feature(repeat_generic_slice)]
@@ -10,11 +14,15 @@
 #![feature(unboxed_closures)]
 #![deny(rust_2018_idioms)]
 
+use std::alloc::{Alloc, AllocErr, Global, Layout};
+use std::cell::Cell;
 use std::hash::{Hash, Hasher};
 use std::collections::hash_map::DefaultHasher;
+use std::ptr::NonNull;
 
 mod arc;
 mod binary_heap;
+mod boxed;
 mod btree;
 mod cow_str;
 mod fmt;
@@ -33,6 +41,25 @@ fn hash<T: Hash>(t: &T) -> u64 {
     s.finish()
 }
 
+/// An allocator which forwards to `Global` while counting live allocations.
+#[derive(Clone)]
+struct CountingAlloc<'a> {
+    live: &'a Cell<usize>,
+}
+
+unsafe impl Alloc for CountingAlloc<'_> {
+    unsafe fn alloc(&mut self, layout: Layout) -> Result<NonNull<u8>, AllocErr> {
+        let ptr = Global.alloc(layout)?;
+        self.live.set(self.live.get() + 1);
+        Ok(ptr)
+    }
+
+    unsafe fn dealloc(&mut self, ptr: NonNull<u8>, layout: Layout) {
+        self.live.set(self.live.get() - 1);
+        Global.dealloc(ptr, layout)
+    }
+}
+
 // FIXME: Instantiated functions with i128 in the signature is not supported in Emscripten.
 // See https://github.com/kripken/emscripten-fastcomp/issues/169
 #[cfg(not(target_os = "emscripten"))]
@@ -202,3 +202,12 @@ fn shared_from_iter_trustedlen_no_fuse() {
         &*iter.collect::<Rc<[_]>>()
     );
 }
+
+#[test]
+fn try_new() {
+    let x = Rc::try_new(RefCell::new(5)).unwrap();
+    *x.borrow_mut() += 1;
+    assert_eq!(*x.borrow(), 6);
+    assert_eq!(Rc::strong_count(&x), 1);
+    assert_eq!(Rc::weak_count(&x), 0);
+}
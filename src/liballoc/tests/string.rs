@@ -659,3 +659,12 @@ fn test_try_reserve_exact() {
     }
 
 }
+
+#[test]
+fn test_try_push_str() {
+    let mut s = String::new();
+    s.try_push_str("abc").unwrap();
+    s.try_push('é').unwrap();
+    s.try_push('!').unwrap();
+    assert_eq!(s, "abcé!");
+}
@@ -1,9 +1,12 @@
 use std::borrow::Cow;
+use std::cell::Cell;
 use std::mem::size_of;
 use std::{usize, isize};
 use std::vec::{Drain, IntoIter};
 use std::collections::CollectionAllocErr::*;
 
+use crate::CountingAlloc;
+
 struct DropCounter<'a> {
     count: &'a mut u32,
 }
@@ -1281,3 +1284,62 @@ fn test_stable_push_pop() {
     v.pop().unwrap();
     assert_eq!(*v0, 13);
 }
+
+#[test]
+fn test_new_in() {
+    let live = Cell::new(0);
+    {
+        let mut v = Vec::new_in(CountingAlloc { live: &live });
+        assert_eq!(live.get(), 0);
+
+        v.extend(0..100);
+        assert_eq!(live.get(), 1);
+
+        let w = v.clone();
+        assert_eq!(live.get(), 2);
+        assert_eq!(v, w);
+
+        let tail = v.split_off(50);
+        assert_eq!(live.get(), 3);
+        assert_eq!(v, (0..50).collect::<Vec<_>>());
+        assert_eq!(tail, (50..100).collect::<Vec<_>>());
+    }
+    assert_eq!(live.get(), 0);
+}
+
+#[test]
+fn test_with_capacity_in() {
+    let live = Cell::new(0);
+    let mut v = Vec::with_capacity_in(4, CountingAlloc { live: &live });
+    assert_eq!(v.capacity(), 4);
+    assert_eq!(live.get(), 1);
+
+    v.push("a".to_string());
+    v.push("b".to_string());
+    v.push("c".to_string());
+    v.drain(..1);
+    assert_eq!(v, ["b", "c"]);
+
+    let mut iter = v.into_iter();
+    assert_eq!(iter.next(), Some("b".to_string()));
+    assert_eq!(live.get(), 1);
+    drop(iter);
+    assert_eq!(live.get(), 0);
+}
+
+#[test]
+fn test_try_push() {
+    let mut v = Vec::new();
+    for i in 0..10 {
+        v.try_push(i).unwrap();
+    }
+    v.try_extend_from_slice(&[10, 11]).unwrap();
+    assert_eq!(v, (0..12).collect::<Vec<_>>());
+
+    // Zero-sized values never allocate, but the length can still overflow.
+    let mut zst = Vec::new();
+    unsafe { zst.set_len(usize::MAX) };
+    assert_eq!(zst.try_push(()), Err(CapacityOverflow));
+    assert_eq!(zst.try_extend_from_slice(&[()]), Err(CapacityOverflow));
+    assert_eq!(zst.len(), usize::MAX);
+}
@@ -2,9 +2,10 @@ use std::fmt::Debug;
 use std::collections::{VecDeque, vec_deque::Drain};
 use std::collections::CollectionAllocErr::*;
 use std::mem::size_of;
+use std::cell::Cell;
 use std::{usize, isize};
 
-use crate::hash;
+use crate::{hash, CountingAlloc};
 
 use Taggy::*;
 use Taggypar::*;
@@ -1540,3 +1541,55 @@ fn test_try_rfold_moves_iter() {
     assert_eq!(iter.try_rfold(0_i8, |acc, &x| acc.checked_add(x)), None);
     assert_eq!(iter.next_back(), Some(&70));
 }
+
+#[test]
+fn test_new_in() {
+    let live = Cell::new(0);
+    {
+        let mut d = VecDeque::new_in(CountingAlloc { live: &live });
+        assert_eq!(live.get(), 1);
+
+        d.extend(0..10);
+        d.push_front(-1);
+        d.rotate_left(3);
+
+        let e = d.clone();
+        assert_eq!(live.get(), 2);
+        assert_eq!(d, e);
+
+        let tail = d.split_off(5);
+        assert_eq!(live.get(), 3);
+        assert_eq!(d, [2, 3, 4, 5, 6]);
+        assert_eq!(tail, [7, 8, 9, -1, 0, 1]);
+    }
+    assert_eq!(live.get(), 0);
+}
+
+#[test]
+fn test_with_capacity_in() {
+    let live = Cell::new(0);
+    let mut d = VecDeque::with_capacity_in(10, CountingAlloc { live: &live });
+    assert!(d.capacity() >= 10);
+    assert_eq!(live.get(), 1);
+
+    d.push_back("b".to_string());
+    d.push_front("a".to_string());
+    d.drain(..1);
+    assert_eq!(d, ["b"]);
+
+    let mut iter = d.into_iter();
+    assert_eq!(iter.next(), Some("b".to_string()));
+    drop(iter);
+    assert_eq!(live.get(), 0);
+}
+
+#[test]
+fn test_try_push() {
+    let mut d = VecDeque::with_capacity(3);
+    for i in 0..8 {
+        d.try_push_back(i).unwrap();
+        d.try_push_front(-i).unwrap();
+    }
+    let expected: Vec<_> = (0..8).rev().map(|i| -i).chain(0..8).collect();
+    assert_eq!(d, expected);
+}
@@ -0,0 +1,200 @@
+//! Defines the `IntoIter` owned iterator for arrays.
+
+use crate::{
+    fmt,
+    iter::{ExactSizeIterator, FusedIterator, TrustedLen},
+    mem::{self, MaybeUninit},
+    ops::Range,
+    ptr,
+};
+
+/// A by-value [array] iterator.
+///
+/// [array]: ../../std/primitive.array.html
+#[unstable(feature = "array_value_iter", issue = "0")]
+pub struct IntoIter<T, const N: usize> {
+    /// This is the array we are iterating over.
+    ///
+    /// Elements with index `i` where `alive.start <= i < alive.end` have not
+    /// been yielded yet and are valid array entries. Elements with indices `i
+    /// < alive.start` or `i >= alive.end` have been yielded already and must
+    /// not be accessed anymore! Those dead elements might even be in a
+    /// completely uninitialized state!
+    ///
+    /// So the invariants are:
+    /// - `data[alive]` is alive (i.e. contains valid elements)
+    /// - `data[..alive.start]` and `data[alive.end..]` are dead (i.e. the
+    ///   elements were already read and must not be touched anymore!)
+    data: [MaybeUninit<T>; N],
+
+    /// The elements in `data` that have not been yielded yet.
+    ///
+    /// Invariants:
+    /// - `alive.start <= alive.end`
+    /// - `alive.end <= N`
+    alive: Range<usize>,
+}
+
+impl<T, const N: usize> IntoIter<T, N> {
+    /// Creates a new iterator over the given `array`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(array_value_iter)]
+    /// use std::array::IntoIter;
+    ///
+    /// let names = [String::from("a"), String::from("b")];
+    /// let mut iter = IntoIter::new(names);
+    ///
+    /// assert_eq!(iter.next(), Some(String::from("a")));
+    /// assert_eq!(iter.next(), Some(String::from("b")));
+    /// assert_eq!(iter.next(), None);
+    /// ```
+    #[unstable(feature = "array_value_iter", issue = "0")]
+    pub fn new(array: [T; N]) -> Self {
+        // The transmute here is actually safe. `MaybeUninit<T>` has the same
+        // layout as `T`, so `[MaybeUninit<T>; N]` has the same layout as
+        // `[T; N]`. `mem::transmute` cannot be used as the compiler can't
+        // prove the sizes equal for a generic `N`, so read the array out and
+        // forget the original instead.
+        let data = unsafe {
+            let data = ptr::read(&array as *const [T; N] as *const [MaybeUninit<T>; N]);
+            mem::forget(array);
+            data
+        };
+
+        Self {
+            data,
+            alive: 0..N,
+        }
+    }
+
+    /// Creates an iterator over the elements of `buffer` within `alive`.
+    ///
+    /// The caller must guarantee that `alive.start <= alive.end <= N` and
+    /// that all elements in `buffer[alive]` are initialized.
+    pub(crate) unsafe fn new_unchecked(buffer: [MaybeUninit<T>; N], alive: Range<usize>) -> Self {
+        Self {
+            data: buffer,
+            alive,
+        }
+    }
+
+    /// Returns an immutable slice of all elements that have not been yielded
+    /// yet.
+    #[unstable(feature = "array_value_iter", issue = "0")]
+    pub fn as_slice(&self) -> &[T] {
+        let slice = &self.data[self.alive.clone()];
+        // This is safe as the elements in `alive` are initialized, and
+        // `MaybeUninit<T>` has the same layout as `T`.
+        unsafe { &*(slice as *const [MaybeUninit<T>] as *const [T]) }
+    }
+
+    /// Returns a mutable slice of all elements that have not been yielded
+    /// yet.
+    #[unstable(feature = "array_value_iter", issue = "0")]
+    pub fn as_mut_slice(&mut self) -> &mut [T] {
+        let slice = &mut self.data[self.alive.clone()];
+        // This is safe for the same reasons as in `as_slice`.
+        unsafe { &mut *(slice as *mut [MaybeUninit<T>] as *mut [T]) }
+    }
+}
+
+#[unstable(feature = "array_value_iter", issue = "0")]
+impl<T, const N: usize> Iterator for IntoIter<T, N> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        // Moving `alive.start` forward first marks the element as dead, and
+        // only then is it read out. `get_unchecked` is fine since `alive` is
+        // always a range within `0..N`.
+        self.alive.next().map(|idx| unsafe { self.data.get_unchecked(idx).read() })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.len();
+        (len, Some(len))
+    }
+
+    fn count(self) -> usize {
+        self.len()
+    }
+
+    fn last(mut self) -> Option<Self::Item> {
+        self.next_back()
+    }
+}
+
+#[unstable(feature = "array_value_iter", issue = "0")]
+impl<T, const N: usize> DoubleEndedIterator for IntoIter<T, N> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        // See `next` above.
+        self.alive.next_back().map(|idx| unsafe { self.data.get_unchecked(idx).read() })
+    }
+}
+
+#[unstable(feature = "array_value_iter", issue = "0")]
+impl<T, const N: usize> Drop for IntoIter<T, N> {
+    fn drop(&mut self) {
+        // Only the elements that have not been yielded yet are dropped.
+        unsafe { ptr::drop_in_place(self.as_mut_slice()) }
+    }
+}
+
+#[unstable(feature = "array_value_iter", issue = "0")]
+impl<T, const N: usize> ExactSizeIterator for IntoIter<T, N> {
+    fn len(&self) -> usize {
+        // Will never underflow due to the invariant `alive.start <=
+        // alive.end`.
+        self.alive.end - self.alive.start
+    }
+    fn is_empty(&self) -> bool {
+        self.alive.is_empty()
+    }
+}
+
+#[unstable(feature = "array_value_iter", issue = "0")]
+impl<T, const N: usize> FusedIterator for IntoIter<T, N> {}
+
+// The iterator indeed reports the correct length. The number of "alive"
+// elements (that will still be yielded) is the length of the range `alive`.
+// This range is decremented in length in either `next` or `next_back`. It is
+// always decremented by 1 in those methods, but only if `Some(_)` is returned.
+#[unstable(feature = "array_value_iter", issue = "0")]
+unsafe impl<T, const N: usize> TrustedLen for IntoIter<T, N> {}
+
+#[unstable(feature = "array_value_iter", issue = "0")]
+impl<T: Clone, const N: usize> Clone for IntoIter<T, N> {
+    fn clone(&self) -> Self {
+        unsafe {
+            // This creates a new uninitialized array. Note that the
+            // `assume_init` refers to the array, not the individual elements,
+            // and an array of `MaybeUninit` needs no initialization.
+            let mut new_data: [MaybeUninit<T>; N] = MaybeUninit::uninit().assume_init();
+
+            // Clone all alive elements. Should a clone panic, the clones made
+            // so far are leaked, which is safe.
+            for idx in self.alive.clone() {
+                let clone = self.data.get_unchecked(idx).get_ref().clone();
+                new_data.get_unchecked_mut(idx).write(clone);
+            }
+
+            Self {
+                data: new_data,
+                alive: self.alive.clone(),
+            }
+        }
+    }
+}
+
+#[unstable(feature = "array_value_iter", issue = "0")]
+impl<T: fmt::Debug, const N: usize> fmt::Debug for IntoIter<T, N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // Only print the elements that were not yielded yet: we cannot
+        // access the yielded elements anymore.
+        f.debug_tuple("IntoIter")
+            .field(&self.as_slice())
+            .finish()
+    }
+}
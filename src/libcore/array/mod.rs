@@ -1,6 +1,5 @@
-//! Implementations of things like `Eq` for fixed-length arrays
-//! up to a certain length. Eventually we should able to generalize
-//! to all lengths.
+//! Implementations of things like `Eq` for fixed-length arrays, and a
+//! by-value iterator over arrays.
 //!
 //! *[See also the array primitive type](../../std/primitive.array.html).*
 
@@ -12,8 +11,21 @@ use crate::convert::{Infallible, TryFrom};
 use crate::fmt;
 use crate::hash::{Hash, self};
 use crate::marker::Unsize;
+#[cfg(not(bootstrap))]
+use crate::mem::{self, MaybeUninit};
+#[cfg(not(bootstrap))]
+use crate::ptr;
+#[cfg(not(bootstrap))]
+use crate::slice;
 use crate::slice::{Iter, IterMut};
 
+#[cfg(not(bootstrap))]
+mod iter;
+
+#[cfg(not(bootstrap))]
+#[unstable(feature = "array_value_iter", issue = "0")]
+pub use iter::IntoIter;
+
 /// Utility trait implemented only on arrays of fixed size
 ///
 /// This trait can be used to implement other traits on fixed-size arrays
@@ -272,10 +284,7 @@ mod impls_using_const_generics {
     use super::*;
 
     #[stable(feature = "rust1", since = "1.0.0")]
-    impl<T, const N: usize> AsRef<[T]> for [T; N]
-    where
-        [T; N]: LengthAtMost32,
-    {
+    impl<T, const N: usize> AsRef<[T]> for [T; N] {
         #[inline]
         fn as_ref(&self) -> &[T] {
             &self[..]
@@ -283,10 +292,7 @@ mod impls_using_const_generics {
     }
 
     #[stable(feature = "rust1", since = "1.0.0")]
-    impl<T, const N: usize> AsMut<[T]> for [T; N]
-    where
-        [T; N]: LengthAtMost32,
-    {
+    impl<T, const N: usize> AsMut<[T]> for [T; N] {
         #[inline]
         fn as_mut(&mut self) -> &mut [T] {
             &mut self[..]
@@ -294,20 +300,14 @@ mod impls_using_const_generics {
     }
 
     #[stable(feature = "array_borrow", since = "1.4.0")]
-    impl<T, const N: usize> Borrow<[T]> for [T; N]
-    where
-        [T; N]: LengthAtMost32,
-    {
+    impl<T, const N: usize> Borrow<[T]> for [T; N] {
         fn borrow(&self) -> &[T] {
             self
         }
     }
 
     #[stable(feature = "array_borrow", since = "1.4.0")]
-    impl<T, const N: usize> BorrowMut<[T]> for [T; N]
-    where
-        [T; N]: LengthAtMost32,
-    {
+    impl<T, const N: usize> BorrowMut<[T]> for [T; N] {
         fn borrow_mut(&mut self) -> &mut [T] {
             self
         }
@@ -317,7 +317,6 @@ mod impls_using_const_generics {
     impl<T, const N: usize> TryFrom<&[T]> for [T; N]
     where
         T: Copy,
-        [T; N]: LengthAtMost32,
     {
         type Error = TryFromSliceError;
 
@@ -327,10 +326,7 @@ mod impls_using_const_generics {
     }
 
     #[stable(feature = "try_from", since = "1.34.0")]
-    impl<'a, T, const N: usize> TryFrom<&'a [T]> for &'a [T; N]
-    where
-        [T; N]: LengthAtMost32,
-    {
+    impl<'a, T, const N: usize> TryFrom<&'a [T]> for &'a [T; N] {
         type Error = TryFromSliceError;
 
         fn try_from(slice: &[T]) -> Result<&[T; N], TryFromSliceError> {
@@ -344,10 +340,7 @@ mod impls_using_const_generics {
     }
 
     #[stable(feature = "try_from", since = "1.34.0")]
-    impl<'a, T, const N: usize> TryFrom<&'a mut [T]> for &'a mut [T; N]
-    where
-        [T; N]: LengthAtMost32,
-    {
+    impl<'a, T, const N: usize> TryFrom<&'a mut [T]> for &'a mut [T; N] {
         type Error = TryFromSliceError;
 
         fn try_from(slice: &mut [T]) -> Result<&mut [T; N], TryFromSliceError> {
@@ -361,30 +354,21 @@ mod impls_using_const_generics {
     }
 
     #[stable(feature = "rust1", since = "1.0.0")]
-    impl<T: Hash, const N: usize> Hash for [T; N]
-    where
-        [T; N]: LengthAtMost32,
-    {
+    impl<T: Hash, const N: usize> Hash for [T; N] {
         fn hash<H: hash::Hasher>(&self, state: &mut H) {
             Hash::hash(&self[..], state)
         }
     }
 
     #[stable(feature = "rust1", since = "1.0.0")]
-    impl<T: fmt::Debug, const N: usize> fmt::Debug for [T; N]
-    where
-        [T; N]: LengthAtMost32,
-    {
+    impl<T: fmt::Debug, const N: usize> fmt::Debug for [T; N] {
         fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
             fmt::Debug::fmt(&&self[..], f)
         }
     }
 
     #[stable(feature = "rust1", since = "1.0.0")]
-    impl<'a, T, const N: usize> IntoIterator for &'a [T; N]
-    where
-        [T; N]: LengthAtMost32,
-    {
+    impl<'a, T, const N: usize> IntoIterator for &'a [T; N] {
         type Item = &'a T;
         type IntoIter = Iter<'a, T>;
 
@@ -394,10 +378,7 @@ mod impls_using_const_generics {
     }
 
     #[stable(feature = "rust1", since = "1.0.0")]
-    impl<'a, T, const N: usize> IntoIterator for &'a mut [T; N]
-    where
-        [T; N]: LengthAtMost32,
-    {
+    impl<'a, T, const N: usize> IntoIterator for &'a mut [T; N] {
         type Item = &'a mut T;
         type IntoIter = IterMut<'a, T>;
 
@@ -410,8 +391,6 @@ mod impls_using_const_generics {
     impl<'a, 'b, A, B, const N: usize> PartialEq<[B; N]> for [A; N]
     where
         A: PartialEq<B>,
-        [A; N]: LengthAtMost32,
-        [B; N]: LengthAtMost32,
     {
         #[inline]
         fn eq(&self, other: &[B; N]) -> bool {
@@ -427,7 +406,6 @@ mod impls_using_const_generics {
     impl<'a, 'b, A, B, const N: usize> PartialEq<[B]> for [A; N]
     where
         A: PartialEq<B>,
-        [A; N]: LengthAtMost32,
     {
         #[inline]
         fn eq(&self, other: &[B]) -> bool {
@@ -443,7 +421,6 @@ mod impls_using_const_generics {
     impl<'a, 'b, A, B, const N: usize> PartialEq<[A; N]> for [B]
     where
         B: PartialEq<A>,
-        [A; N]: LengthAtMost32,
     {
         #[inline]
         fn eq(&self, other: &[A; N]) -> bool {
@@ -459,7 +436,6 @@ mod impls_using_const_generics {
     impl<'a, 'b, A, B, const N: usize> PartialEq<&'b [B]> for [A; N]
     where
         A: PartialEq<B>,
-        [A; N]: LengthAtMost32,
     {
         #[inline]
         fn eq(&self, other: &&'b [B]) -> bool {
@@ -475,7 +451,6 @@ mod impls_using_const_generics {
     impl<'a, 'b, A, B, const N: usize> PartialEq<[A; N]> for &'b [B]
     where
         B: PartialEq<A>,
-        [A; N]: LengthAtMost32,
     {
         #[inline]
         fn eq(&self, other: &[A; N]) -> bool {
@@ -491,7 +466,6 @@ mod impls_using_const_generics {
     impl<'a, 'b, A, B, const N: usize> PartialEq<&'b mut [B]> for [A; N]
     where
         A: PartialEq<B>,
-        [A; N]: LengthAtMost32,
     {
         #[inline]
         fn eq(&self, other: &&'b mut [B]) -> bool {
@@ -507,7 +481,6 @@ mod impls_using_const_generics {
     impl<'a, 'b, A, B, const N: usize> PartialEq<[A; N]> for &'b mut [B]
     where
         B: PartialEq<A>,
-        [A; N]: LengthAtMost32,
     {
         #[inline]
         fn eq(&self, other: &[A; N]) -> bool {
@@ -524,13 +497,10 @@ mod impls_using_const_generics {
     // __impl_slice_eq2! { [A; $N], &'b mut [B; $N] }
 
     #[stable(feature = "rust1", since = "1.0.0")]
-    impl<T: Eq, const N: usize> Eq for [T; N] where [T; N]: LengthAtMost32 {}
+    impl<T: Eq, const N: usize> Eq for [T; N] {}
 
     #[stable(feature = "rust1", since = "1.0.0")]
-    impl<T: PartialOrd, const N: usize> PartialOrd for [T; N]
-    where
-        [T; N]: LengthAtMost32,
-    {
+    impl<T: PartialOrd, const N: usize> PartialOrd for [T; N] {
         #[inline]
         fn partial_cmp(&self, other: &[T; N]) -> Option<Ordering> {
             PartialOrd::partial_cmp(&&self[..], &&other[..])
@@ -554,10 +524,7 @@ mod impls_using_const_generics {
     }
 
     #[stable(feature = "rust1", since = "1.0.0")]
-    impl<T: Ord, const N: usize> Ord for [T; N]
-    where
-        [T; N]: LengthAtMost32,
-    {
+    impl<T: Ord, const N: usize> Ord for [T; N] {
         #[inline]
         fn cmp(&self, other: &[T; N]) -> Ordering {
             Ord::cmp(&&self[..], &&other[..])
@@ -565,25 +532,107 @@ mod impls_using_const_generics {
     }
 }
 
-/// Implemented for lengths where trait impls are allowed on arrays in core/std
-#[rustc_on_unimplemented(
-    message="arrays only have std trait implementations for lengths 0..=32",
-)]
-#[unstable(feature = "const_generic_impls_guard", issue = "0",
-    reason = "will never be stable, just a temporary step until const generics are stable")]
 #[cfg(not(bootstrap))]
-pub trait LengthAtMost32 {}
+#[lang = "array"]
+impl<T, const N: usize> [T; N] {
+    /// Returns an array of the same size as `self`, with function `f` applied to each element
+    /// in order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(array_map)]
+    /// let x = [1, 2, 3];
+    /// let y = x.map(|v| v + 1);
+    /// assert_eq!(y, [2, 3, 4]);
+    ///
+    /// let x = [1, 2, 3];
+    /// let mut temp = 0;
+    /// let y = x.map(|v| { temp += 1; v * temp });
+    /// assert_eq!(y, [1, 4, 9]);
+    ///
+    /// let x = ["Ferris", "Bueller's", "Day", "Off"];
+    /// let y = x.map(|v| v.len());
+    /// assert_eq!(y, [6, 9, 3, 3]);
+    /// ```
+    #[unstable(feature = "array_map", issue = "0")]
+    pub fn map<F, U>(self, mut f: F) -> [U; N]
+    where
+        F: FnMut(T) -> U,
+    {
+        // An array of `MaybeUninit` needs no initialization.
+        let mut dst: [MaybeUninit<U>; N] = unsafe { MaybeUninit::uninit().assume_init() };
+        // Drops the already mapped elements should `f` panic.
+        let mut guard = Guard { dst: &mut dst as *mut _ as *mut U, initialized: 0 };
+        for (src, dst) in IntoIter::new(self).zip(&mut dst) {
+            dst.write(f(src));
+            guard.initialized += 1;
+        }
+        // All elements are initialized now, so the guard must not drop them.
+        mem::forget(guard);
+        // `mem::transmute` cannot prove the sizes equal for a generic `N`, so
+        // read the array out instead. `[MaybeUninit<U>; N]` has no drop glue.
+        unsafe { ptr::read(&dst as *const [MaybeUninit<U>; N] as *const [U; N]) }
+    }
+}
 
+/// Drops the first `initialized` elements starting at `dst` when dropped.
+///
+/// Used to clean up a partially initialized array when filling it panics.
 #[cfg(not(bootstrap))]
-macro_rules! array_impls {
-    ($($N:literal)+) => {
-        $(
-            #[unstable(feature = "const_generic_impls_guard", issue = "0")]
-            impl<T> LengthAtMost32 for [T; $N] {}
-        )+
+struct Guard<T> {
+    dst: *mut T,
+    initialized: usize,
+}
+
+#[cfg(not(bootstrap))]
+impl<T> Drop for Guard<T> {
+    fn drop(&mut self) {
+        unsafe {
+            ptr::drop_in_place(slice::from_raw_parts_mut(self.dst, self.initialized));
+        }
     }
 }
 
+/// Pulls `N` items from `iter` and returns them as an array. If the iterator
+/// yields fewer than `N` items, `Err` is returned containing an iterator over
+/// the already yielded items.
+///
+/// If `iter.next()` panics, all items already yielded by the iterator are
+/// dropped.
+#[cfg(not(bootstrap))]
+pub(crate) fn iter_next_chunk<I, const N: usize>(
+    iter: &mut I,
+) -> Result<[I::Item; N], IntoIter<I::Item, N>>
+where
+    I: Iterator,
+{
+    // An array of `MaybeUninit` needs no initialization.
+    let mut array: [MaybeUninit<I::Item>; N] = unsafe { MaybeUninit::uninit().assume_init() };
+    let mut guard = Guard { dst: &mut array as *mut _ as *mut I::Item, initialized: 0 };
+
+    while guard.initialized < N {
+        match iter.next() {
+            Some(item) => {
+                unsafe { guard.dst.add(guard.initialized).write(item) };
+                guard.initialized += 1;
+            }
+            None => {
+                let alive = 0..guard.initialized;
+                mem::forget(guard);
+                // Only the first `alive.end` elements have been initialized.
+                return Err(unsafe { IntoIter::new_unchecked(array, alive) });
+            }
+        }
+    }
+
+    mem::forget(guard);
+    // `mem::transmute` cannot prove the sizes equal for a generic `N`, so read
+    // the array out instead. `[MaybeUninit<T>; N]` has no drop glue.
+    Ok(unsafe { ptr::read(&array as *const [MaybeUninit<I::Item>; N] as *const [I::Item; N]) })
+}
+
+#[cfg(bootstrap)]
 array_impls! {
      0  1  2  3  4  5  6  7  8  9
     10 11 12 13 14 15 16 17 18 19
@@ -0,0 +1,136 @@
+use crate::array;
+
+use super::super::{Iterator, DoubleEndedIterator, ExactSizeIterator, FusedIterator};
+
+/// An iterator over `N` elements of the iterator at a time.
+///
+/// The chunks do not overlap. If `N` does not divide the length of the
+/// iterator, then the last up to `N-1` elements will be omitted.
+///
+/// This `struct` is created by the [`array_chunks`] method on [`Iterator`]. See
+/// its documentation for more.
+///
+/// [`array_chunks`]: trait.Iterator.html#method.array_chunks
+/// [`Iterator`]: trait.Iterator.html
+#[derive(Debug, Clone)]
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+#[unstable(feature = "iter_array_chunks", reason = "recently added", issue = "0")]
+pub struct ArrayChunks<I: Iterator, const N: usize> {
+    iter: I,
+    remainder: Option<array::IntoIter<I::Item, N>>,
+}
+
+impl<I, const N: usize> ArrayChunks<I, N>
+where
+    I: Iterator,
+{
+    pub(in super::super) fn new(iter: I) -> Self {
+        assert!(N != 0, "chunk size must be non-zero");
+        Self { iter, remainder: None }
+    }
+
+    /// Returns an iterator over the remaining elements of the original iterator
+    /// that are not going to be returned by this iterator. The returned
+    /// iterator will yield at most `N-1` elements.
+    ///
+    /// Returns `None` if the end of the underlying iterator has not been
+    /// reached yet.
+    #[unstable(feature = "iter_array_chunks", reason = "recently added", issue = "0")]
+    #[inline]
+    pub fn into_remainder(self) -> Option<array::IntoIter<I::Item, N>> {
+        self.remainder
+    }
+}
+
+#[unstable(feature = "iter_array_chunks", reason = "recently added", issue = "0")]
+impl<I, const N: usize> Iterator for ArrayChunks<I, N>
+where
+    I: Iterator,
+{
+    type Item = [I::Item; N];
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        match array::iter_next_chunk(&mut self.iter) {
+            Ok(chunk) => Some(chunk),
+            Err(remainder) => {
+                // A remainder already taken from the back by `next_back` is
+                // kept, the one found here is empty in that case.
+                if self.remainder.is_none() {
+                    self.remainder = Some(remainder);
+                }
+                None
+            }
+        }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (lower, upper) = self.iter.size_hint();
+        (lower / N, upper.map(|n| n / N))
+    }
+
+    #[inline]
+    fn count(self) -> usize {
+        self.iter.count() / N
+    }
+}
+
+#[unstable(feature = "iter_array_chunks", reason = "recently added", issue = "0")]
+impl<I, const N: usize> DoubleEndedIterator for ArrayChunks<I, N>
+where
+    I: DoubleEndedIterator + ExactSizeIterator,
+{
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.next_back_remainder();
+
+        // The remainder is split off, so exactly a multiple of `N` elements
+        // is left and this always yields a full chunk or none at all.
+        let mut chunk = array::iter_next_chunk(&mut self.iter.by_ref().rev()).ok()?;
+        chunk.reverse();
+        Some(chunk)
+    }
+}
+
+impl<I, const N: usize> ArrayChunks<I, N>
+where
+    I: DoubleEndedIterator + ExactSizeIterator,
+{
+    /// Splits off the trailing `len % N` elements of the underlying iterator
+    /// into `self.remainder`, unless that has already happened.
+    fn next_back_remainder(&mut self) {
+        if self.remainder.is_some() {
+            return;
+        }
+
+        let rem = self.iter.len() % N;
+        // `rem < N`, so this always returns the elements that were pulled.
+        let mut remainder = match array::iter_next_chunk(&mut self.iter.by_ref().rev().take(rem)) {
+            Ok(_) => unreachable!(),
+            Err(remainder) => remainder,
+        };
+        // The elements were taken from the back, so restore their order.
+        remainder.as_mut_slice().reverse();
+        self.remainder = Some(remainder);
+    }
+}
+
+#[unstable(feature = "iter_array_chunks", reason = "recently added", issue = "0")]
+impl<I, const N: usize> FusedIterator for ArrayChunks<I, N> where I: FusedIterator {}
+
+#[unstable(feature = "iter_array_chunks", reason = "recently added", issue = "0")]
+impl<I, const N: usize> ExactSizeIterator for ArrayChunks<I, N>
+where
+    I: ExactSizeIterator,
+{
+    #[inline]
+    fn len(&self) -> usize {
+        self.iter.len() / N
+    }
+
+    #[inline]
+    fn is_empty(&self) -> bool {
+        self.iter.len() < N
+    }
+}
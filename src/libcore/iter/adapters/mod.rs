@@ -7,13 +7,21 @@ use crate::intrinsics;
 use super::{Iterator, DoubleEndedIterator, ExactSizeIterator, FusedIterator, TrustedLen};
 use super::LoopState;
 
+#[cfg(not(bootstrap))]
+mod array_chunks;
 mod chain;
 mod flatten;
+mod intersperse;
 mod zip;
 
+#[cfg(not(bootstrap))]
+#[unstable(feature = "iter_array_chunks", reason = "recently added", issue = "0")]
+pub use self::array_chunks::ArrayChunks;
 pub use self::chain::Chain;
 #[stable(feature = "rust1", since = "1.0.0")]
 pub use self::flatten::{FlatMap, Flatten};
+#[unstable(feature = "iter_intersperse", reason = "recently added", issue = "0")]
+pub use self::intersperse::{Intersperse, IntersperseWith};
 pub use self::zip::Zip;
 pub(crate) use self::zip::TrustedRandomAccess;
 
@@ -1386,6 +1394,68 @@ impl<I: Iterator, P> Iterator for TakeWhile<I, P>
 impl<I, P> FusedIterator for TakeWhile<I, P>
     where I: FusedIterator, P: FnMut(&I::Item) -> bool {}
 
+/// An iterator that only accepts elements while `predicate` returns `Some(_)`.
+///
+/// This `struct` is created by the [`map_while`] method on [`Iterator`]. See its
+/// documentation for more.
+///
+/// [`map_while`]: trait.Iterator.html#method.map_while
+/// [`Iterator`]: trait.Iterator.html
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+#[unstable(feature = "iter_map_while", reason = "recently added", issue = "0")]
+#[derive(Clone)]
+pub struct MapWhile<I, P> {
+    iter: I,
+    predicate: P,
+}
+
+impl<I, P> MapWhile<I, P> {
+    pub(super) fn new(iter: I, predicate: P) -> MapWhile<I, P> {
+        MapWhile { iter, predicate }
+    }
+}
+
+#[unstable(feature = "iter_map_while", reason = "recently added", issue = "0")]
+impl<I: fmt::Debug, P> fmt::Debug for MapWhile<I, P> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MapWhile")
+            .field("iter", &self.iter)
+            .finish()
+    }
+}
+
+#[unstable(feature = "iter_map_while", reason = "recently added", issue = "0")]
+impl<B, I: Iterator, P> Iterator for MapWhile<I, P>
+    where P: FnMut(I::Item) -> Option<B>
+{
+    type Item = B;
+
+    #[inline]
+    fn next(&mut self) -> Option<B> {
+        let x = self.iter.next()?;
+        (self.predicate)(x)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (_, upper) = self.iter.size_hint();
+        (0, upper) // can't know a lower bound, due to the predicate
+    }
+
+    #[inline]
+    fn try_fold<Acc, Fold, R>(&mut self, init: Acc, mut fold: Fold) -> R where
+        Self: Sized, Fold: FnMut(Acc, Self::Item) -> R, R: Try<Ok=Acc>
+    {
+        let p = &mut self.predicate;
+        self.iter.try_fold(init, move |acc, x|{
+            match p(x) {
+                Some(item) => LoopState::from_try(fold(acc, item)),
+                None => LoopState::Break(Try::from_ok(acc)),
+            }
+        }).into_try()
+    }
+}
+
 /// An iterator that skips over `n` elements of `iter`.
 ///
 /// This `struct` is created by the [`skip`] method on [`Iterator`]. See its
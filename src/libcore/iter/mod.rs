@@ -359,6 +359,13 @@ pub use self::adapters::StepBy;
 pub use self::adapters::Flatten;
 #[stable(feature = "iter_copied", since = "1.36.0")]
 pub use self::adapters::Copied;
+#[unstable(feature = "iter_intersperse", reason = "recently added", issue = "0")]
+pub use self::adapters::{Intersperse, IntersperseWith};
+#[unstable(feature = "iter_map_while", reason = "recently added", issue = "0")]
+pub use self::adapters::MapWhile;
+#[cfg(not(bootstrap))]
+#[unstable(feature = "iter_array_chunks", reason = "recently added", issue = "0")]
+pub use self::adapters::ArrayChunks;
 
 pub(crate) use self::adapters::TrustedRandomAccess;
 
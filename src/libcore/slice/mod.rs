@@ -45,6 +45,16 @@ pub mod memchr;
 mod rotate;
 mod sort;
 
+#[cfg(not(bootstrap))]
+mod array_iters;
+
+#[cfg(not(bootstrap))]
+#[unstable(feature = "array_chunks", issue = "0")]
+pub use self::array_iters::{ArrayChunks, ArrayChunksMut, ArrayRChunks, ArrayRChunksMut};
+#[cfg(not(bootstrap))]
+#[unstable(feature = "array_windows", issue = "0")]
+pub use self::array_iters::ArrayWindows;
+
 //
 // Extension traits
 //
@@ -918,6 +928,308 @@ impl<T> [T] {
         RChunksExactMut { v: snd, rem: fst, chunk_size }
     }
 
+    /// Splits the slice into a slice of `N`-element arrays, starting at the beginning of the
+    /// slice, and a remainder slice with length strictly less than `N`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `N` is 0.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(slice_as_chunks)]
+    /// let slice = ['l', 'o', 'r', 'e', 'm'];
+    /// let (chunks, remainder) = slice.as_chunks::<2>();
+    /// assert_eq!(chunks, &[['l', 'o'], ['r', 'e']]);
+    /// assert_eq!(remainder, &['m']);
+    /// ```
+    #[cfg(not(bootstrap))]
+    #[unstable(feature = "slice_as_chunks", issue = "0")]
+    #[inline]
+    pub fn as_chunks<const N: usize>(&self) -> (&[[T; N]], &[T]) {
+        assert!(N != 0);
+        let len = self.len() / N;
+        let (multiple_of_n, remainder) = self.split_at(len * N);
+        // SAFETY: `multiple_of_n` holds exactly `len * N` elements, and `[T; N]` has the
+        // same layout as `N` consecutive `T`s.
+        let array_slice = unsafe { from_raw_parts(multiple_of_n.as_ptr() as *const [T; N], len) };
+        (array_slice, remainder)
+    }
+
+    /// Splits the slice into a slice of `N`-element arrays, starting at the end of the slice,
+    /// and a remainder slice with length strictly less than `N`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `N` is 0.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(slice_as_chunks)]
+    /// let slice = ['l', 'o', 'r', 'e', 'm'];
+    /// let (remainder, chunks) = slice.as_rchunks::<2>();
+    /// assert_eq!(remainder, &['l']);
+    /// assert_eq!(chunks, &[['o', 'r'], ['e', 'm']]);
+    /// ```
+    #[cfg(not(bootstrap))]
+    #[unstable(feature = "slice_as_chunks", issue = "0")]
+    #[inline]
+    pub fn as_rchunks<const N: usize>(&self) -> (&[T], &[[T; N]]) {
+        assert!(N != 0);
+        let len = self.len() / N;
+        let (remainder, multiple_of_n) = self.split_at(self.len() - len * N);
+        // SAFETY: see `as_chunks`.
+        let array_slice = unsafe { from_raw_parts(multiple_of_n.as_ptr() as *const [T; N], len) };
+        (remainder, array_slice)
+    }
+
+    /// Splits the slice into a mutable slice of `N`-element arrays, starting at the beginning
+    /// of the slice, and a mutable remainder slice with length strictly less than `N`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `N` is 0.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(slice_as_chunks)]
+    /// let v = &mut [0, 0, 0, 0, 0];
+    /// let mut count = 1;
+    ///
+    /// let (chunks, remainder) = v.as_chunks_mut::<2>();
+    /// remainder[0] = 9;
+    /// for chunk in chunks {
+    ///     *chunk = [count; 2];
+    ///     count += 1;
+    /// }
+    /// assert_eq!(v, &[1, 1, 2, 2, 9]);
+    /// ```
+    #[cfg(not(bootstrap))]
+    #[unstable(feature = "slice_as_chunks", issue = "0")]
+    #[inline]
+    pub fn as_chunks_mut<const N: usize>(&mut self) -> (&mut [[T; N]], &mut [T]) {
+        assert!(N != 0);
+        let len = self.len() / N;
+        let (multiple_of_n, remainder) = self.split_at_mut(len * N);
+        // SAFETY: see `as_chunks`.
+        let array_slice = unsafe {
+            from_raw_parts_mut(multiple_of_n.as_mut_ptr() as *mut [T; N], len)
+        };
+        (array_slice, remainder)
+    }
+
+    /// Splits the slice into a mutable slice of `N`-element arrays, starting at the end of
+    /// the slice, and a mutable remainder slice with length strictly less than `N`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `N` is 0.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(slice_as_chunks)]
+    /// let v = &mut [0, 0, 0, 0, 0];
+    /// let mut count = 1;
+    ///
+    /// let (remainder, chunks) = v.as_rchunks_mut::<2>();
+    /// remainder[0] = 9;
+    /// for chunk in chunks {
+    ///     *chunk = [count; 2];
+    ///     count += 1;
+    /// }
+    /// assert_eq!(v, &[9, 1, 1, 2, 2]);
+    /// ```
+    #[cfg(not(bootstrap))]
+    #[unstable(feature = "slice_as_chunks", issue = "0")]
+    #[inline]
+    pub fn as_rchunks_mut<const N: usize>(&mut self) -> (&mut [T], &mut [[T; N]]) {
+        assert!(N != 0);
+        let len = self.len() / N;
+        let (remainder, multiple_of_n) = self.split_at_mut(self.len() - len * N);
+        // SAFETY: see `as_chunks`.
+        let array_slice = unsafe {
+            from_raw_parts_mut(multiple_of_n.as_mut_ptr() as *mut [T; N], len)
+        };
+        (remainder, array_slice)
+    }
+
+    /// Returns an iterator over `N` elements of the slice at a time, starting at the
+    /// beginning of the slice.
+    ///
+    /// The chunks are array references and do not overlap. If `N` does not divide the length
+    /// of the slice, then the last up to `N-1` elements will be omitted and can be retrieved
+    /// from the `remainder` function of the iterator.
+    ///
+    /// This method is the const generic equivalent of [`chunks_exact`]. As the chunks are
+    /// arrays, their length is known to the compiler and no bounds checks are needed when
+    /// indexing into them.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `N` is 0.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(array_chunks)]
+    /// let slice = ['l', 'o', 'r', 'e', 'm'];
+    /// let mut iter = slice.array_chunks();
+    /// assert_eq!(iter.next().unwrap(), &['l', 'o']);
+    /// assert_eq!(iter.next().unwrap(), &['r', 'e']);
+    /// assert!(iter.next().is_none());
+    /// assert_eq!(iter.remainder(), &['m']);
+    /// ```
+    ///
+    /// [`chunks_exact`]: #method.chunks_exact
+    #[cfg(not(bootstrap))]
+    #[unstable(feature = "array_chunks", issue = "0")]
+    #[inline]
+    pub fn array_chunks<const N: usize>(&self) -> ArrayChunks<'_, T, N> {
+        let (chunks, rem) = self.as_chunks();
+        ArrayChunks { iter: chunks.iter(), rem }
+    }
+
+    /// Returns an iterator over `N` elements of the slice at a time, starting at the
+    /// beginning of the slice.
+    ///
+    /// The chunks are mutable array references and do not overlap. If `N` does not divide
+    /// the length of the slice, then the last up to `N-1` elements will be omitted and can be
+    /// retrieved from the `into_remainder` function of the iterator.
+    ///
+    /// This method is the const generic equivalent of [`chunks_exact_mut`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `N` is 0.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(array_chunks)]
+    /// let v = &mut [0, 0, 0, 0, 0];
+    /// let mut count = 1;
+    ///
+    /// for chunk in v.array_chunks_mut() {
+    ///     *chunk = [count; 2];
+    ///     count += 1;
+    /// }
+    /// assert_eq!(v, &[1, 1, 2, 2, 0]);
+    /// ```
+    ///
+    /// [`chunks_exact_mut`]: #method.chunks_exact_mut
+    #[cfg(not(bootstrap))]
+    #[unstable(feature = "array_chunks", issue = "0")]
+    #[inline]
+    pub fn array_chunks_mut<const N: usize>(&mut self) -> ArrayChunksMut<'_, T, N> {
+        let (chunks, rem) = self.as_chunks_mut();
+        ArrayChunksMut { iter: chunks.iter_mut(), rem }
+    }
+
+    /// Returns an iterator over `N` elements of the slice at a time, starting at the end
+    /// of the slice.
+    ///
+    /// The chunks are array references and do not overlap. If `N` does not divide the length
+    /// of the slice, then the first up to `N-1` elements will be omitted and can be retrieved
+    /// from the `remainder` function of the iterator.
+    ///
+    /// This method is the const generic equivalent of [`rchunks_exact`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `N` is 0.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(array_chunks)]
+    /// let slice = ['l', 'o', 'r', 'e', 'm'];
+    /// let mut iter = slice.array_rchunks();
+    /// assert_eq!(iter.next().unwrap(), &['e', 'm']);
+    /// assert_eq!(iter.next().unwrap(), &['o', 'r']);
+    /// assert!(iter.next().is_none());
+    /// assert_eq!(iter.remainder(), &['l']);
+    /// ```
+    ///
+    /// [`rchunks_exact`]: #method.rchunks_exact
+    #[cfg(not(bootstrap))]
+    #[unstable(feature = "array_chunks", issue = "0")]
+    #[inline]
+    pub fn array_rchunks<const N: usize>(&self) -> ArrayRChunks<'_, T, N> {
+        let (rem, chunks) = self.as_rchunks();
+        ArrayRChunks { iter: chunks.iter(), rem }
+    }
+
+    /// Returns an iterator over `N` elements of the slice at a time, starting at the end
+    /// of the slice.
+    ///
+    /// The chunks are mutable array references and do not overlap. If `N` does not divide
+    /// the length of the slice, then the first up to `N-1` elements will be omitted and can be
+    /// retrieved from the `into_remainder` function of the iterator.
+    ///
+    /// This method is the const generic equivalent of [`rchunks_exact_mut`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `N` is 0.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(array_chunks)]
+    /// let v = &mut [0, 0, 0, 0, 0];
+    /// let mut count = 1;
+    ///
+    /// for chunk in v.array_rchunks_mut() {
+    ///     *chunk = [count; 2];
+    ///     count += 1;
+    /// }
+    /// assert_eq!(v, &[0, 2, 2, 1, 1]);
+    /// ```
+    ///
+    /// [`rchunks_exact_mut`]: #method.rchunks_exact_mut
+    #[cfg(not(bootstrap))]
+    #[unstable(feature = "array_chunks", issue = "0")]
+    #[inline]
+    pub fn array_rchunks_mut<const N: usize>(&mut self) -> ArrayRChunksMut<'_, T, N> {
+        let (rem, chunks) = self.as_rchunks_mut();
+        ArrayRChunksMut { iter: chunks.iter_mut(), rem }
+    }
+
+    /// Returns an iterator over overlapping windows of `N` elements of the slice, starting
+    /// at the beginning of the slice.
+    ///
+    /// This is the const generic equivalent of [`windows`]. If the slice is shorter than `N`,
+    /// the iterator returns no values.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `N` is 0.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(array_windows)]
+    /// let slice = [0, 1, 2, 3];
+    /// let mut iter = slice.array_windows();
+    /// assert_eq!(iter.next().unwrap(), &[0, 1]);
+    /// assert_eq!(iter.next().unwrap(), &[1, 2]);
+    /// assert_eq!(iter.next().unwrap(), &[2, 3]);
+    /// assert!(iter.next().is_none());
+    /// ```
+    ///
+    /// [`windows`]: #method.windows
+    #[cfg(not(bootstrap))]
+    #[unstable(feature = "array_windows", issue = "0")]
+    #[inline]
+    pub fn array_windows<const N: usize>(&self) -> ArrayWindows<'_, T, N> {
+        assert!(N != 0);
+        ArrayWindows { v: self }
+    }
+
     /// Divides one slice into two at an index.
     ///
     /// The first will contain all indices from `[0, mid)` (excluding
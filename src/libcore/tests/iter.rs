@@ -2496,3 +2496,214 @@ fn test_partition() {
     check(xs, |&x| x < 3, 3); // small
     check(xs, |&x| x > 6, 3); // large
 }
+
+#[test]
+fn test_intersperse() {
+    let v = std::iter::empty().intersperse(0u32).collect::<Vec<_>>();
+    assert_eq!(v, vec![]);
+
+    let v = std::iter::once(1).intersperse(0).collect::<Vec<_>>();
+    assert_eq!(v, vec![1]);
+
+    let xs = ["a", "", "b", "c"];
+    let v: Vec<&str> = xs.iter().map(|x| x.clone()).intersperse(", ").collect();
+    let text: String = v.concat();
+    assert_eq!(text, "a, , b, c".to_string());
+
+    let ys = [0, 1, 2, 3];
+    let mut it = ys[..0].iter().map(|x| *x).intersperse(1);
+    assert!(it.next() == None);
+}
+
+#[test]
+fn test_intersperse_size_hint() {
+    let iter = std::iter::empty::<i32>().intersperse(0);
+    assert_eq!(iter.size_hint(), (0, Some(0)));
+
+    let xs = ["a", "", "b", "c"];
+    let mut iter = xs.iter().map(|x| x.clone()).intersperse(", ");
+    assert_eq!(iter.size_hint(), (7, Some(7)));
+
+    assert_eq!(iter.next(), Some("a"));
+    assert_eq!(iter.size_hint(), (6, Some(6)));
+    assert_eq!(iter.next(), Some(", "));
+    assert_eq!(iter.size_hint(), (5, Some(5)));
+
+    assert_eq!([].iter().intersperse(&()).size_hint(), (0, Some(0)));
+}
+
+#[test]
+fn test_fold_specialization_intersperse() {
+    let mut iter = (1..2).intersperse(0);
+    iter.clone().for_each(|x| assert_eq!(Some(x), iter.next()));
+
+    let mut iter = (1..3).intersperse(0);
+    iter.clone().for_each(|x| assert_eq!(Some(x), iter.next()));
+
+    let mut iter = (1..4).intersperse(0);
+    iter.clone().for_each(|x| assert_eq!(Some(x), iter.next()));
+
+    // Folding after the first element was already yielded.
+    let mut iter = (1..4).intersperse(0);
+    assert_eq!(iter.next(), Some(1));
+    assert_eq!(iter.fold(Vec::new(), |mut v, x| { v.push(x); v }), vec![0, 2, 0, 3]);
+}
+
+#[test]
+fn test_intersperse_with() {
+    #[derive(PartialEq, Debug)]
+    struct NotClone {
+        u: u32,
+    }
+    let r = vec![NotClone { u: 0 }, NotClone { u: 1 }]
+        .into_iter()
+        .intersperse_with(|| NotClone { u: 2 })
+        .collect::<Vec<_>>();
+    assert_eq!(r, vec![NotClone { u: 0 }, NotClone { u: 2 }, NotClone { u: 1 }]);
+
+    let mut ctr = 100;
+    let separator = || {
+        ctr *= 2;
+        ctr
+    };
+    let r = (0..3).intersperse_with(separator).collect::<Vec<_>>();
+    assert_eq!(r, vec![0, 200, 1, 400, 2]);
+}
+
+#[test]
+fn test_intersperse_with_not_called_at_end() {
+    let mut calls = 0;
+    let v = (0..2).intersperse_with(|| { calls += 1; 9 }).collect::<Vec<_>>();
+    assert_eq!(v, vec![0, 9, 1]);
+    assert_eq!(calls, 1);
+}
+
+#[test]
+fn test_map_while() {
+    let xs = [0, 1, 2, 3, 5, 13, 15, 16, 17, 19];
+    let ys = [0, 2, 4, 6, 10];
+    let mut it = xs.iter().map_while(|&x| if x < 10 { Some(x * 2) } else { None });
+    let mut i = 0;
+    for x in it.by_ref() {
+        assert_eq!(x, ys[i]);
+        i += 1;
+    }
+    assert_eq!(i, ys.len());
+    assert_eq!(it.size_hint(), (0, Some(4)));
+}
+
+#[test]
+fn test_map_while_not_fused() {
+    let xs = [1, -1, 2];
+    let mut it = xs.iter().map_while(|&x| if x > 0 { Some(x) } else { None });
+    assert_eq!(it.next(), Some(1));
+    assert_eq!(it.next(), None);
+    assert_eq!(it.next(), Some(2));
+    assert_eq!(it.next(), None);
+}
+
+#[test]
+fn test_map_while_try_fold() {
+    let xs = [1, 2, 3, -4, 5];
+    let mut it = xs.iter().map_while(|&x| if x > 0 { Some(x * 10) } else { None });
+    assert_eq!(it.try_fold(0, |acc, x| Some(acc + x)), Some(60));
+    assert_eq!(it.next(), Some(50));
+}
+
+#[test]
+fn test_iterator_array_chunks() {
+    let mut it = (0..7).array_chunks::<3>();
+    assert_eq!(it.size_hint(), (2, Some(2)));
+    assert_eq!(it.len(), 2);
+    assert_eq!(it.next(), Some([0, 1, 2]));
+    assert_eq!(it.next(), Some([3, 4, 5]));
+    assert_eq!(it.next(), None);
+    assert_eq!(it.into_remainder().unwrap().as_slice(), &[6]);
+
+    let it = (0..6).array_chunks::<2>();
+    assert_eq!(it.count(), 3);
+
+    let mut it = (0..6).array_chunks::<3>();
+    assert_eq!(it.next(), Some([0, 1, 2]));
+    assert_eq!(it.next(), Some([3, 4, 5]));
+    assert_eq!(it.next(), None);
+    assert_eq!(it.into_remainder().unwrap().len(), 0);
+
+    let it = (0..6).array_chunks::<3>();
+    assert!(it.into_remainder().is_none());
+}
+
+#[test]
+fn test_iterator_array_chunks_rev() {
+    let mut it = (0..8).array_chunks::<3>();
+    assert_eq!(it.next_back(), Some([3, 4, 5]));
+    assert_eq!(it.next(), Some([0, 1, 2]));
+    assert_eq!(it.next_back(), None);
+    assert_eq!(it.next(), None);
+    assert_eq!(it.into_remainder().unwrap().as_slice(), &[6, 7]);
+
+    let v = (0..10).array_chunks::<2>().rev().collect::<Vec<_>>();
+    assert_eq!(v, vec![[8, 9], [6, 7], [4, 5], [2, 3], [0, 1]]);
+}
+
+#[test]
+fn test_iterator_array_chunks_drops() {
+    #[derive(Clone)]
+    struct Foo<'a>(&'a Cell<usize>);
+
+    impl Drop for Foo<'_> {
+        fn drop(&mut self) {
+            self.0.set(self.0.get() + 1);
+        }
+    }
+
+    let count = Cell::new(0);
+    let mut it = std::iter::repeat(Foo(&count)).take(5).array_chunks::<2>();
+    assert_eq!(count.get(), 0);
+    drop(it.next());
+    assert_eq!(count.get(), 2);
+    drop(it.next());
+    assert_eq!(count.get(), 4);
+    assert!(it.next().is_none());
+    assert_eq!(count.get(), 4);
+    drop(it);
+    // The remainder and the `repeat` prototype are dropped with the adapter.
+    assert_eq!(count.get(), 6);
+}
+
+#[test]
+#[should_panic]
+fn test_iterator_array_chunks_zero() {
+    let _ = (0..4).array_chunks::<0>();
+}
+
+#[test]
+fn test_iterator_next_chunk() {
+    // This will panic if the iterator is not exhausted properly.
+    let mut it = 0..12;
+    assert_eq!(it.next_chunk().unwrap(), [0, 1, 2, 3]);
+    assert_eq!(it.next_chunk().unwrap(), []);
+    assert_eq!(it.next_chunk().unwrap(), [4, 5, 6, 7, 8, 9]);
+    assert_eq!(it.next_chunk::<4>().unwrap_err().as_slice(), &[10, 11]);
+    assert_eq!(it.next_chunk::<1>().unwrap_err().as_slice(), &[]);
+}
+
+#[test]
+fn test_iterator_try_collect() {
+    let a = vec![Ok(1), Ok(2), Ok(3)];
+    assert_eq!(a.into_iter().try_collect::<Vec<i32>>(), Ok::<_, ()>(vec![1, 2, 3]));
+
+    let a = vec![Ok(1), Err(2), Ok(3), Err(4)];
+    let mut it = a.into_iter();
+    assert_eq!(it.try_collect::<Vec<i32>>(), Err(2));
+    assert_eq!(it.try_collect::<Vec<i32>>(), Err(4));
+    assert_eq!(it.try_collect::<Vec<i32>>(), Ok(vec![]));
+
+    let a = vec![Some(1), Some(2)];
+    assert_eq!(a.into_iter().try_collect::<Vec<i32>>().ok(), Some(vec![1, 2]));
+
+    let a = vec![Some(1), None, Some(3)];
+    let mut it = a.into_iter();
+    assert!(it.try_collect::<Vec<i32>>().is_err());
+    assert_eq!(it.next(), Some(Some(3)));
+}
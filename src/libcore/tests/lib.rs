@@ -1,3 +1,7 @@
+#![feature(array_chunks)]
+#![feature(array_map)]
+#![feature(array_value_iter)]
+#![feature(array_windows)]
 #![feature(bound_cloned)]
 #![feature(box_syntax)]
 #![feature(cell_update)]
@@ -12,7 +16,12 @@
 #![feature(fmt_internals)]
 #![feature(hashmap_internals)]
 #![feature(is_sorted)]
+#![feature(iter_array_chunks)]
+#![feature(iter_intersperse)]
+#![feature(iter_map_while)]
+#![feature(iter_next_chunk)]
 #![feature(iter_once_with)]
+#![feature(iterator_try_collect)]
 #![feature(pattern)]
 #![feature(range_is_empty)]
 #![feature(raw)]
@@ -29,10 +38,12 @@
 #![feature(inner_deref)]
 #![feature(slice_internals)]
 #![feature(slice_partition_dedup)]
+#![feature(slice_as_chunks)]
 #![feature(int_error_matching)]
 #![feature(const_fn)]
 #![feature(iter_partition_in_place)]
 #![feature(iter_is_partitioned)]
+#![feature(once_cell)]
 #![warn(rust_2018_idioms)]
 
 extern crate test;
@@ -723,6 +723,224 @@ fn test_windows_zip() {
     assert_eq!(res, [14, 18, 22, 26]);
 }
 
+#[test]
+fn test_as_chunks() {
+    let v: &[i32] = &[0, 1, 2, 3, 4];
+    let (chunks, rem) = v.as_chunks::<2>();
+    assert_eq!(chunks, &[[0, 1], [2, 3]]);
+    assert_eq!(rem, &[4]);
+
+    let (rem, chunks) = v.as_rchunks::<2>();
+    assert_eq!(rem, &[0]);
+    assert_eq!(chunks, &[[1, 2], [3, 4]]);
+
+    let (chunks, rem) = v.as_chunks::<6>();
+    assert!(chunks.is_empty());
+    assert_eq!(rem, v);
+
+    let v: &mut [i32] = &mut [0, 1, 2, 3, 4, 5];
+    let (chunks, rem) = v.as_chunks_mut::<3>();
+    assert!(rem.is_empty());
+    chunks[1] = [7; 3];
+    let (_, chunks) = v.as_rchunks_mut::<4>();
+    chunks[0][0] += 10;
+    assert_eq!(v, &[0, 1, 12, 7, 7, 7]);
+}
+
+#[test]
+#[should_panic]
+fn test_as_chunks_zero() {
+    let v: &[i32] = &[0, 1, 2];
+    let _ = v.as_chunks::<0>();
+}
+
+#[test]
+fn test_array_chunks_infer() {
+    let v: &[i32] = &[0, 1, 2, 3, 4, -4];
+    let c = v.array_chunks();
+    for &[a, b, c] in c {
+        assert_eq!(a + b + c, 3);
+    }
+
+    let v2: &[i32] = &[0, 1, 2, 3, 4, 5, 6];
+    let total = v2.array_chunks().map(|&[a, b]| a * b).sum::<i32>();
+    assert_eq!(total, 2 * 3 + 4 * 5);
+}
+
+#[test]
+fn test_array_chunks_count() {
+    let v: &[i32] = &[0, 1, 2, 3, 4, 5];
+    let c = v.array_chunks::<3>();
+    assert_eq!(c.count(), 2);
+
+    let v2: &[i32] = &[0, 1, 2, 3, 4];
+    let c2 = v2.array_chunks::<2>();
+    assert_eq!(c2.count(), 2);
+
+    let v3: &[i32] = &[];
+    let c3 = v3.array_chunks::<2>();
+    assert_eq!(c3.count(), 0);
+}
+
+#[test]
+fn test_array_chunks_nth() {
+    let v: &[i32] = &[0, 1, 2, 3, 4, 5];
+    let mut c = v.array_chunks::<2>();
+    assert_eq!(c.nth(1).unwrap(), &[2, 3]);
+    assert_eq!(c.next().unwrap(), &[4, 5]);
+
+    let v2: &[i32] = &[0, 1, 2, 3, 4, 5, 6];
+    let mut c2 = v2.array_chunks::<3>();
+    assert_eq!(c2.nth(1).unwrap(), &[3, 4, 5]);
+    assert_eq!(c2.next(), None);
+    assert_eq!(c2.remainder(), &[6]);
+}
+
+#[test]
+fn test_array_chunks_nth_back() {
+    let v: &[i32] = &[0, 1, 2, 3, 4, 5];
+    let mut c = v.array_chunks::<2>();
+    assert_eq!(c.nth_back(1).unwrap(), &[2, 3]);
+    assert_eq!(c.next().unwrap(), &[0, 1]);
+    assert_eq!(c.next(), None);
+}
+
+#[test]
+fn test_array_chunks_zip() {
+    let v1: &[i32] = &[0, 1, 2, 3, 4];
+    let v2: &[i32] = &[6, 7, 8, 9, 10];
+
+    let res = v1.array_chunks::<2>()
+        .zip(v2.array_chunks::<2>())
+        .map(|(a, b)| a.iter().sum::<i32>() + b.iter().sum::<i32>())
+        .collect::<Vec<_>>();
+    assert_eq!(res, vec![14, 22]);
+}
+
+#[test]
+fn test_array_chunks_mut() {
+    let v: &mut [i32] = &mut [0, 1, 2, 3, 4, 5, 6];
+    for a in v.array_chunks_mut::<3>() {
+        a[0] += 10;
+    }
+    assert_eq!(v, &[10, 1, 2, 13, 4, 5, 6]);
+
+    let mut c = v.array_chunks_mut::<2>();
+    assert_eq!(c.len(), 3);
+    assert_eq!(c.next_back().unwrap(), &mut [4, 5]);
+    c.into_remainder()[0] = 0;
+    assert_eq!(v, &[10, 1, 2, 13, 4, 5, 0]);
+}
+
+#[test]
+fn test_array_rchunks() {
+    let v: &[i32] = &[0, 1, 2, 3, 4];
+    let mut c = v.array_rchunks::<2>();
+    assert_eq!(c.len(), 2);
+    assert_eq!(c.next().unwrap(), &[3, 4]);
+    assert_eq!(c.next_back().unwrap(), &[1, 2]);
+    assert_eq!(c.next(), None);
+    assert_eq!(c.remainder(), &[0]);
+
+    let mut c = v.array_rchunks::<1>();
+    assert_eq!(c.nth(1).unwrap(), &[3]);
+    assert_eq!(c.nth_back(1).unwrap(), &[1]);
+    assert_eq!(c.last().unwrap(), &[2]);
+
+    let res = v.array_rchunks::<2>()
+        .zip(v.array_chunks::<2>())
+        .map(|(a, b)| a[0] * b[0])
+        .collect::<Vec<_>>();
+    assert_eq!(res, vec![0, 2]);
+}
+
+#[test]
+fn test_array_rchunks_mut() {
+    let v: &mut [i32] = &mut [0, 1, 2, 3, 4];
+    for a in v.array_rchunks_mut::<2>() {
+        a[1] *= 10;
+    }
+    assert_eq!(v, &[0, 1, 20, 3, 40]);
+
+    let c = v.array_rchunks_mut::<3>();
+    c.into_remainder()[1] = 7;
+    assert_eq!(v, &[0, 7, 20, 3, 40]);
+}
+
+#[test]
+fn test_array_windows_infer() {
+    let v: &[i32] = &[0, 1, 0, 1];
+    assert_eq!(v.array_windows::<2>().count(), 3);
+    let c = v.array_windows();
+    for &[a, b] in c {
+        assert_eq!(a + b, 1);
+    }
+
+    let v2: &[i32] = &[0, 1, 2, 3, 4, 5, 6];
+    let total: usize = v2.array_windows().map(|&[a, b, c]| (a + b + c) as usize).sum();
+    assert_eq!(total, 3 + 6 + 9 + 12 + 15);
+}
+
+#[test]
+fn test_array_windows_count() {
+    let v: &[i32] = &[0, 1, 2, 3, 4, 5];
+    let c = v.array_windows::<3>();
+    assert_eq!(c.count(), 4);
+
+    let v2: &[i32] = &[0, 1, 2, 3, 4];
+    let c2 = v2.array_windows::<6>();
+    assert_eq!(c2.count(), 0);
+
+    let v3: &[i32] = &[];
+    let c3 = v3.array_windows::<2>();
+    assert_eq!(c3.count(), 0);
+}
+
+#[test]
+fn test_array_windows_nth() {
+    let v: &[i32] = &[0, 1, 2, 3, 4, 5];
+    let snd = v.array_windows::<4>().nth(1);
+    assert_eq!(snd, Some(&[1, 2, 3, 4]));
+    let mut arr_iter = v.array_windows::<2>();
+    assert_ne!(arr_iter.nth(0), arr_iter.nth(0));
+    let last = v.array_windows::<3>().last();
+    assert_eq!(last, Some(&[3, 4, 5]));
+}
+
+#[test]
+fn test_array_windows_nth_back() {
+    let v: &[i32] = &[0, 1, 2, 3, 4, 5];
+    let mut c = v.array_windows::<2>();
+    assert_eq!(c.nth_back(2).unwrap(), &[2, 3]);
+    assert_eq!(c.next_back().unwrap(), &[1, 2]);
+    assert_eq!(c.len(), 1);
+
+    let v2: &[i32] = &[0, 1, 2, 3, 4];
+    let mut c2 = v2.array_windows::<4>();
+    assert_eq!(c2.nth_back(1).unwrap(), &[0, 1, 2, 3]);
+    assert_eq!(c2.next_back(), None);
+}
+
+#[test]
+fn test_array_windows_zip() {
+    let v1: &[i32] = &[0, 1, 2, 3, 4];
+    let v2: &[i32] = &[6, 7, 8, 9, 10];
+
+    let res = v1.array_windows::<2>()
+        .zip(v2.array_windows::<2>())
+        .map(|(a, b)| a.iter().sum::<i32>() + b.iter().sum::<i32>())
+        .collect::<Vec<_>>();
+
+    assert_eq!(res, [14, 18, 22, 26]);
+}
+
+#[test]
+#[should_panic]
+fn test_array_windows_zero() {
+    let v: &[i32] = &[0, 1, 2];
+    let _ = v.array_windows::<0>();
+}
+
 #[test]
 #[allow(const_err)]
 fn test_iter_ref_consistency() {
@@ -248,6 +248,7 @@ language_item_table! {
 //  Variant name,                Name,                 Method name,             Target;
     CharImplItem,                "char",               char_impl,               Target::Impl;
     StrImplItem,                 "str",                str_impl,                Target::Impl;
+    ArrayImplItem,               "array",              array_impl,              Target::Impl;
     SliceImplItem,               "slice",              slice_impl,              Target::Impl;
     SliceU8ImplItem,             "slice_u8",           slice_u8_impl,           Target::Impl;
     StrAllocImplItem,            "str_alloc",          str_alloc_impl,          Target::Impl;
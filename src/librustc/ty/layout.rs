@@ -2208,9 +2208,13 @@ where
                 }
 
                 // FIXME(eddyb) This should be for `ptr::Unique<T>`, not `Box<T>`.
+                // The allocator of a `Box<T, A>` may be laid out first, only the
+                // pointer in field 0 is unique.
                 if let Some(ref mut pointee) = result {
                     if let ty::Adt(def, _) = this.ty.sty {
-                        if def.is_box() && offset.bytes() == 0 {
+                        if def.is_box() && offset.bytes() == 0 &&
+                            this.fields.offset(0) == offset
+                        {
                             pointee.safe = Some(PointerKind::UniqueOwned);
                         }
                     }
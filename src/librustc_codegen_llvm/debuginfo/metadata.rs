@@ -627,7 +627,8 @@ pub fn type_metadata(
                 Err(metadata) => return metadata,
             }
         }
-        ty::Adt(def, _) if def.is_box() => {
+        // A box with a non-zero-sized allocator is described like any other struct.
+        ty::Adt(def, _) if def.is_box() && cx.layout_of(t).field(cx, 1).is_zst() => {
             match ptr_metadata(t.boxed_ty()) {
                 Ok(res) => res,
                 Err(metadata) => return metadata,
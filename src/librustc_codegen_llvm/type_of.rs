@@ -323,7 +323,8 @@ impl<'tcx> LayoutLlvmExt<'tcx> for TyLayout<'tcx> {
             ty::RawPtr(_) => {
                 return self.field(cx, index).llvm_type(cx);
             }
-            ty::Adt(def, _) if def.is_box() => {
+            // A box with a non-zero-sized allocator is laid out like any other struct.
+            ty::Adt(def, _) if def.is_box() && self.field(cx, 1).is_zst() => {
                 let ptr_ty = cx.tcx.mk_mut_ptr(self.ty.boxed_ty());
                 return cx.layout_of(ptr_ty).scalar_pair_element_llvm_type(cx, index, immediate);
             }
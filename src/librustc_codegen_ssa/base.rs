@@ -215,6 +215,8 @@ pub fn coerce_unsized_into<'a, 'tcx, Bx: BuilderMethods<'a, 'tcx>>(
 ) {
     let src_ty = src.layout.ty;
     let dst_ty = dst.layout.ty;
+    // A box with a non-zero-sized allocator is coerced field by field below.
+    let src_is_ptr = !src_ty.is_box() || src.layout.field(bx.cx(), 1).is_zst();
     let mut coerce_ptr = || {
         let (base, info) = match bx.load_operand(src).val {
             OperandValue::Pair(base, info) => {
@@ -238,7 +240,9 @@ pub fn coerce_unsized_into<'a, 'tcx, Bx: BuilderMethods<'a, 'tcx>>(
         (&ty::RawPtr(..), &ty::RawPtr(..)) => {
             coerce_ptr()
         }
-        (&ty::Adt(def_a, _), &ty::Adt(def_b, _)) if def_a.is_box() && def_b.is_box() => {
+        (&ty::Adt(def_a, _), &ty::Adt(def_b, _))
+            if def_a.is_box() && def_b.is_box() && src_is_ptr =>
+        {
             coerce_ptr()
         }
 
@@ -9,7 +9,7 @@ use crate::glue;
 use crate::traits::*;
 
 use super::{FunctionCx, LocalRef};
-use super::operand::OperandValue;
+use super::operand::{OperandRef, OperandValue};
 
 #[derive(Copy, Clone, Debug)]
 pub struct PlaceRef<'tcx, V> {
@@ -457,8 +457,31 @@ impl<'a, 'tcx, Bx: BuilderMethods<'a, 'tcx>> FunctionCx<'a, 'tcx, Bx> {
                 ref base,
                 elem: mir::ProjectionElem::Deref
             }) => {
-                // Load the pointer from its location.
-                self.codegen_consume(bx, base).deref(bx.cx())
+                let base_ty = self.monomorphized_place_ty(base);
+                let base_layout = cx.layout_of(base_ty);
+                if base_ty.is_box() && !base_layout.field(cx, 1).is_zst() {
+                    // A box with a non-zero-sized allocator isn't a plain
+                    // pointer, so only load the pointer in its first field.
+                    let field_ty = base_layout.field(cx, 0).ty;
+                    let ptr_place = base.clone().field(mir::Field::new(0), field_ty);
+                    let ptr = self.codegen_consume(bx, &ptr_place);
+                    let ptr_layout = cx.layout_of(tcx.mk_mut_ptr(base_ty.boxed_ty()));
+                    let val = match ptr.val {
+                        OperandValue::Immediate(llptr) => {
+                            let llty = cx.immediate_backend_type(ptr_layout);
+                            OperandValue::Immediate(bx.pointercast(llptr, llty))
+                        }
+                        OperandValue::Pair(llptr, llextra) => {
+                            let llty = cx.scalar_pair_element_backend_type(ptr_layout, 0, true);
+                            OperandValue::Pair(bx.pointercast(llptr, llty), llextra)
+                        }
+                        OperandValue::Ref(..) => bug!("by-Ref pointer of box {:?}", base),
+                    };
+                    OperandRef { val, layout: ptr_layout }.deref(cx)
+                } else {
+                    // Load the pointer from its location.
+                    self.codegen_consume(bx, base).deref(cx)
+                }
             }
             mir::Place::Projection(ref projection) => {
                 let cg_base = self.codegen_place(bx, &projection.base);
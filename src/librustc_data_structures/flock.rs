@@ -3,6 +3,10 @@
 //! This is not meant to be in the standard library, it does nothing with
 //! green/native threading. This is just a bare-bones enough solution for
 //! librustdoc, it is not production quality at all.
+//!
+//! Apart from the bootstrap compiler, which doesn't have them yet, this uses
+//! the locks of `std::fs::File`. Solaris keeps using `fcntl` locks, as it has
+//! no `flock` to build the former on.
 
 #![allow(non_camel_case_types)]
 #![allow(nonstandard_style)]
@@ -11,7 +15,57 @@ use std::io;
 use std::path::Path;
 
 cfg_if! {
-    if #[cfg(unix)] {
+    if #[cfg(all(not(bootstrap), not(target_os = "solaris")))] {
+        use std::fs::{File, OpenOptions};
+
+        #[derive(Debug)]
+        pub struct Lock {
+            _file: File,
+        }
+
+        impl Lock {
+            pub fn new(p: &Path,
+                       wait: bool,
+                       create: bool,
+                       exclusive: bool)
+                       -> io::Result<Lock> {
+                let mut open_options = OpenOptions::new();
+                open_options.read(true);
+                if create {
+                    open_options.create(true)
+                                .write(true);
+                }
+
+                debug!("Attempting to open lock file `{}`", p.display());
+                let file = open_options.open(p)?;
+
+                debug!("Attempting to acquire lock on lock file `{}`", p.display());
+                let locked = match (wait, exclusive) {
+                    (true, true) => file.lock_exclusive().map(|()| true),
+                    (true, false) => file.lock_shared().map(|()| true),
+                    (false, true) => file.try_lock_exclusive(),
+                    (false, false) => file.try_lock_shared(),
+                };
+                match locked {
+                    Ok(true) => {
+                        debug!("Successfully acquired lock.");
+                        Ok(Lock { _file: file })
+                    }
+                    Ok(false) => {
+                        let msg = "the lock is held by another process";
+                        Err(io::Error::new(io::ErrorKind::WouldBlock, msg))
+                    }
+                    Err(err) => {
+                        debug!("Failed acquiring file lock: {}", err);
+                        Err(err)
+                    }
+                }
+            }
+        }
+
+        // Note that we don't need a Drop impl: the file is unlocked
+        // automatically when it's closed.
+    } else if #[cfg(unix)] {
         use std::ffi::{CString, OsStr};
         use std::os::unix::prelude::*;
 
@@ -24,6 +24,7 @@
 #![feature(integer_atomics)]
 
 #![cfg_attr(unix, feature(libc))]
+#![cfg_attr(not(bootstrap), feature(file_lock))]
 #![cfg_attr(test, feature(test))]
 
 #![deny(rust_2018_idioms)]
@@ -33,7 +34,7 @@
 extern crate log;
 #[allow(unused_extern_crates)]
 extern crate serialize as rustc_serialize; // used by deriving
-#[cfg(unix)]
+#[cfg(all(unix, any(bootstrap, target_os = "solaris")))]
 extern crate libc;
 #[macro_use]
 extern crate cfg_if;
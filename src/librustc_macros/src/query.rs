@@ -474,7 +474,7 @@ pub fn rustc_queries(input: TokenStream) -> TokenStream {
             };
 
             let mut attribute_stream = quote! {};
-            for e in attributes.into_iter().intersperse(quote! {,}) {
+            for e in Itertools::intersperse(attributes.into_iter(), quote! {,}) {
                 attribute_stream.extend(e);
             }
 
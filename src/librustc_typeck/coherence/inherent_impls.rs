@@ -83,6 +83,14 @@ impl ItemLikeVisitor<'v> for InherentCollect<'tcx> {
                                           "str",
                                           item.span);
             }
+            ty::Array(_, _) => {
+                self.check_primitive_impl(def_id,
+                                          lang_items.array_impl(),
+                                          None,
+                                          "array",
+                                          "[T; N]",
+                                          item.span);
+            }
             ty::Slice(slice_item) if slice_item == self.tcx.types.u8 => {
                 self.check_primitive_impl(def_id,
                                           lang_items.slice_u8_impl(),
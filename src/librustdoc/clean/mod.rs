@@ -3883,7 +3883,7 @@ fn build_deref_target_impls(cx: &DocContext<'_>,
             Bool => None,
             Str => tcx.lang_items().str_impl(),
             Slice => tcx.lang_items().slice_impl(),
-            Array => tcx.lang_items().slice_impl(),
+            Array => tcx.lang_items().array_impl(),
             Tuple => None,
             Unit => None,
             RawPointer => tcx.lang_items().const_ptr_impl(),
@@ -3897,6 +3897,20 @@ fn build_deref_target_impls(cx: &DocContext<'_>,
                 inline::build_impl(cx, did, ret);
             }
         }
+
+        // Arrays unsize to slices, so the methods of slices can be called
+        // through the `Deref` as well.
+        if let Type::Array(ref elem, _) = *target {
+            let mut slice_impls = vec![tcx.lang_items().slice_impl()];
+            if let Primitive(U8) = **elem {
+                slice_impls.push(tcx.lang_items().slice_u8_impl());
+            }
+            for did in slice_impls.into_iter().filter_map(|did| did) {
+                if !did.is_local() {
+                    inline::build_impl(cx, did, ret);
+                }
+            }
+        }
     }
 }
 
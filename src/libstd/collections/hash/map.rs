@@ -823,6 +823,81 @@ where
         self.base.insert(k, v)
     }
 
+    /// Inserts a key-value pair into the map, returning an error instead of
+    /// aborting if the map needs to grow and the allocator reports a failure.
+    ///
+    /// On success this returns what [`insert`] would. On error, the key and
+    /// value are dropped and the map is left unchanged.
+    ///
+    /// [`insert`]: #method.insert
+    ///
+    /// # Errors
+    ///
+    /// If the capacity overflows, or the allocator reports a failure, then an error
+    /// is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(try_reserve)]
+    /// use std::collections::HashMap;
+    ///
+    /// let mut map = HashMap::new();
+    /// assert_eq!(map.try_insert(37, "a"), Ok(None));
+    /// assert_eq!(map.try_insert(37, "b"), Ok(Some("a")));
+    /// assert_eq!(map[&37], "b");
+    /// ```
+    #[inline]
+    #[unstable(feature = "try_reserve", reason = "new API", issue = "48043")]
+    pub fn try_insert(&mut self, k: K, v: V) -> Result<Option<V>, CollectionAllocErr> {
+        // Once there is room for one more element, `insert` cannot grow the table.
+        self.try_reserve(1)?;
+        Ok(self.insert(k, v))
+    }
+
+    /// Extends the map with the contents of an iterator, returning an error
+    /// instead of aborting if the map needs to grow and the allocator reports
+    /// a failure.
+    ///
+    /// On error, the pairs inserted so far stay in the map and the rest of the
+    /// iterator is dropped.
+    ///
+    /// # Errors
+    ///
+    /// If the capacity overflows, or the allocator reports a failure, then an error
+    /// is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(try_reserve)]
+    /// use std::collections::HashMap;
+    ///
+    /// let mut map = HashMap::new();
+    /// map.try_extend(vec![(1, "a"), (2, "b")])
+    ///    .expect("why is the test harness OOMing on two entries?");
+    /// assert_eq!(map.len(), 2);
+    /// ```
+    #[unstable(feature = "try_reserve", reason = "new API", issue = "48043")]
+    pub fn try_extend<T>(&mut self, iter: T) -> Result<(), CollectionAllocErr>
+    where
+        T: IntoIterator<Item = (K, V)>,
+    {
+        // Reserve the way `extend` does: if the map already holds keys, only
+        // half of the iterator is expected to be new ones.
+        let iter = iter.into_iter();
+        let reserve = if self.is_empty() {
+            iter.size_hint().0
+        } else {
+            (iter.size_hint().0 + 1) / 2
+        };
+        self.try_reserve(reserve)?;
+        for (k, v) in iter {
+            self.try_insert(k, v)?;
+        }
+        Ok(())
+    }
+
     /// Removes a key from the map, returning the value at the key if the key
     /// was previously in the map.
     ///
@@ -3409,6 +3484,40 @@ mod test_map {
         }
     }
 
+    #[test]
+    fn test_try_insert() {
+        let mut m = HashMap::new();
+        assert_eq!(m.try_insert(1, 10), Ok(None));
+        assert_eq!(m.try_insert(1, 20), Ok(Some(10)));
+        for i in 2..100 {
+            assert_eq!(m.try_insert(i, i * 10), Ok(None));
+        }
+        assert_eq!(m.len(), 99);
+        assert_eq!(m[&1], 20);
+        assert_eq!(m[&99], 990);
+    }
+
+    #[test]
+    fn test_try_extend() {
+        let mut m = HashMap::new();
+        m.try_extend((0..100).map(|i| (i, i * 2))).unwrap();
+        m.try_extend((50..150).map(|i| (i, i))).unwrap();
+        assert_eq!(m.len(), 150);
+        assert_eq!(m[&10], 20);
+        assert_eq!(m[&75], 75);
+
+        // A size hint beyond the address space fails before any element is taken.
+        let mut taken = 0;
+        let huge = (0..usize::MAX).map(|i| {
+            taken += 1;
+            (i as u8, 0u8)
+        });
+        let mut bytes: HashMap<u8, u8> = HashMap::new();
+        assert_eq!(bytes.try_extend(huge), Err(CapacityOverflow));
+        assert_eq!(taken, 0);
+        assert!(bytes.is_empty());
+    }
+
     #[test]
     fn test_raw_entry() {
         use super::RawEntryMut::{Occupied, Vacant};
@@ -775,6 +775,66 @@ impl<T, S> HashSet<T, S>
         self.map.insert(value, ()).is_none()
     }
 
+    /// Adds a value to the set, returning an error instead of aborting if the
+    /// set needs to grow and the allocator reports a failure.
+    ///
+    /// On success this returns what [`insert`] would. On error, `value` is
+    /// dropped and the set is left unchanged.
+    ///
+    /// [`insert`]: #method.insert
+    ///
+    /// # Errors
+    ///
+    /// If the capacity overflows, or the allocator reports a failure, then an error
+    /// is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(try_reserve)]
+    /// use std::collections::HashSet;
+    ///
+    /// let mut set = HashSet::new();
+    /// assert_eq!(set.try_insert(2), Ok(true));
+    /// assert_eq!(set.try_insert(2), Ok(false));
+    /// assert_eq!(set.len(), 1);
+    /// ```
+    #[inline]
+    #[unstable(feature = "try_reserve", reason = "new API", issue="48043")]
+    pub fn try_insert(&mut self, value: T) -> Result<bool, CollectionAllocErr> {
+        self.map.try_insert(value, ()).map(|old| old.is_none())
+    }
+
+    /// Extends the set with the contents of an iterator, returning an error
+    /// instead of aborting if the set needs to grow and the allocator reports
+    /// a failure.
+    ///
+    /// On error, the values inserted so far stay in the set and the rest of the
+    /// iterator is dropped.
+    ///
+    /// # Errors
+    ///
+    /// If the capacity overflows, or the allocator reports a failure, then an error
+    /// is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(try_reserve)]
+    /// use std::collections::HashSet;
+    ///
+    /// let mut set = HashSet::new();
+    /// set.try_extend(vec![1, 2, 2, 3]).expect("why is the test harness OOMing on 3 i32s?");
+    /// assert_eq!(set.len(), 3);
+    /// ```
+    #[inline]
+    #[unstable(feature = "try_reserve", reason = "new API", issue="48043")]
+    pub fn try_extend<I>(&mut self, iter: I) -> Result<(), CollectionAllocErr>
+        where I: IntoIterator<Item = T>
+    {
+        self.map.try_extend(iter.into_iter().map(|k| (k, ())))
+    }
+
     /// Adds a value to the set, replacing the existing value, if any, that is equal to the given
     /// one. Returns the replaced value.
     ///
@@ -1941,6 +2001,16 @@ mod test_set {
         assert!(a.contains(&6));
     }
 
+    #[test]
+    fn test_try_insert_and_extend() {
+        let mut set = HashSet::new();
+        assert_eq!(set.try_insert(1), Ok(true));
+        assert_eq!(set.try_insert(1), Ok(false));
+        set.try_extend(0..100).unwrap();
+        assert_eq!(set.len(), 100);
+        assert!((0..100).all(|i| set.contains(&i)));
+    }
+
     #[test]
     fn test_retain() {
         let xs = [1, 2, 3, 4, 5, 6];
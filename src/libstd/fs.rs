@@ -11,7 +11,7 @@
 
 use crate::fmt;
 use crate::ffi::OsString;
-use crate::io::{self, SeekFrom, Seek, Read, Initializer, Write, IoSlice, IoSliceMut};
+use crate::io::{self, SeekFrom, Seek, Read, Initializer, Write, IoSlice, IoSliceMut, ReadBuf};
 use crate::path::{Path, PathBuf};
 use crate::sys::fs as fs_imp;
 use crate::sys_common::{AsInnerMut, FromInner, AsInner, IntoInner};
@@ -588,6 +588,191 @@ impl File {
     pub fn set_permissions(&self, perm: Permissions) -> io::Result<()> {
         self.inner.set_permissions(perm.0)
     }
+
+    /// Acquires a shared lock on the file, blocking until it can be acquired.
+    ///
+    /// Any number of handles may hold a shared lock on the same file at once,
+    /// but none of them may hold one while another holds an exclusive lock.
+    ///
+    /// The lock is released by [`unlock`] or when the file is closed.
+    ///
+    /// On Unix file locks are advisory: they only coordinate between processes
+    /// which also lock the file, and do not prevent anyone else from reading
+    /// from or writing to it. The lock belongs to the open file description,
+    /// so it is shared with handles created by [`try_clone`], and calling this
+    /// while already holding an exclusive lock converts it into a shared one.
+    /// Conversion is not atomic, so the existing lock may be released even if
+    /// the new one cannot be acquired.
+    ///
+    /// On Windows file locks are mandatory: while a handle holds an exclusive
+    /// lock, reads and writes through any other handle fail with an error,
+    /// and while any handle holds a shared lock, writes through every handle,
+    /// including that one, fail. Locks are not converted but stack, so
+    /// locking the same handle twice takes two locks, and each call to
+    /// [`unlock`] only releases one of them.
+    ///
+    /// # Platform-specific behavior
+    ///
+    /// This function currently corresponds to the `flock` function on Unix
+    /// with the `LOCK_SH` flag, and the `LockFileEx` function on Windows. Note
+    /// that, this [may change in the future][changes].
+    ///
+    /// [changes]: ../io/index.html#platform-specific-behavior
+    /// [`unlock`]: #method.unlock
+    /// [`try_clone`]: #method.try_clone
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(file_lock)]
+    /// use std::fs::File;
+    ///
+    /// fn main() -> std::io::Result<()> {
+    ///     let f = File::open("foo.txt")?;
+    ///     f.lock_shared()?;
+    ///     Ok(())
+    /// }
+    /// ```
+    #[unstable(feature = "file_lock", reason = "recently added", issue = "0")]
+    pub fn lock_shared(&self) -> io::Result<()> {
+        self.inner.lock_shared()
+    }
+
+    /// Acquires an exclusive lock on the file, blocking until it can be
+    /// acquired.
+    ///
+    /// No other handle may hold a lock of any kind on the file while an
+    /// exclusive lock is held. See [`lock_shared`] for the semantics shared
+    /// by all of the locking methods.
+    ///
+    /// # Platform-specific behavior
+    ///
+    /// This function currently corresponds to the `flock` function on Unix
+    /// with the `LOCK_EX` flag, and the `LockFileEx` function on Windows with
+    /// the `LOCKFILE_EXCLUSIVE_LOCK` flag. Note that, this
+    /// [may change in the future][changes].
+    ///
+    /// [changes]: ../io/index.html#platform-specific-behavior
+    /// [`lock_shared`]: #method.lock_shared
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(file_lock)]
+    /// use std::fs::File;
+    ///
+    /// fn main() -> std::io::Result<()> {
+    ///     let f = File::create("foo.txt")?;
+    ///     f.lock_exclusive()?;
+    ///     Ok(())
+    /// }
+    /// ```
+    #[unstable(feature = "file_lock", reason = "recently added", issue = "0")]
+    pub fn lock_exclusive(&self) -> io::Result<()> {
+        self.inner.lock_exclusive()
+    }
+
+    /// Attempts to acquire a shared lock on the file without blocking.
+    ///
+    /// Returns `Ok(false)` if another handle holds an exclusive lock on the
+    /// file, and `Ok(true)` if the lock was acquired. See [`lock_shared`] for
+    /// the semantics of the lock.
+    ///
+    /// # Platform-specific behavior
+    ///
+    /// This function currently corresponds to the `flock` function on Unix
+    /// with the `LOCK_SH` and `LOCK_NB` flags, and the `LockFileEx` function
+    /// on Windows with the `LOCKFILE_FAIL_IMMEDIATELY` flag. Note that, this
+    /// [may change in the future][changes].
+    ///
+    /// [changes]: ../io/index.html#platform-specific-behavior
+    /// [`lock_shared`]: #method.lock_shared
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(file_lock)]
+    /// use std::fs::File;
+    ///
+    /// fn main() -> std::io::Result<()> {
+    ///     let f = File::open("foo.txt")?;
+    ///     if !f.try_lock_shared()? {
+    ///         println!("foo.txt is being written to");
+    ///     }
+    ///     Ok(())
+    /// }
+    /// ```
+    #[unstable(feature = "file_lock", reason = "recently added", issue = "0")]
+    pub fn try_lock_shared(&self) -> io::Result<bool> {
+        self.inner.try_lock_shared()
+    }
+
+    /// Attempts to acquire an exclusive lock on the file without blocking.
+    ///
+    /// Returns `Ok(false)` if another handle holds a lock of any kind on the
+    /// file, and `Ok(true)` if the lock was acquired. See [`lock_shared`] for
+    /// the semantics of the lock.
+    ///
+    /// # Platform-specific behavior
+    ///
+    /// This function currently corresponds to the `flock` function on Unix
+    /// with the `LOCK_EX` and `LOCK_NB` flags, and the `LockFileEx` function
+    /// on Windows with the `LOCKFILE_EXCLUSIVE_LOCK` and
+    /// `LOCKFILE_FAIL_IMMEDIATELY` flags. Note that, this
+    /// [may change in the future][changes].
+    ///
+    /// [changes]: ../io/index.html#platform-specific-behavior
+    /// [`lock_shared`]: #method.lock_shared
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(file_lock)]
+    /// use std::fs::File;
+    ///
+    /// fn main() -> std::io::Result<()> {
+    ///     let f = File::create("foo.txt")?;
+    ///     if !f.try_lock_exclusive()? {
+    ///         println!("foo.txt is in use");
+    ///     }
+    ///     Ok(())
+    /// }
+    /// ```
+    #[unstable(feature = "file_lock", reason = "recently added", issue = "0")]
+    pub fn try_lock_exclusive(&self) -> io::Result<bool> {
+        self.inner.try_lock_exclusive()
+    }
+
+    /// Releases the lock held on the file, if any.
+    ///
+    /// Locks are also released when the file is closed, so this is only
+    /// needed to release one early.
+    ///
+    /// # Platform-specific behavior
+    ///
+    /// This function currently corresponds to the `flock` function on Unix
+    /// with the `LOCK_UN` flag, and the `UnlockFile` function on Windows. Note
+    /// that, this [may change in the future][changes].
+    ///
+    /// [changes]: ../io/index.html#platform-specific-behavior
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(file_lock)]
+    /// use std::fs::File;
+    ///
+    /// fn main() -> std::io::Result<()> {
+    ///     let f = File::open("foo.txt")?;
+    ///     f.lock_shared()?;
+    ///     f.unlock()?;
+    ///     Ok(())
+    /// }
+    /// ```
+    #[unstable(feature = "file_lock", reason = "recently added", issue = "0")]
+    pub fn unlock(&self) -> io::Result<()> {
+        self.inner.unlock()
+    }
 }
 
 impl AsInner<fs_imp::File> for File {
@@ -621,6 +806,10 @@ impl Read for File {
         self.inner.read_vectored(bufs)
     }
 
+    fn read_buf(&mut self, buf: &mut ReadBuf<'_>) -> io::Result<()> {
+        self.inner.read_buf(buf)
+    }
+
     #[inline]
     unsafe fn initializer(&self) -> Initializer {
         Initializer::nop()
@@ -654,6 +843,10 @@ impl Read for &File {
         self.inner.read_vectored(bufs)
     }
 
+    fn read_buf(&mut self, buf: &mut ReadBuf<'_>) -> io::Result<()> {
+        self.inner.read_buf(buf)
+    }
+
     #[inline]
     unsafe fn initializer(&self) -> Initializer {
         Initializer::nop()
@@ -1616,7 +1809,9 @@ pub fn rename<P: AsRef<Path>, Q: AsRef<Path>>(from: P, to: Q) -> io::Result<()>
 /// On Windows, this function currently corresponds to `CopyFileEx`. Alternate
 /// NTFS streams are copied but only the size of the main stream is returned by
 /// this function. On MacOS, this function corresponds to `fclonefileat` and
-/// `fcopyfile`.
+/// `fcopyfile`. On Linux and Android, the data is copied in the kernel with
+/// `copy_file_range`, falling back to `sendfile` and then to `read` and
+/// `write` where that is not supported.
 /// Note that, this [may change in the future][changes].
 ///
 /// [changes]: ../io/index.html#platform-specific-behavior
@@ -1914,9 +2109,15 @@ pub fn remove_dir<P: AsRef<Path>>(path: P) -> io::Result<()> {
 ///
 /// # Platform-specific behavior
 ///
-/// This function currently corresponds to `opendir`, `lstat`, `rm` and `rmdir` functions on Unix
-/// and the `FindFirstFile`, `GetFileAttributesEx`, `DeleteFile`, and `RemoveDirectory` functions
-/// on Windows.
+/// This function currently corresponds to `lstat`, `openat`, `fdopendir`, `unlinkat` and `rmdir`
+/// functions on Unix and the `FindFirstFile`, `GetFileAttributesEx`, `DeleteFile`, and
+/// `RemoveDirectory` functions on Windows.
+///
+/// On Unix, the contents of the directory are removed relative to open handles of their parent
+/// directories, and directories are opened without following symlinks. Replacing a directory
+/// in the tree with a symlink while it is being removed can therefore not make this function
+/// remove anything outside of the tree. On macOS releases before 10.10, which lack these
+/// functions, the tree is removed by path instead, without that guarantee.
 /// Note that, this [may change in the future][changes].
 ///
 /// [changes]: ../io/index.html#platform-specific-behavior
@@ -2131,12 +2332,162 @@ impl AsInnerMut<fs_imp::DirBuilder> for DirBuilder {
     }
 }
 
+/// A handle to an open directory, which other files and directories can be
+/// opened, created and removed relative to.
+///
+/// Paths given to the methods of `Dir` are resolved relative to the directory
+/// itself, rather than to the current working directory, and absolute paths
+/// are used as is. The handle keeps referring to the same directory even if
+/// it is renamed or replaced on the filesystem, so unlike joining paths onto
+/// the path of the directory, operations can't be redirected to a different
+/// location after the directory has been opened.
+///
+/// # Platform-specific behavior
+///
+/// This corresponds to a file descriptor used with the `openat`, `mkdirat`,
+/// `unlinkat`, `renameat` and `fstatat` functions on Unix. Other platforms
+/// currently join the paths onto the path the directory was opened with, and
+/// don't give the guarantees above. On macOS releases before 10.10, which lack
+/// these functions, every operation fails with `ENOSYS`.
+/// Note that, this [may change in the future][changes].
+///
+/// [changes]: ../io/index.html#platform-specific-behavior
+///
+/// # Examples
+///
+/// ```no_run
+/// #![feature(dir_handle)]
+/// use std::fs::{Dir, OpenOptions};
+/// use std::io::Write;
+///
+/// fn main() -> std::io::Result<()> {
+///     let dir = Dir::open("/some/dir")?;
+///     dir.create_dir_at("sub")?;
+///     let mut file = dir.open_file_at("sub/foo.txt",
+///                                     OpenOptions::new().write(true).create(true))?;
+///     file.write_all(b"Hello, world!")?;
+///     dir.rename_at("sub/foo.txt", &dir, "foo.txt")?;
+///     Ok(())
+/// }
+/// ```
+#[unstable(feature = "dir_handle", reason = "recently added", issue = "0")]
+pub struct Dir {
+    inner: fs_imp::Dir,
+}
+
+impl Dir {
+    /// Opens the directory at `path`.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if `path` does not exist or is not
+    /// a directory.
+    #[unstable(feature = "dir_handle", reason = "recently added", issue = "0")]
+    pub fn open<P: AsRef<Path>>(path: P) -> io::Result<Dir> {
+        fs_imp::Dir::open(path.as_ref()).map(|inner| Dir { inner })
+    }
+
+    /// Opens the directory at `path` relative to this directory.
+    #[unstable(feature = "dir_handle", reason = "recently added", issue = "0")]
+    pub fn open_dir_at<P: AsRef<Path>>(&self, path: P) -> io::Result<Dir> {
+        self.inner.open_dir_at(path.as_ref()).map(|inner| Dir { inner })
+    }
+
+    /// Opens the file at `path` relative to this directory with the options
+    /// in `opts`.
+    ///
+    /// See [`OpenOptions::open`] for the errors this can return.
+    ///
+    /// [`OpenOptions::open`]: struct.OpenOptions.html#method.open
+    #[unstable(feature = "dir_handle", reason = "recently added", issue = "0")]
+    pub fn open_file_at<P: AsRef<Path>>(&self, path: P, opts: &OpenOptions) -> io::Result<File> {
+        self.inner.open_file_at(path.as_ref(), &opts.0).map(|inner| File { inner })
+    }
+
+    /// Creates a new, empty directory at `path` relative to this directory.
+    ///
+    /// This behaves like [`fs::create_dir`] otherwise.
+    ///
+    /// [`fs::create_dir`]: fn.create_dir.html
+    #[unstable(feature = "dir_handle", reason = "recently added", issue = "0")]
+    pub fn create_dir_at<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        self.inner.create_dir_at(path.as_ref())
+    }
+
+    /// Removes the file at `path` relative to this directory.
+    ///
+    /// This behaves like [`fs::remove_file`] otherwise.
+    ///
+    /// [`fs::remove_file`]: fn.remove_file.html
+    #[unstable(feature = "dir_handle", reason = "recently added", issue = "0")]
+    pub fn remove_file_at<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        self.inner.remove_file_at(path.as_ref())
+    }
+
+    /// Removes the empty directory at `path` relative to this directory.
+    ///
+    /// This behaves like [`fs::remove_dir`] otherwise.
+    ///
+    /// [`fs::remove_dir`]: fn.remove_dir.html
+    #[unstable(feature = "dir_handle", reason = "recently added", issue = "0")]
+    pub fn remove_dir_at<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        self.inner.remove_dir_at(path.as_ref())
+    }
+
+    /// Renames the file or directory at `from` relative to this directory to
+    /// `to` relative to `to_dir`, replacing the original file if `to` already
+    /// exists.
+    ///
+    /// This behaves like [`fs::rename`] otherwise.
+    ///
+    /// [`fs::rename`]: fn.rename.html
+    #[unstable(feature = "dir_handle", reason = "recently added", issue = "0")]
+    pub fn rename_at<P: AsRef<Path>, Q: AsRef<Path>>(&self, from: P, to_dir: &Dir, to: Q)
+                                                     -> io::Result<()> {
+        self.inner.rename_at(from.as_ref(), &to_dir.inner, to.as_ref())
+    }
+
+    /// Queries the metadata of the file at `path` relative to this directory,
+    /// without following symlinks.
+    ///
+    /// This behaves like [`fs::symlink_metadata`] otherwise.
+    ///
+    /// [`fs::symlink_metadata`]: fn.symlink_metadata.html
+    #[unstable(feature = "dir_handle", reason = "recently added", issue = "0")]
+    pub fn symlink_metadata_at<P: AsRef<Path>>(&self, path: P) -> io::Result<Metadata> {
+        self.inner.symlink_metadata_at(path.as_ref()).map(Metadata)
+    }
+
+    /// Returns an iterator over the entries within this directory.
+    ///
+    /// The [`path`] of the entries is built from the path this directory was
+    /// opened with, and does not refer to the entry anymore if the directory
+    /// has been moved since.
+    ///
+    /// [`path`]: struct.DirEntry.html#method.path
+    #[unstable(feature = "dir_handle", reason = "recently added", issue = "0")]
+    pub fn read_dir(&self) -> io::Result<ReadDir> {
+        self.inner.read_dir().map(ReadDir)
+    }
+}
+
+#[unstable(feature = "dir_handle", reason = "recently added", issue = "0")]
+impl fmt::Debug for Dir {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.inner.fmt(f)
+    }
+}
+
+impl AsInner<fs_imp::Dir> for Dir {
+    fn as_inner(&self) -> &fs_imp::Dir { &self.inner }
+}
+
 #[cfg(all(test, not(any(target_os = "cloudabi", target_os = "emscripten", target_env = "sgx"))))]
 mod tests {
     use crate::io::prelude::*;
 
     use crate::fs::{self, File, OpenOptions};
-    use crate::io::{ErrorKind, SeekFrom};
+    use crate::io::{self, ErrorKind, SeekFrom};
     use crate::path::Path;
     use crate::str;
     use crate::sys_common::io::test::{TempDir, tmpdir};
@@ -2721,6 +3072,52 @@ mod tests {
         }
     }
 
+    #[test]
+    fn dir_handle() {
+        let tmpdir = tmpdir();
+        let dir = check!(fs::Dir::open(tmpdir.path()));
+
+        check!(dir.create_dir_at("d"));
+        let mut f = check!(dir.open_file_at("d/f", OpenOptions::new().write(true).create(true)));
+        check!(f.write(b"foo"));
+        assert!(tmpdir.join("d/f").is_file());
+
+        let sub = check!(dir.open_dir_at("d"));
+        check!(sub.rename_at("f", &dir, "g"));
+        assert!(!tmpdir.join("d/f").exists());
+        let mut contents = String::new();
+        check!(check!(dir.open_file_at("g", OpenOptions::new().read(true)))
+            .read_to_string(&mut contents));
+        assert_eq!(contents, "foo");
+        assert!(check!(dir.symlink_metadata_at("g")).is_file());
+        assert!(check!(dir.symlink_metadata_at("d")).is_dir());
+
+        let names = check!(dir.read_dir())
+            .map(|e| check!(e).file_name())
+            .collect::<Vec<_>>();
+        assert_eq!(names.len(), 2);
+
+        assert!(dir.remove_file_at("d").is_err());
+        check!(dir.remove_dir_at("d"));
+        check!(dir.remove_file_at("g"));
+        assert_eq!(check!(dir.read_dir()).count(), 0);
+        assert!(fs::Dir::open(tmpdir.join("d")).is_err());
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn dir_handle_follows_rename() {
+        let tmpdir = tmpdir();
+        check!(fs::create_dir(tmpdir.join("a")));
+        let dir = check!(fs::Dir::open(tmpdir.join("a")));
+        check!(fs::rename(tmpdir.join("a"), tmpdir.join("b")));
+        check!(fs::create_dir(tmpdir.join("a")));
+
+        check!(dir.open_file_at("f", OpenOptions::new().write(true).create(true)));
+        assert!(tmpdir.join("b/f").is_file());
+        assert!(!tmpdir.join("a/f").exists());
+    }
+
     #[test]
     fn unicode_path_is_dir() {
         assert!(Path::new(".").is_dir());
@@ -2796,6 +3193,184 @@ mod tests {
                    check!(out.metadata()).permissions());
     }
 
+    #[test]
+    fn io_copy_between_files_uses_offsets() {
+        let tmpdir = tmpdir();
+        let input = tmpdir.join("in.txt");
+        let out = tmpdir.join("out.txt");
+        let data = (0..100_000u32).map(|i| i as u8).collect::<Vec<_>>();
+        check!(fs::write(&input, &data));
+
+        let mut reader = check!(File::open(&input));
+        check!(reader.seek(SeekFrom::Start(10)));
+        let mut writer = check!(File::create(&out));
+        check!(writer.write_all(b"head"));
+        assert_eq!(check!(io::copy(&mut reader, &mut writer)), data.len() as u64 - 10);
+        assert_eq!(check!(reader.seek(SeekFrom::Current(0))), data.len() as u64);
+
+        let v = check!(fs::read(&out));
+        assert_eq!(&v[..4], b"head");
+        assert_eq!(&v[4..], &data[10..]);
+    }
+
+    #[test]
+    fn io_copy_to_append_file_uses_offsets() {
+        // `copy_file_range` and `sendfile` reject an output opened for
+        // appending, so this goes through every fallback.
+        let tmpdir = tmpdir();
+        let input = tmpdir.join("in.txt");
+        let out = tmpdir.join("out.txt");
+        let data = (0..100_000u32).map(|i| i as u8).collect::<Vec<_>>();
+        check!(fs::write(&input, &data));
+        check!(fs::write(&out, b"head"));
+
+        let mut reader = check!(File::open(&input));
+        check!(reader.seek(SeekFrom::Start(10)));
+        let mut writer = check!(OpenOptions::new().append(true).open(&out));
+        assert_eq!(check!(io::copy(&mut reader, &mut writer)), data.len() as u64 - 10);
+        assert_eq!(check!(reader.seek(SeekFrom::Current(0))), data.len() as u64);
+
+        let v = check!(fs::read(&out));
+        assert_eq!(&v[..4], b"head");
+        assert_eq!(&v[4..], &data[10..]);
+    }
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn io_copy_from_procfs() {
+        // Files in procfs report a size of zero, so copying them must not
+        // stop at the first empty `copy_file_range`.
+        let expected = check!(fs::read("/proc/self/mountinfo"));
+        assert!(!expected.is_empty());
+
+        let tmpdir = tmpdir();
+        let out = tmpdir.join("out.txt");
+        let mut reader = check!(File::open("/proc/self/mountinfo"));
+        let mut writer = check!(File::create(&out));
+        check!(writer.write_all(b"head"));
+        let copied = check!(io::copy(&mut reader, &mut writer));
+        assert_eq!(check!(writer.seek(SeekFrom::Current(0))), copied + 4);
+
+        let v = check!(fs::read(&out));
+        assert_eq!(&v[..4], b"head");
+        assert_eq!(&v[4..], &expected[..]);
+    }
+
+    #[test]
+    fn io_copy_file_to_tcp_stream() {
+        use crate::net::{TcpListener, TcpStream};
+
+        let tmpdir = tmpdir();
+        let input = tmpdir.join("in.txt");
+        let data = (0..100_000u32).map(|i| i as u8).collect::<Vec<_>>();
+        check!(fs::write(&input, &data));
+
+        let listener = check!(TcpListener::bind("127.0.0.1:0"));
+        let addr = check!(listener.local_addr());
+        let receiver = thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut v = Vec::new();
+            stream.read_to_end(&mut v).unwrap();
+            v
+        });
+
+        let mut reader = check!(File::open(&input));
+        check!(reader.seek(SeekFrom::Start(10)));
+        let mut stream = check!(TcpStream::connect(addr));
+        assert_eq!(check!(io::copy(&mut reader, &mut stream)), data.len() as u64 - 10);
+        assert_eq!(check!(reader.seek(SeekFrom::Current(0))), data.len() as u64);
+        drop(stream);
+        assert_eq!(&receiver.join().unwrap()[..], &data[10..]);
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn io_copy_file_to_unix_stream() {
+        use crate::os::unix::net::UnixStream;
+
+        let tmpdir = tmpdir();
+        let input = tmpdir.join("in.txt");
+        let data = (0..100_000u32).map(|i| i as u8).collect::<Vec<_>>();
+        check!(fs::write(&input, &data));
+
+        let (mut stream, mut other) = check!(UnixStream::pair());
+        let receiver = thread::spawn(move || {
+            let mut v = Vec::new();
+            other.read_to_end(&mut v).unwrap();
+            v
+        });
+
+        let mut reader = check!(File::open(&input));
+        check!(reader.seek(SeekFrom::Start(10)));
+        assert_eq!(check!(io::copy(&mut reader, &mut stream)), data.len() as u64 - 10);
+        assert_eq!(check!(reader.seek(SeekFrom::Current(0))), data.len() as u64);
+        drop(stream);
+        assert_eq!(&receiver.join().unwrap()[..], &data[10..]);
+    }
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn file_allocate_and_punch_hole() {
+        use crate::os::linux::fs::FileAllocateExt;
+
+        let tmpdir = tmpdir();
+        let path = tmpdir.join("sparse");
+        let mut f = check!(OpenOptions::new().read(true).write(true).create(true).open(&path));
+        // Not every filesystem supports `fallocate`, or punching holes with
+        // it, but any other error is a bug.
+        let unsupported = |e: &io::Error| e.raw_os_error() == Some(libc::EOPNOTSUPP);
+        match f.allocate(0, 8192) {
+            Ok(()) => {}
+            Err(ref e) if unsupported(e) => return,
+            Err(e) => panic!("f.allocate(0, 8192) failed with: {}", e),
+        }
+        assert_eq!(check!(f.metadata()).len(), 8192);
+
+        check!(f.write_all(&[1; 8192]));
+        match f.punch_hole(0, 4096) {
+            Ok(()) => {}
+            Err(ref e) if unsupported(e) => return,
+            Err(e) => panic!("f.punch_hole(0, 4096) failed with: {}", e),
+        }
+        assert_eq!(check!(f.metadata()).len(), 8192);
+        let mut v = Vec::new();
+        check!(f.seek(SeekFrom::Start(0)));
+        check!(f.read_to_end(&mut v));
+        assert!(v[..4096].iter().all(|&b| b == 0));
+        assert!(v[4096..].iter().all(|&b| b == 1));
+    }
+
+    #[test]
+    #[cfg(any(all(unix, not(target_os = "solaris")), windows))]
+    fn file_lock() {
+        let tmpdir = tmpdir();
+        let path = tmpdir.join("lock");
+        let f1 = check!(File::create(&path));
+        let f2 = check!(File::open(&path));
+
+        check!(f1.lock_shared());
+        assert!(check!(f2.try_lock_shared()));
+        assert!(!check!(f1.try_lock_exclusive()));
+        check!(f2.unlock());
+        check!(f1.unlock());
+
+        check!(f1.lock_exclusive());
+        assert!(!check!(f2.try_lock_shared()));
+        assert!(!check!(f2.try_lock_exclusive()));
+        check!(f1.unlock());
+        assert!(check!(f2.try_lock_exclusive()));
+        check!(f2.unlock());
+
+        // Closing the file releases its lock. Windows releases the locks of a
+        // closed handle asynchronously, so this is only checked on Unix.
+        #[cfg(unix)]
+        {
+            check!(f1.lock_exclusive());
+            drop(f1);
+            assert!(check!(f2.try_lock_shared()));
+        }
+    }
+
     #[test]
     fn copy_file_dst_dir() {
         let tmpdir = tmpdir();
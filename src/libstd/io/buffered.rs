@@ -6,7 +6,8 @@ use crate::cmp;
 use crate::error;
 use crate::fmt;
 use crate::io::{self, Initializer, DEFAULT_BUF_SIZE, Error, ErrorKind, SeekFrom, IoSlice,
-        IoSliceMut};
+        IoSliceMut, ReadBuf};
+use crate::mem::MaybeUninit;
 use crate::memchr;
 
 /// The `BufReader` struct adds buffering to any reader.
@@ -50,9 +51,12 @@ use crate::memchr;
 #[stable(feature = "rust1", since = "1.0.0")]
 pub struct BufReader<R> {
     inner: R,
-    buf: Box<[u8]>,
+    buf: Box<[MaybeUninit<u8>]>,
     pos: usize,
     cap: usize,
+    // The number of bytes at the start of `buf` that were initialized by an
+    // earlier read, which is always at least `cap`.
+    init: usize,
 }
 
 impl<R: Read> BufReader<R> {
@@ -94,16 +98,18 @@ impl<R: Read> BufReader<R> {
     /// ```
     #[stable(feature = "rust1", since = "1.0.0")]
     pub fn with_capacity(capacity: usize, inner: R) -> BufReader<R> {
+        let mut buffer = Vec::with_capacity(capacity);
+        // The buffer starts out uninitialized, `fill_buf` hands it to the
+        // reader through a `ReadBuf` which tracks what gets initialized.
         unsafe {
-            let mut buffer = Vec::with_capacity(capacity);
             buffer.set_len(capacity);
-            inner.initializer().initialize(&mut buffer);
-            BufReader {
-                inner,
-                buf: buffer.into_boxed_slice(),
-                pos: 0,
-                cap: 0,
-            }
+        }
+        BufReader {
+            inner,
+            buf: buffer.into_boxed_slice(),
+            pos: 0,
+            cap: 0,
+            init: 0,
         }
     }
 }
@@ -174,7 +180,9 @@ impl<R> BufReader<R> {
     /// ```
     #[stable(feature = "bufreader_buffer", since = "1.37.0")]
     pub fn buffer(&self) -> &[u8] {
-        &self.buf[self.pos..self.cap]
+        let buf = &self.buf[self.pos..self.cap];
+        // `cap <= init`, so these bytes have been initialized.
+        unsafe { &*(buf as *const [MaybeUninit<u8>] as *const [u8]) }
     }
 
     /// Unwraps this `BufReader`, returning the underlying reader.
@@ -263,6 +271,24 @@ impl<R: Read> Read for BufReader<R> {
         Ok(nread)
     }
 
+    fn read_buf(&mut self, buf: &mut ReadBuf<'_>) -> io::Result<()> {
+        // If we don't have any buffered data and we're doing a massive read
+        // (larger than our internal buffer), bypass our internal buffer
+        // entirely.
+        if self.pos == self.cap && buf.remaining() >= self.buf.len() {
+            self.discard_buffer();
+            return self.inner.read_buf(buf);
+        }
+        let amt = {
+            let rem = self.fill_buf()?;
+            let amt = cmp::min(rem.len(), buf.remaining());
+            buf.append(&rem[..amt]);
+            amt
+        };
+        self.consume(amt);
+        Ok(())
+    }
+
     // we can't skip unconditionally because of the large buffer case in read.
     unsafe fn initializer(&self) -> Initializer {
         self.inner.initializer()
@@ -278,10 +304,22 @@ impl<R: Read> BufRead for BufReader<R> {
         // to tell the compiler that the pos..cap slice is always valid.
         if self.pos >= self.cap {
             debug_assert!(self.pos == self.cap);
-            self.cap = self.inner.read(&mut self.buf)?;
+            let (buf_ptr, buf_len) = (self.buf.as_ptr(), self.buf.len());
+            let mut read_buf = ReadBuf::uninit(&mut self.buf);
+            unsafe {
+                read_buf.assume_init(self.init);
+            }
+            let ret = self.inner.read_buf(&mut read_buf);
+            // The lengths of a buffer the reader swapped in say nothing about
+            // what it initialized in `self.buf`.
+            assert!(read_buf.is_backed_by(buf_ptr, buf_len),
+                    "`read_buf` replaced the buffer it was given");
+            ret?;
+            self.cap = read_buf.filled_len();
+            self.init = read_buf.initialized_len();
             self.pos = 0;
         }
-        Ok(&self.buf[self.pos..self.cap])
+        Ok(self.buffer())
     }
 
     fn consume(&mut self, amt: usize) {
@@ -992,7 +1030,8 @@ impl<W: Write> fmt::Debug for LineWriter<W> where W: fmt::Debug {
 #[cfg(test)]
 mod tests {
     use crate::io::prelude::*;
-    use crate::io::{self, BufReader, BufWriter, LineWriter, SeekFrom};
+    use crate::io::{self, BufReader, BufWriter, LineWriter, ReadBuf, SeekFrom};
+    use crate::mem::MaybeUninit;
     use crate::sync::atomic::{AtomicUsize, Ordering};
     use crate::thread;
 
@@ -1048,6 +1087,73 @@ mod tests {
         assert_eq!(reader.read(&mut buf).unwrap(), 0);
     }
 
+    #[test]
+    fn test_buffered_reader_read_buf() {
+        let inner: &[u8] = &[5, 6, 7, 0, 1, 2, 3, 4];
+        let mut reader = BufReader::with_capacity(3, inner);
+
+        let mut buf = [MaybeUninit::<u8>::uninit(); 3];
+        let mut buf = ReadBuf::uninit(&mut buf);
+
+        reader.read_buf(&mut buf).unwrap();
+        assert_eq!(buf.filled(), [5, 6, 7]);
+        assert_eq!(reader.buffer(), []);
+
+        let mut buf = [MaybeUninit::<u8>::uninit(); 2];
+        let mut buf = ReadBuf::uninit(&mut buf);
+
+        reader.read_buf(&mut buf).unwrap();
+        assert_eq!(buf.filled(), [0, 1]);
+        assert_eq!(reader.buffer(), [2]);
+
+        reader.read_buf(&mut buf).unwrap();
+        assert_eq!(buf.filled(), [0, 1]);
+        assert_eq!(reader.buffer(), [2]);
+
+        buf.clear();
+
+        reader.read_buf(&mut buf).unwrap();
+        assert_eq!(buf.filled(), [2]);
+        assert_eq!(reader.buffer(), []);
+
+        reader.read_buf(&mut buf).unwrap();
+        assert_eq!(buf.filled(), [2, 3]);
+        assert_eq!(reader.buffer(), [4]);
+
+        buf.clear();
+
+        reader.read_buf(&mut buf).unwrap();
+        assert_eq!(buf.filled(), [4]);
+        assert_eq!(reader.buffer(), []);
+
+        reader.read_buf(&mut buf).unwrap();
+        assert_eq!(buf.filled(), [4]);
+    }
+
+    #[test]
+    #[should_panic(expected = "replaced the buffer")]
+    fn test_buffered_reader_replaced_read_buf() {
+        // Swaps the buffer it is handed for a filled one over leaked memory,
+        // which must not make `fill_buf` expose uninitialized bytes.
+        struct Swap;
+
+        impl Read for Swap {
+            fn read(&mut self, _: &mut [u8]) -> io::Result<usize> {
+                unreachable!()
+            }
+
+            fn read_buf(&mut self, buf: &mut ReadBuf<'_>) -> io::Result<()> {
+                let mut other = ReadBuf::new(Box::leak(vec![0; 64].into_boxed_slice()));
+                other.add_filled(64);
+                *buf = other;
+                Ok(())
+            }
+        }
+
+        let mut reader = BufReader::with_capacity(16, Swap);
+        let _ = reader.fill_buf();
+    }
+
     #[test]
     fn test_buffered_reader_seek() {
         let inner: &[u8] = &[5, 6, 7, 0, 1, 2, 3, 4];
@@ -264,6 +264,7 @@ use crate::fmt;
 use crate::slice;
 use crate::str;
 use crate::memchr;
+use crate::mem::MaybeUninit;
 use crate::ops::{Deref, DerefMut};
 use crate::ptr;
 use crate::sys;
@@ -276,8 +277,11 @@ pub use self::buffered::IntoInnerError;
 pub use self::cursor::Cursor;
 #[stable(feature = "rust1", since = "1.0.0")]
 pub use self::error::{Result, Error, ErrorKind};
+#[unstable(feature = "read_buf", reason = "recently added", issue = "0")]
+pub use self::readbuf::ReadBuf;
 #[stable(feature = "rust1", since = "1.0.0")]
 pub use self::util::{copy, sink, Sink, empty, Empty, repeat, Repeat};
+pub(crate) use self::util::generic_copy;
 #[stable(feature = "rust1", since = "1.0.0")]
 pub use self::stdio::{stdin, stdout, stderr, Stdin, Stdout, Stderr};
 #[stable(feature = "rust1", since = "1.0.0")]
@@ -294,6 +298,7 @@ mod cursor;
 mod error;
 mod impls;
 mod lazy;
+mod readbuf;
 mod util;
 mod stdio;
 
@@ -350,8 +355,9 @@ fn append_to_string<F>(buf: &mut String, f: F) -> Result<usize>
 // time is 4,500 times (!) slower than a default reservation size of 32 if the
 // reader has a very small amount of data to return.
 //
-// Because we're extending the buffer with uninitialized data for trusted
-// readers, we need to make sure to truncate that if any of this panics.
+// The spare capacity of the vector is handed to `read_buf` uninitialized. The
+// length of the vector only ever covers bytes the reader has filled, so a panic
+// in the reader can't expose uninitialized memory.
 fn read_to_end<R: Read + ?Sized>(r: &mut R, buf: &mut Vec<u8>) -> Result<usize> {
     read_to_end_with_reservation(r, buf, 32)
 }
@@ -361,33 +367,49 @@ fn read_to_end_with_reservation<R: Read + ?Sized>(r: &mut R,
                                                   reservation_size: usize) -> Result<usize>
 {
     let start_len = buf.len();
-    let mut g = Guard { len: buf.len(), buf: buf };
-    let ret;
+    // The number of bytes past `buf.len()` that an earlier `read_buf` call
+    // already initialized, so that they are not zeroed again.
+    let mut initialized = 0;
     loop {
-        if g.len == g.buf.len() {
+        if buf.len() == buf.capacity() {
+            buf.reserve(reservation_size);
+        }
+
+        let (filled, ret) = {
+            // The spare capacity of the vector, which `read_buf` may leave
+            // uninitialized.
+            let spare = unsafe {
+                slice::from_raw_parts_mut(
+                    buf.as_mut_ptr().add(buf.len()) as *mut MaybeUninit<u8>,
+                    buf.capacity() - buf.len(),
+                )
+            };
+            let (spare_ptr, spare_len) = (spare.as_ptr(), spare.len());
+            let mut read_buf = ReadBuf::uninit(spare);
             unsafe {
-                g.buf.reserve(reservation_size);
-                let capacity = g.buf.capacity();
-                g.buf.set_len(capacity);
-                r.initializer().initialize(&mut g.buf[g.len..]);
+                read_buf.assume_init(initialized);
             }
+
+            let ret = r.read_buf(&mut read_buf);
+            assert!(read_buf.is_backed_by(spare_ptr, spare_len),
+                    "`read_buf` replaced the buffer it was given");
+            initialized = read_buf.initialized_len() - read_buf.filled_len();
+            (read_buf.filled_len(), ret)
+        };
+
+        // `ReadBuf` guarantees that the filled bytes are initialized.
+        unsafe {
+            let new_len = buf.len() + filled;
+            buf.set_len(new_len);
         }
 
-        match r.read(&mut g.buf[g.len..]) {
-            Ok(0) => {
-                ret = Ok(g.len - start_len);
-                break;
-            }
-            Ok(n) => g.len += n,
+        match ret {
+            Ok(()) if filled == 0 => return Ok(buf.len() - start_len),
+            Ok(()) => {}
             Err(ref e) if e.kind() == ErrorKind::Interrupted => {}
-            Err(e) => {
-                ret = Err(e);
-                break;
-            }
+            Err(e) => return Err(e),
         }
     }
-
-    ret
 }
 
 pub(crate) fn default_read_vectored<F>(read: F, bufs: &mut [IoSliceMut<'_>]) -> Result<usize>
@@ -401,6 +423,15 @@ where
     read(buf)
 }
 
+pub(crate) fn default_read_buf<F>(read: F, buf: &mut ReadBuf<'_>) -> Result<()>
+where
+    F: FnOnce(&mut [u8]) -> Result<usize>
+{
+    let n = read(buf.initialize_unfilled())?;
+    buf.add_filled(n);
+    Ok(())
+}
+
 pub(crate) fn default_write_vectored<F>(write: F, bufs: &[IoSlice<'_>]) -> Result<usize>
 where
     F: FnOnce(&[u8]) -> Result<usize>
@@ -568,6 +599,27 @@ pub trait Read {
         default_read_vectored(|b| self.read(b), bufs)
     }
 
+    /// Pull some bytes from this source into the specified buffer.
+    ///
+    /// This is equivalent to the [`read`] method, except that it is passed a
+    /// [`ReadBuf`] rather than `&mut [u8]` to allow use with uninitialized
+    /// buffers. The new data will be appended to any existing contents of
+    /// `buf`.
+    ///
+    /// Reaching the end of the source is signalled by returning `Ok(())`
+    /// without having filled any bytes, as long as `buf` had room for at least
+    /// one.
+    ///
+    /// The default implementation delegates to [`read`] after initializing
+    /// the unfilled part of `buf`.
+    ///
+    /// [`read`]: #tymethod.read
+    /// [`ReadBuf`]: struct.ReadBuf.html
+    #[unstable(feature = "read_buf", reason = "recently added", issue = "0")]
+    fn read_buf(&mut self, buf: &mut ReadBuf<'_>) -> Result<()> {
+        default_read_buf(|b| self.read(b), buf)
+    }
+
     /// Determines if this `Read`er can work with buffers of uninitialized
     /// memory.
     ///
@@ -2267,6 +2319,7 @@ impl<B: BufRead> Iterator for Lines<B> {
 
 #[cfg(test)]
 mod tests {
+    use crate::cmp;
     use crate::io::prelude::*;
     use crate::io;
     use super::{Cursor, SeekFrom, repeat};
@@ -2359,6 +2412,60 @@ mod tests {
         assert_eq!(v, data);
     }
 
+    #[test]
+    fn read_to_end_read_buf() {
+        // A reader which only ever fills part of the buffer it is handed and
+        // never initializes anything beyond that.
+        struct Chunks<'a>(&'a [u8], bool);
+
+        impl Read for Chunks<'_> {
+            fn read(&mut self, _: &mut [u8]) -> io::Result<usize> {
+                unreachable!()
+            }
+
+            fn read_buf(&mut self, buf: &mut io::ReadBuf<'_>) -> io::Result<()> {
+                self.1 = !self.1;
+                if self.1 {
+                    return Err(io::Error::new(io::ErrorKind::Interrupted, ""));
+                }
+                let amt = cmp::min(cmp::min(self.0.len(), buf.remaining()), 3);
+                buf.append(&self.0[..amt]);
+                self.0 = &self.0[amt..];
+                Ok(())
+            }
+        }
+
+        let data = (0..100).collect::<Vec<u8>>();
+        let mut v = vec![255];
+        assert_eq!(Chunks(&data, false).read_to_end(&mut v).unwrap(), 100);
+        assert_eq!(v[0], 255);
+        assert_eq!(&v[1..], &data[..]);
+    }
+
+    #[test]
+    #[should_panic(expected = "replaced the buffer")]
+    fn read_to_end_replaced_read_buf() {
+        // A reader which swaps the buffer it is handed for a filled one over
+        // leaked memory, claiming to have read more than fits in the vector.
+        struct Swap;
+
+        impl Read for Swap {
+            fn read(&mut self, _: &mut [u8]) -> io::Result<usize> {
+                unreachable!()
+            }
+
+            fn read_buf(&mut self, buf: &mut io::ReadBuf<'_>) -> io::Result<()> {
+                let mut other = io::ReadBuf::new(Box::leak(vec![0; 1 << 16].into_boxed_slice()));
+                other.add_filled(1 << 16);
+                *buf = other;
+                Ok(())
+            }
+        }
+
+        let mut v = Vec::new();
+        let _ = Swap.read_to_end(&mut v);
+    }
+
     #[test]
     fn read_to_string() {
         let mut c = Cursor::new(&b""[..]);
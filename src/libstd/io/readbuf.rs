@@ -0,0 +1,473 @@
+//! A borrowed byte buffer which is incrementally filled and initialized.
+
+use crate::cmp;
+use crate::fmt::{self, Debug, Formatter};
+use crate::mem::MaybeUninit;
+use crate::ptr;
+
+/// A wrapper around a byte buffer that is incrementally filled and initialized.
+///
+/// This type is a sort of "double cursor". It tracks three regions in the
+/// buffer: a region at the beginning of the buffer that has been logically
+/// filled with data, a region that has been initialized at some point but not
+/// yet logically filled, and a region at the end that is fully uninitialized.
+/// The filled region is guaranteed to be a subset of the initialized region.
+///
+/// In summary, the contents of the buffer can be visualized as:
+///
+/// ```not_rust
+/// [             capacity              ]
+/// [ filled |         unfilled         ]
+/// [    initialized    | uninitialized ]
+/// ```
+///
+/// `ReadBuf` is what [`Read::read_buf`] reads into. Unlike a plain `&mut [u8]`,
+/// it lets a reader be handed memory that has never been written to, without
+/// the caller having to zero it first, and it remembers how much of that
+/// memory the reader did initialize so that a buffer reused across several
+/// reads never needs to be zeroed twice.
+///
+/// [`Read::read_buf`]: trait.Read.html#method.read_buf
+///
+/// # Examples
+///
+/// ```
+/// #![feature(read_buf)]
+/// use std::io::{Read, ReadBuf};
+/// use std::mem::MaybeUninit;
+///
+/// fn main() -> std::io::Result<()> {
+///     let mut reader: &[u8] = b"hello world";
+///     let mut storage = [MaybeUninit::<u8>::uninit(); 5];
+///     let mut buf = ReadBuf::uninit(&mut storage);
+///
+///     reader.read_buf(&mut buf)?;
+///     assert_eq!(buf.filled(), b"hello");
+///     assert_eq!(buf.remaining(), 0);
+///     Ok(())
+/// }
+/// ```
+#[unstable(feature = "read_buf", reason = "recently added", issue = "0")]
+pub struct ReadBuf<'a> {
+    buf: &'a mut [MaybeUninit<u8>],
+    filled: usize,
+    initialized: usize,
+}
+
+#[unstable(feature = "read_buf", reason = "recently added", issue = "0")]
+impl Debug for ReadBuf<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ReadBuf")
+            .field("init", &self.initialized())
+            .field("filled", &self.filled)
+            .field("capacity", &self.capacity())
+            .finish()
+    }
+}
+
+impl<'a> ReadBuf<'a> {
+    /// Creates a new `ReadBuf` from a fully initialized buffer.
+    #[unstable(feature = "read_buf", reason = "recently added", issue = "0")]
+    #[inline]
+    pub fn new(buf: &'a mut [u8]) -> ReadBuf<'a> {
+        let len = buf.len();
+
+        ReadBuf {
+            // `MaybeUninit<u8>` has the same layout as `u8`, and safe code
+            // can't de-initialize bytes through a `ReadBuf`.
+            buf: unsafe { &mut *(buf as *mut [u8] as *mut [MaybeUninit<u8>]) },
+            filled: 0,
+            initialized: len,
+        }
+    }
+
+    /// Creates a new `ReadBuf` from a fully uninitialized buffer.
+    ///
+    /// Use [`assume_init`] if part of the buffer is known to be already
+    /// initialized.
+    ///
+    /// [`assume_init`]: #method.assume_init
+    #[unstable(feature = "read_buf", reason = "recently added", issue = "0")]
+    #[inline]
+    pub fn uninit(buf: &'a mut [MaybeUninit<u8>]) -> ReadBuf<'a> {
+        ReadBuf {
+            buf,
+            filled: 0,
+            initialized: 0,
+        }
+    }
+
+    /// Returns the total capacity of the buffer.
+    #[unstable(feature = "read_buf", reason = "recently added", issue = "0")]
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        self.buf.len()
+    }
+
+    /// Returns a shared reference to the filled portion of the buffer.
+    #[unstable(feature = "read_buf", reason = "recently added", issue = "0")]
+    #[inline]
+    pub fn filled(&self) -> &[u8] {
+        // The filled region is always initialized.
+        unsafe { slice_assume_init(&self.buf[..self.filled]) }
+    }
+
+    /// Returns a mutable reference to the filled portion of the buffer.
+    #[unstable(feature = "read_buf", reason = "recently added", issue = "0")]
+    #[inline]
+    pub fn filled_mut(&mut self) -> &mut [u8] {
+        // The filled region is always initialized.
+        unsafe { slice_assume_init_mut(&mut self.buf[..self.filled]) }
+    }
+
+    /// Returns a shared reference to the initialized portion of the buffer.
+    ///
+    /// This includes the filled portion.
+    #[unstable(feature = "read_buf", reason = "recently added", issue = "0")]
+    #[inline]
+    pub fn initialized(&self) -> &[u8] {
+        // `initialized` only ever covers initialized bytes.
+        unsafe { slice_assume_init(&self.buf[..self.initialized]) }
+    }
+
+    /// Returns a mutable reference to the initialized portion of the buffer.
+    ///
+    /// This includes the filled portion.
+    #[unstable(feature = "read_buf", reason = "recently added", issue = "0")]
+    #[inline]
+    pub fn initialized_mut(&mut self) -> &mut [u8] {
+        // `initialized` only ever covers initialized bytes.
+        unsafe { slice_assume_init_mut(&mut self.buf[..self.initialized]) }
+    }
+
+    /// Returns a mutable reference to the unfilled part of the buffer without
+    /// ensuring that it has been fully initialized.
+    ///
+    /// # Safety
+    ///
+    /// The caller must not de-initialize portions of the buffer that have
+    /// already been initialized.
+    #[unstable(feature = "read_buf", reason = "recently added", issue = "0")]
+    #[inline]
+    pub unsafe fn unfilled_mut(&mut self) -> &mut [MaybeUninit<u8>] {
+        &mut self.buf[self.filled..]
+    }
+
+    /// Returns a mutable reference to the uninitialized part of the buffer.
+    ///
+    /// It is safe to uninitialize any of these bytes.
+    #[unstable(feature = "read_buf", reason = "recently added", issue = "0")]
+    #[inline]
+    pub fn uninitialized_mut(&mut self) -> &mut [MaybeUninit<u8>] {
+        &mut self.buf[self.initialized..]
+    }
+
+    /// Returns a mutable reference to the unfilled part of the buffer,
+    /// ensuring it is fully initialized.
+    ///
+    /// Since `ReadBuf` tracks the region of the buffer that has been
+    /// initialized, this is effectively "free" after the first use.
+    #[unstable(feature = "read_buf", reason = "recently added", issue = "0")]
+    #[inline]
+    pub fn initialize_unfilled(&mut self) -> &mut [u8] {
+        // Should be optimized out.
+        let n = self.remaining();
+        self.initialize_unfilled_to(n)
+    }
+
+    /// Returns a mutable reference to the first `n` bytes of the unfilled
+    /// part of the buffer, ensuring it is fully initialized.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `self.remaining()` is less than `n`.
+    #[unstable(feature = "read_buf", reason = "recently added", issue = "0")]
+    #[inline]
+    pub fn initialize_unfilled_to(&mut self, n: usize) -> &mut [u8] {
+        assert!(self.remaining() >= n);
+
+        let extra_init = self.initialized - self.filled;
+        // If we don't have enough initialized, do zeroing.
+        if n > extra_init {
+            let uninit = n - extra_init;
+            let unfilled = &mut self.uninitialized_mut()[0..uninit];
+
+            for byte in unfilled.iter_mut() {
+                *byte = MaybeUninit::new(0);
+            }
+
+            // We just initialized `uninit` bytes.
+            unsafe {
+                self.assume_init(n);
+            }
+        }
+
+        let filled = self.filled;
+
+        &mut self.initialized_mut()[filled..filled + n]
+    }
+
+    /// Returns the number of bytes at the end of the slice that have not yet
+    /// been filled.
+    #[unstable(feature = "read_buf", reason = "recently added", issue = "0")]
+    #[inline]
+    pub fn remaining(&self) -> usize {
+        self.capacity() - self.filled
+    }
+
+    /// Clears the buffer, resetting the filled region to empty.
+    ///
+    /// The number of initialized bytes is not changed, and the contents of
+    /// the buffer are not modified.
+    #[unstable(feature = "read_buf", reason = "recently added", issue = "0")]
+    #[inline]
+    pub fn clear(&mut self) {
+        self.set_filled(0); // The assertion in `set_filled` is optimized out.
+    }
+
+    /// Increases the size of the filled region of the buffer.
+    ///
+    /// The number of initialized bytes is not changed.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the filled region of the buffer would become larger than the
+    /// initialized region.
+    #[unstable(feature = "read_buf", reason = "recently added", issue = "0")]
+    #[inline]
+    pub fn add_filled(&mut self, n: usize) {
+        self.set_filled(self.filled + n);
+    }
+
+    /// Sets the size of the filled region of the buffer.
+    ///
+    /// The number of initialized bytes is not changed.
+    ///
+    /// Note that this can be used to *shrink* the filled region of the
+    /// buffer in addition to growing it (for example, by a `Read`
+    /// implementation that compresses data in-place).
+    ///
+    /// # Panics
+    ///
+    /// Panics if the filled region of the buffer would become larger than the
+    /// initialized region.
+    #[unstable(feature = "read_buf", reason = "recently added", issue = "0")]
+    #[inline]
+    pub fn set_filled(&mut self, n: usize) {
+        assert!(n <= self.initialized);
+
+        self.filled = n;
+    }
+
+    /// Asserts that the first `n` unfilled bytes of the buffer are
+    /// initialized.
+    ///
+    /// `ReadBuf` assumes that bytes are never de-initialized, so this method
+    /// does nothing when called with fewer bytes than are already known to be
+    /// initialized.
+    ///
+    /// # Safety
+    ///
+    /// The caller must ensure that the first `n` unfilled bytes of the buffer
+    /// have already been initialized.
+    #[unstable(feature = "read_buf", reason = "recently added", issue = "0")]
+    #[inline]
+    pub unsafe fn assume_init(&mut self, n: usize) {
+        self.initialized = cmp::max(self.initialized, self.filled + n);
+    }
+
+    /// Appends data to the buffer, advancing the filled and initialized
+    /// regions as needed.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `self.remaining()` is less than `buf.len()`.
+    #[unstable(feature = "read_buf", reason = "recently added", issue = "0")]
+    #[inline]
+    pub fn append(&mut self, buf: &[u8]) {
+        assert!(self.remaining() >= buf.len());
+
+        // We do not de-initialize any of the elements of the slice,
+        // and the two buffers can't overlap since `self.buf` is borrowed
+        // mutably.
+        unsafe {
+            let dst = self.unfilled_mut().as_mut_ptr() as *mut u8;
+            ptr::copy_nonoverlapping(buf.as_ptr(), dst, buf.len());
+        }
+
+        // We just added the entire contents of `buf` to the filled
+        // section.
+        unsafe {
+            self.assume_init(buf.len());
+        }
+        self.add_filled(buf.len());
+    }
+
+    /// Returns the amount of bytes that have been filled.
+    #[unstable(feature = "read_buf", reason = "recently added", issue = "0")]
+    #[inline]
+    pub fn filled_len(&self) -> usize {
+        self.filled
+    }
+
+    /// Returns the amount of bytes that have been initialized.
+    #[unstable(feature = "read_buf", reason = "recently added", issue = "0")]
+    #[inline]
+    pub fn initialized_len(&self) -> usize {
+        self.initialized
+    }
+
+    /// Returns whether the buffer still borrows the `len` bytes at `ptr`.
+    ///
+    /// `Read::read_buf` is safe to implement, and nothing stops an
+    /// implementation from replacing the `ReadBuf` it is handed with one
+    /// borrowing some other memory, such as a leaked allocation. Callers which
+    /// trust the lengths of the buffer to know what they can assume is
+    /// initialized must check this after `read_buf` returns.
+    pub(crate) fn is_backed_by(&self, ptr: *const MaybeUninit<u8>, len: usize) -> bool {
+        self.buf.as_ptr() == ptr && self.buf.len() == len
+    }
+}
+
+// `MaybeUninit<u8>` has the same layout as `u8`; the caller guarantees that
+// every byte of the slice is initialized.
+unsafe fn slice_assume_init(slice: &[MaybeUninit<u8>]) -> &[u8] {
+    &*(slice as *const [MaybeUninit<u8>] as *const [u8])
+}
+
+unsafe fn slice_assume_init_mut(slice: &mut [MaybeUninit<u8>]) -> &mut [u8] {
+    &mut *(slice as *mut [MaybeUninit<u8>] as *mut [u8])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ReadBuf;
+    use crate::mem::MaybeUninit;
+
+    #[test]
+    fn new() {
+        let mut buf = [0; 16];
+        let rbuf = ReadBuf::new(&mut buf);
+
+        assert_eq!(rbuf.filled_len(), 0);
+        assert_eq!(rbuf.initialized_len(), 16);
+        assert_eq!(rbuf.capacity(), 16);
+        assert_eq!(rbuf.remaining(), 16);
+    }
+
+    #[test]
+    fn uninit() {
+        let mut buf = [MaybeUninit::<u8>::uninit(); 16];
+        let rbuf = ReadBuf::uninit(&mut buf);
+
+        assert_eq!(rbuf.filled_len(), 0);
+        assert_eq!(rbuf.initialized_len(), 0);
+        assert_eq!(rbuf.capacity(), 16);
+        assert_eq!(rbuf.remaining(), 16);
+    }
+
+    #[test]
+    fn initialize_unfilled() {
+        let mut buf = [MaybeUninit::<u8>::uninit(); 16];
+        let mut rbuf = ReadBuf::uninit(&mut buf);
+
+        assert_eq!(rbuf.initialize_unfilled(), &[0; 16][..]);
+        assert_eq!(rbuf.initialized_len(), 16);
+    }
+
+    #[test]
+    fn initialize_unfilled_to() {
+        let mut buf = [MaybeUninit::<u8>::uninit(); 16];
+        let mut rbuf = ReadBuf::uninit(&mut buf);
+
+        rbuf.initialize_unfilled_to(8);
+        assert_eq!(rbuf.initialized_len(), 8);
+
+        rbuf.initialize_unfilled_to(4);
+        assert_eq!(rbuf.initialized_len(), 8);
+
+        rbuf.set_filled(8);
+        rbuf.initialize_unfilled_to(6);
+        assert_eq!(rbuf.initialized_len(), 14);
+
+        rbuf.initialize_unfilled_to(8);
+        assert_eq!(rbuf.initialized_len(), 16);
+    }
+
+    #[test]
+    fn add_filled() {
+        let mut buf = [0; 16];
+        let mut rbuf = ReadBuf::new(&mut buf);
+
+        rbuf.add_filled(1);
+        assert_eq!(rbuf.filled_len(), 1);
+        assert_eq!(rbuf.remaining(), 15);
+    }
+
+    #[test]
+    #[should_panic]
+    fn add_filled_panic() {
+        let mut buf = [MaybeUninit::<u8>::uninit(); 16];
+        let mut rbuf = ReadBuf::uninit(&mut buf);
+
+        rbuf.add_filled(1);
+    }
+
+    #[test]
+    fn clear() {
+        let mut buf = [255; 16];
+        let mut rbuf = ReadBuf::new(&mut buf);
+
+        rbuf.set_filled(16);
+        assert_eq!(rbuf.filled_len(), 16);
+        assert_eq!(rbuf.remaining(), 0);
+
+        rbuf.clear();
+        assert_eq!(rbuf.filled_len(), 0);
+        assert_eq!(rbuf.remaining(), 16);
+        assert_eq!(rbuf.initialized(), [255; 16]);
+    }
+
+    #[test]
+    fn assume_init() {
+        let mut buf = [MaybeUninit::<u8>::uninit(); 16];
+        let mut rbuf = ReadBuf::uninit(&mut buf);
+
+        unsafe { rbuf.assume_init(8) };
+        assert_eq!(rbuf.initialized_len(), 8);
+
+        rbuf.add_filled(4);
+        unsafe { rbuf.assume_init(2) };
+        assert_eq!(rbuf.initialized_len(), 8);
+
+        unsafe { rbuf.assume_init(8) };
+        assert_eq!(rbuf.initialized_len(), 12);
+    }
+
+    #[test]
+    fn append() {
+        let mut buf = [MaybeUninit::new(255u8); 16];
+        let mut rbuf = ReadBuf::uninit(&mut buf);
+
+        rbuf.append(&[0; 8]);
+        assert_eq!(rbuf.initialized_len(), 8);
+        assert_eq!(rbuf.filled_len(), 8);
+        assert_eq!(rbuf.filled(), [0; 8]);
+
+        rbuf.clear();
+
+        rbuf.append(&[1; 16]);
+        assert_eq!(rbuf.initialized_len(), 16);
+        assert_eq!(rbuf.filled_len(), 16);
+        assert_eq!(rbuf.filled(), [1; 16]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn append_too_much() {
+        let mut buf = [0; 4];
+        let mut rbuf = ReadBuf::new(&mut buf);
+
+        rbuf.append(&[0; 5]);
+    }
+}
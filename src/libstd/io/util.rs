@@ -1,8 +1,9 @@
 #![allow(missing_copy_implementations)]
 
 use crate::fmt;
-use crate::io::{self, Read, Initializer, Write, ErrorKind, BufRead, IoSlice, IoSliceMut};
-use crate::mem;
+use crate::io::{self, Read, Initializer, Write, ErrorKind, BufRead, IoSlice, IoSliceMut,
+        ReadBuf};
+use crate::mem::MaybeUninit;
 
 /// Copies the entire contents of a reader into a writer.
 ///
@@ -18,6 +19,21 @@ use crate::mem;
 ///
 /// [`fs::copy`]: ../fs/fn.copy.html
 ///
+/// # Platform-specific behavior
+///
+/// On Linux and Android, this function copies the data in the kernel, without
+/// reading it into memory, when both `reader` and `writer` are a [`File`], a
+/// [`TcpStream`], a [`UnixStream`] or the pipes of a child process. It uses
+/// `copy_file_range`, `sendfile` or `splice` for this, depending on the types
+/// of the files, and falls back to reading and writing if none of them can be
+/// used.
+/// Note that, this [may change in the future][changes].
+///
+/// [`File`]: ../fs/struct.File.html
+/// [`TcpStream`]: ../net/struct.TcpStream.html
+/// [`UnixStream`]: ../os/unix/net/struct.UnixStream.html
+/// [changes]: index.html#platform-specific-behavior
+///
 /// # Errors
 ///
 /// This function will return an error immediately if any call to `read` or
@@ -43,23 +59,42 @@ use crate::mem;
 pub fn copy<R: ?Sized, W: ?Sized>(reader: &mut R, writer: &mut W) -> io::Result<u64>
     where R: Read, W: Write
 {
-    let mut buf = unsafe {
-        #[allow(deprecated)]
-        let mut buf: [u8; super::DEFAULT_BUF_SIZE] = mem::uninitialized();
-        reader.initializer().initialize(&mut buf);
-        buf
-    };
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    {
+        crate::sys::kernel_copy::copy_spec(reader, writer)
+    }
+    #[cfg(not(any(target_os = "linux", target_os = "android")))]
+    {
+        generic_copy(reader, writer)
+    }
+}
+
+/// The implementation of `copy` which reads into a buffer in memory, used as
+/// is on most platforms and as the fallback of the specialized copies.
+pub(crate) fn generic_copy<R: ?Sized, W: ?Sized>(reader: &mut R, writer: &mut W)
+                                                 -> io::Result<u64>
+    where R: Read, W: Write
+{
+    let mut buf = [MaybeUninit::<u8>::uninit(); super::DEFAULT_BUF_SIZE];
+    let mut buf = ReadBuf::uninit(&mut buf);
 
     let mut written = 0;
     loop {
-        let len = match reader.read(&mut buf) {
-            Ok(0) => return Ok(written),
-            Ok(len) => len,
+        match reader.read_buf(&mut buf) {
+            Ok(()) => {}
             Err(ref e) if e.kind() == ErrorKind::Interrupted => continue,
             Err(e) => return Err(e),
-        };
-        writer.write_all(&buf[..len])?;
-        written += len as u64;
+        }
+
+        if buf.filled().is_empty() {
+            return Ok(written);
+        }
+
+        writer.write_all(buf.filled())?;
+        written += buf.filled_len() as u64;
+        // Only the filled region is reset, the bytes the reader initialized
+        // are remembered so they aren't zeroed again on the next read.
+        buf.clear();
     }
 }
 
@@ -1,6 +1,8 @@
 #![stable(feature = "metadata_ext", since = "1.1.0")]
 
-use crate::fs::Metadata;
+use crate::fs::{self, Metadata};
+use crate::io;
+use crate::path::Path;
 use crate::sys_common::AsInner;
 
 #[allow(deprecated)]
@@ -381,3 +383,104 @@ impl MetadataExt for Metadata {
         self.as_inner().as_inner().st_blocks as u64
     }
 }
+
+/// Linux-specific extensions to [`fs::File`] for managing the disk space it uses.
+///
+/// [`fs::File`]: ../../../../std/fs/struct.File.html
+#[cfg(target_os = "linux")]
+#[unstable(feature = "linux_file_ext", reason = "recently added", issue = "0")]
+pub trait FileAllocateExt {
+    /// Allocates disk space for the byte range `offset..offset + len` of the
+    /// file, extending the file if the range ends beyond its current size.
+    ///
+    /// Writing to the range is guaranteed not to fail for lack of disk space
+    /// afterwards. The newly allocated parts read as zeros.
+    ///
+    /// This corresponds to the `fallocate` function with a `mode` of `0`.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(linux_file_ext)]
+    /// use std::fs::File;
+    /// use std::os::linux::fs::FileAllocateExt;
+    ///
+    /// fn main() -> std::io::Result<()> {
+    ///     let file = File::create("foo.db")?;
+    ///     file.allocate(0, 1 << 20)?;
+    ///     assert_eq!(file.metadata()?.len(), 1 << 20);
+    ///     Ok(())
+    /// }
+    /// ```
+    fn allocate(&self, offset: u64, len: u64) -> io::Result<()>;
+
+    /// Deallocates the disk space of the byte range `offset..offset + len` of
+    /// the file, which reads as zeros afterwards. The size of the file does
+    /// not change.
+    ///
+    /// This corresponds to the `fallocate` function with the
+    /// `FALLOC_FL_PUNCH_HOLE` and `FALLOC_FL_KEEP_SIZE` flags, and fails with
+    /// an error of kind [`Other`] if the filesystem doesn't support it.
+    ///
+    /// [`Other`]: ../../../../std/io/enum.ErrorKind.html#variant.Other
+    fn punch_hole(&self, offset: u64, len: u64) -> io::Result<()>;
+}
+
+#[cfg(target_os = "linux")]
+#[unstable(feature = "linux_file_ext", reason = "recently added", issue = "0")]
+impl FileAllocateExt for fs::File {
+    fn allocate(&self, offset: u64, len: u64) -> io::Result<()> {
+        self.as_inner().allocate(offset, len)
+    }
+
+    fn punch_hole(&self, offset: u64, len: u64) -> io::Result<()> {
+        self.as_inner().punch_hole(offset, len)
+    }
+}
+
+/// Creates a new file at `to` which shares the data of the file at `from`,
+/// as a copy-on-write clone, or "reflink".
+///
+/// This takes constant time and no additional disk space, until either file
+/// is modified. The permissions of `from` are copied to `to`, like
+/// [`fs::copy`] does.
+///
+/// This corresponds to the `FICLONE` `ioctl`, which is only supported by some
+/// filesystems, such as Btrfs and XFS. Unlike [`fs::copy`], this function
+/// fails instead of copying the data if the files can't share it, for example
+/// because they are on different filesystems. `to` is removed again in that
+/// case.
+///
+/// [`fs::copy`]: ../../../../std/fs/fn.copy.html
+///
+/// # Errors
+///
+/// This function will return an error if `to` already exists, in addition to
+/// the cases described above.
+///
+/// # Examples
+///
+/// ```no_run
+/// #![feature(linux_file_ext)]
+/// use std::os::linux::fs;
+///
+/// fn main() -> std::io::Result<()> {
+///     fs::clone_file("foo.db", "foo.db.snapshot")?;
+///     Ok(())
+/// }
+/// ```
+#[cfg(target_os = "linux")]
+#[unstable(feature = "linux_file_ext", reason = "recently added", issue = "0")]
+pub fn clone_file<P: AsRef<Path>, Q: AsRef<Path>>(from: P, to: Q) -> io::Result<()> {
+    let (from, to) = (from.as_ref(), to.as_ref());
+    let reader = fs::File::open(from)?;
+    let perm = reader.metadata()?.permissions();
+    let writer = fs::OpenOptions::new().write(true).create_new(true).open(to)?;
+    let res = writer.as_inner().ficlone(reader.as_inner())
+        .and_then(|()| writer.set_permissions(perm));
+    if res.is_err() {
+        drop(writer);
+        let _ = fs::remove_file(to);
+    }
+    res
+}
@@ -0,0 +1,194 @@
+//! Linux-specific extensions to primitives in the `std::process` module.
+
+#![unstable(feature = "linux_pidfd", issue = "0")]
+
+use crate::fmt;
+use crate::io::{self, ErrorKind};
+use crate::os::unix::io::{AsRawFd, FromRawFd, IntoRawFd, RawFd};
+use crate::process::{self, ExitStatus};
+use crate::sys::fd::FileDesc;
+use crate::sys::process::PidFd;
+use crate::sys_common::{AsInner, AsInnerMut, FromInner, IntoInner};
+
+/// An owned file descriptor referring to a process, a "pidfd".
+///
+/// Unlike a process ID, a pidfd keeps referring to the same process after it
+/// has exited, so it can't be used to signal or wait on an unrelated process
+/// that happened to be given the same pid. The file descriptor becomes
+/// readable once the process exits, which makes it possible to wait for the
+/// process with `poll` or `epoll` alongside other file descriptors.
+///
+/// A pidfd is created for a child process by enabling
+/// [`CommandExt::create_pidfd`] before spawning it, and can then be obtained
+/// from the [`Child`] with [`ChildExt::pidfd`] or [`ChildExt::take_pidfd`].
+///
+/// pidfds are available since Linux 5.3.
+///
+/// [`CommandExt::create_pidfd`]: trait.CommandExt.html#tymethod.create_pidfd
+/// [`Child`]: ../../../../std/process/struct.Child.html
+/// [`ChildExt::pidfd`]: trait.ChildExt.html#tymethod.pidfd
+/// [`ChildExt::take_pidfd`]: trait.ChildExt.html#tymethod.take_pidfd
+///
+/// # Examples
+///
+/// ```no_run
+/// #![feature(linux_pidfd)]
+/// use std::os::linux::process::{ChildExt, CommandExt};
+/// use std::process::Command;
+///
+/// fn main() -> std::io::Result<()> {
+///     let mut child = Command::new("sleep")
+///         .arg("60")
+///         .create_pidfd(true)
+///         .spawn()?;
+///
+///     let pidfd = child.take_pidfd()?;
+///     pidfd.kill()?;
+///     let status = pidfd.wait()?;
+///     assert!(!status.success());
+///     Ok(())
+/// }
+/// ```
+// Transparent so that `ChildExt::pidfd` can hand out the sys-level pidfd the
+// `Child` owns as a reference to an `OwnedPidFd`.
+#[repr(transparent)]
+pub struct OwnedPidFd {
+    inner: PidFd,
+}
+
+impl OwnedPidFd {
+    /// Forces the process to exit, by sending it `SIGKILL`.
+    ///
+    /// This fails with `ESRCH` if the process has already been waited on.
+    pub fn kill(&self) -> io::Result<()> {
+        self.inner.kill()
+    }
+
+    /// Waits for the process to exit completely, returning the status that it
+    /// exited with.
+    ///
+    /// This reaps the process, so waiting on it a second time, either through
+    /// this pidfd or through the [`Child`] it was obtained from, fails.
+    ///
+    /// [`Child`]: ../../../../std/process/struct.Child.html
+    pub fn wait(&self) -> io::Result<ExitStatus> {
+        self.inner.wait().map(ExitStatus::from_inner)
+    }
+
+    /// Attempts to collect the exit status of the process if it has already
+    /// exited.
+    ///
+    /// Returns `Ok(None)` if the process is still running, otherwise the
+    /// process is reaped like with [`wait`].
+    ///
+    /// [`wait`]: #method.wait
+    pub fn try_wait(&self) -> io::Result<Option<ExitStatus>> {
+        Ok(self.inner.try_wait()?.map(ExitStatus::from_inner))
+    }
+}
+
+impl OwnedPidFd {
+    fn from_inner_ref(inner: &PidFd) -> &OwnedPidFd {
+        unsafe { &*(inner as *const PidFd as *const OwnedPidFd) }
+    }
+}
+
+impl AsInner<PidFd> for OwnedPidFd {
+    fn as_inner(&self) -> &PidFd { &self.inner }
+}
+
+impl FromInner<PidFd> for OwnedPidFd {
+    fn from_inner(inner: PidFd) -> OwnedPidFd {
+        OwnedPidFd { inner }
+    }
+}
+
+impl IntoInner<PidFd> for OwnedPidFd {
+    fn into_inner(self) -> PidFd { self.inner }
+}
+
+impl AsRawFd for OwnedPidFd {
+    fn as_raw_fd(&self) -> RawFd {
+        self.inner.as_inner().raw()
+    }
+}
+
+impl FromRawFd for OwnedPidFd {
+    unsafe fn from_raw_fd(fd: RawFd) -> OwnedPidFd {
+        OwnedPidFd::from_inner(PidFd::from_inner(FileDesc::new(fd)))
+    }
+}
+
+impl IntoRawFd for OwnedPidFd {
+    fn into_raw_fd(self) -> RawFd {
+        self.inner.into_inner().into_raw()
+    }
+}
+
+impl fmt::Debug for OwnedPidFd {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("OwnedPidFd")
+            .field("fd", &self.as_raw_fd())
+            .finish()
+    }
+}
+
+/// Linux-specific extensions to the [`process::Child`] type.
+///
+/// [`process::Child`]: ../../../../std/process/struct.Child.html
+pub trait ChildExt {
+    /// Returns a reference to the pidfd of the child.
+    ///
+    /// This fails if the child was spawned without
+    /// [`CommandExt::create_pidfd`], or if the pidfd could not be created,
+    /// for example because the kernel does not support pidfds. The child is
+    /// spawned normally in that case, and can still be managed through the
+    /// methods on [`Child`].
+    ///
+    /// [`CommandExt::create_pidfd`]: trait.CommandExt.html#tymethod.create_pidfd
+    /// [`Child`]: ../../../../std/process/struct.Child.html
+    fn pidfd(&self) -> io::Result<&OwnedPidFd>;
+
+    /// Takes ownership of the pidfd of the child.
+    ///
+    /// Fails in the same cases as [`pidfd`], as well as when the pidfd has
+    /// already been taken.
+    ///
+    /// [`pidfd`]: #tymethod.pidfd
+    fn take_pidfd(&mut self) -> io::Result<OwnedPidFd>;
+}
+
+impl ChildExt for process::Child {
+    fn pidfd(&self) -> io::Result<&OwnedPidFd> {
+        self.as_inner().pidfd().map(OwnedPidFd::from_inner_ref).ok_or_else(no_pidfd)
+    }
+
+    fn take_pidfd(&mut self) -> io::Result<OwnedPidFd> {
+        self.as_inner_mut().take_pidfd().map(OwnedPidFd::from_inner).ok_or_else(no_pidfd)
+    }
+}
+
+fn no_pidfd() -> io::Error {
+    io::Error::new(ErrorKind::Other, "no pidfd was created for this child")
+}
+
+/// Linux-specific extensions to the [`process::Command`] builder.
+///
+/// [`process::Command`]: ../../../../std/process/struct.Command.html
+pub trait CommandExt {
+    /// Sets whether a pidfd should be created for the spawned child, which
+    /// can then be retrieved with [`ChildExt::pidfd`].
+    ///
+    /// Spawning does not fail if the pidfd can't be created, for example on
+    /// kernels older than 5.3, only retrieving it does.
+    ///
+    /// [`ChildExt::pidfd`]: trait.ChildExt.html#tymethod.pidfd
+    fn create_pidfd(&mut self, val: bool) -> &mut process::Command;
+}
+
+impl CommandExt for process::Command {
+    fn create_pidfd(&mut self, val: bool) -> &mut process::Command {
+        self.as_inner_mut().create_pidfd(val);
+        self
+    }
+}
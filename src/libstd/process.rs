@@ -189,6 +189,10 @@ impl AsInner<imp::Process> for Child {
     fn as_inner(&self) -> &imp::Process { &self.handle }
 }
 
+impl AsInnerMut<imp::Process> for Child {
+    fn as_inner_mut(&mut self) -> &mut imp::Process { &mut self.handle }
+}
+
 impl FromInner<(imp::Process, imp::StdioPipes)> for Child {
     fn from_inner((handle, io): (imp::Process, imp::StdioPipes)) -> Child {
         Child {
@@ -822,6 +826,108 @@ impl Command {
         self.inner.spawn(imp::Stdio::Inherit, true).map(Child::from_inner)
                   .and_then(|mut p| p.wait())
     }
+
+    /// Returns the path to the program that was given to [`Command::new`].
+    ///
+    /// [`Command::new`]: #method.new
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(command_access)]
+    /// use std::process::Command;
+    ///
+    /// let cmd = Command::new("echo");
+    /// assert_eq!(cmd.get_program(), "echo");
+    /// ```
+    #[unstable(feature = "command_access", reason = "recently added", issue = "0")]
+    pub fn get_program(&self) -> &OsStr {
+        self.inner.get_program()
+    }
+
+    /// Returns an iterator of the arguments that will be passed to the program.
+    ///
+    /// This does not include the path to the program as the first argument,
+    /// it only includes the arguments specified with [`arg`] and [`args`].
+    ///
+    /// [`arg`]: #method.arg
+    /// [`args`]: #method.args
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(command_access)]
+    /// use std::ffi::OsStr;
+    /// use std::process::Command;
+    ///
+    /// let mut cmd = Command::new("echo");
+    /// cmd.arg("first").arg("second");
+    /// let args: Vec<&OsStr> = cmd.get_args().collect();
+    /// assert_eq!(args, &["first", "second"]);
+    /// ```
+    #[unstable(feature = "command_access", reason = "recently added", issue = "0")]
+    pub fn get_args(&self) -> CommandArgs<'_> {
+        CommandArgs { inner: self.inner.get_args() }
+    }
+
+    /// Returns an iterator of the environment variables that will be set when
+    /// the process is spawned.
+    ///
+    /// Each element is a tuple `(&OsStr, Option<&OsStr>)`, where the first
+    /// value is the key, and the second is the value, which is [`None`] if
+    /// the environment variable is to be explicitly removed.
+    ///
+    /// This only includes environment variables explicitly set with
+    /// [`env`], [`envs`] and [`env_remove`]. It does not include environment
+    /// variables that will be inherited by the child process.
+    ///
+    /// [`None`]: ../option/enum.Option.html#variant.None
+    /// [`env`]: #method.env
+    /// [`envs`]: #method.envs
+    /// [`env_remove`]: #method.env_remove
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(command_access)]
+    /// use std::ffi::OsStr;
+    /// use std::process::Command;
+    ///
+    /// let mut cmd = Command::new("ls");
+    /// cmd.env("TERM", "dumb").env_remove("TZ");
+    /// let envs: Vec<(&OsStr, Option<&OsStr>)> = cmd.get_envs().collect();
+    /// assert_eq!(envs, &[
+    ///     (OsStr::new("TERM"), Some(OsStr::new("dumb"))),
+    ///     (OsStr::new("TZ"), None)
+    /// ]);
+    /// ```
+    #[unstable(feature = "command_access", reason = "recently added", issue = "0")]
+    pub fn get_envs(&self) -> CommandEnvs<'_> {
+        CommandEnvs { iter: self.inner.get_envs() }
+    }
+
+    /// Returns the working directory for the child process.
+    ///
+    /// This returns [`None`] if the working directory will not be changed.
+    ///
+    /// [`None`]: ../option/enum.Option.html#variant.None
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(command_access)]
+    /// use std::path::Path;
+    /// use std::process::Command;
+    ///
+    /// let mut cmd = Command::new("ls");
+    /// assert_eq!(cmd.get_current_dir(), None);
+    /// cmd.current_dir("/bin");
+    /// assert_eq!(cmd.get_current_dir(), Some(Path::new("/bin")));
+    /// ```
+    #[unstable(feature = "command_access", reason = "recently added", issue = "0")]
+    pub fn get_current_dir(&self) -> Option<&Path> {
+        self.inner.get_current_dir()
+    }
 }
 
 #[stable(feature = "rust1", since = "1.0.0")]
@@ -842,6 +948,66 @@ impl AsInnerMut<imp::Command> for Command {
     fn as_inner_mut(&mut self) -> &mut imp::Command { &mut self.inner }
 }
 
+/// An iterator over the command arguments.
+///
+/// This struct is created by [`Command::get_args`]. See its documentation for
+/// more.
+///
+/// [`Command::get_args`]: struct.Command.html#method.get_args
+#[unstable(feature = "command_access", reason = "recently added", issue = "0")]
+#[derive(Debug)]
+pub struct CommandArgs<'a> {
+    inner: imp::CommandArgs<'a>,
+}
+
+#[unstable(feature = "command_access", reason = "recently added", issue = "0")]
+impl<'a> Iterator for CommandArgs<'a> {
+    type Item = &'a OsStr;
+    fn next(&mut self) -> Option<&'a OsStr> {
+        self.inner.next()
+    }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+#[unstable(feature = "command_access", reason = "recently added", issue = "0")]
+impl<'a> ExactSizeIterator for CommandArgs<'a> {
+    fn len(&self) -> usize {
+        self.inner.len()
+    }
+}
+
+/// An iterator over the command environment variables.
+///
+/// This struct is created by [`Command::get_envs`]. See its documentation
+/// for more.
+///
+/// [`Command::get_envs`]: struct.Command.html#method.get_envs
+#[unstable(feature = "command_access", reason = "recently added", issue = "0")]
+#[derive(Debug)]
+pub struct CommandEnvs<'a> {
+    iter: imp::CommandEnvs<'a>,
+}
+
+#[unstable(feature = "command_access", reason = "recently added", issue = "0")]
+impl<'a> Iterator for CommandEnvs<'a> {
+    type Item = (&'a OsStr, Option<&'a OsStr>);
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter.next()
+    }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+#[unstable(feature = "command_access", reason = "recently added", issue = "0")]
+impl<'a> ExactSizeIterator for CommandEnvs<'a> {
+    fn len(&self) -> usize {
+        self.iter.len()
+    }
+}
+
 /// The output of a finished process.
 ///
 /// This is returned in a Result by either the [`output`] method of a
@@ -2038,6 +2204,149 @@ mod tests {
         assert!(events > 0);
     }
 
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn test_pidfd() {
+        use crate::os::linux::process::{ChildExt, CommandExt};
+        use crate::os::unix::process::ExitStatusExt;
+
+        let mut p = Command::new("true").spawn().unwrap();
+        assert!(p.pidfd().is_err());
+        assert!(p.wait().unwrap().success());
+
+        let mut p = Command::new("sleep").arg("1000").create_pidfd(true).spawn().unwrap();
+        let pidfd = match p.take_pidfd() {
+            Ok(pidfd) => pidfd,
+            // Kernels older than 5.3 have no pidfds, the child is spawned
+            // regardless.
+            Err(_) => {
+                p.kill().unwrap();
+                assert!(!p.wait().unwrap().success());
+                return;
+            }
+        };
+        assert!(p.take_pidfd().is_err());
+        assert_eq!(pidfd.try_wait().unwrap(), None);
+        pidfd.kill().unwrap();
+        assert_eq!(pidfd.wait().unwrap().signal(), Some(libc::SIGKILL));
+    }
+
+    #[test]
+    fn test_command_access() {
+        use crate::ffi::OsStr;
+        use crate::path::Path;
+
+        let mut cmd = Command::new("echo");
+        assert_eq!(cmd.get_program(), "echo");
+        assert_eq!(cmd.get_args().len(), 0);
+        assert_eq!(cmd.get_envs().len(), 0);
+        assert_eq!(cmd.get_current_dir(), None);
+
+        cmd.args(&["a", "b"]).env("B", "1").env_remove("A").current_dir("dir");
+        assert_eq!(cmd.get_args().collect::<Vec<_>>(), &["a", "b"]);
+        assert_eq!(cmd.get_envs().collect::<Vec<_>>(), &[
+            (OsStr::new("A"), None),
+            (OsStr::new("B"), Some(OsStr::new("1"))),
+        ]);
+        assert_eq!(cmd.get_current_dir(), Some(Path::new("dir")));
+    }
+
+    #[test]
+    #[cfg(all(unix, not(target_os = "android")))]
+    fn test_arg0() {
+        use crate::os::unix::process::CommandExt;
+
+        let mut cmd = Command::new("sh");
+        cmd.arg0("custom-arg0").args(&["-c", "echo $0"]);
+        assert_eq!(cmd.get_program(), "sh");
+        assert_eq!(cmd.get_args().collect::<Vec<_>>(), &["-c", "echo $0"]);
+
+        let output = cmd.output().unwrap();
+        assert!(output.status.success());
+        assert_eq!(str::from_utf8(&output.stdout).unwrap().trim(), "custom-arg0");
+    }
+
+    #[test]
+    #[cfg(all(unix, not(target_os = "android")))]
+    fn test_process_group_and_setsid() {
+        use crate::os::unix::process::CommandExt;
+
+        let mut p = Command::new("sleep").arg("1000").process_group(0).spawn().unwrap();
+        let pid = p.id() as libc::pid_t;
+        assert_eq!(unsafe { libc::getpgid(pid) }, pid);
+        assert_ne!(unsafe { libc::getsid(pid) }, pid);
+        p.kill().unwrap();
+        p.wait().unwrap();
+
+        let mut p = Command::new("sleep").arg("1000").setsid(true).spawn().unwrap();
+        let pid = p.id() as libc::pid_t;
+        assert_eq!(unsafe { libc::getsid(pid) }, pid);
+        assert_eq!(unsafe { libc::getpgid(pid) }, pid);
+        p.kill().unwrap();
+        p.wait().unwrap();
+
+        let err = Command::new("true").process_group(0).setsid(true).spawn().unwrap_err();
+        assert_eq!(err.kind(), crate::io::ErrorKind::InvalidInput);
+    }
+
+    #[test]
+    #[cfg(all(unix, not(target_os = "android")))]
+    fn test_io_copy_child_stdout_to_file() {
+        use crate::fs::{self, File};
+        use crate::io::{self, SeekFrom};
+        use crate::sys_common::io::test::tmpdir;
+
+        let tmpdir = tmpdir();
+        let out = tmpdir.join("out.txt");
+        let mut writer = File::create(&out).unwrap();
+        writer.write_all(b"head").unwrap();
+
+        let mut p = Command::new("echo").arg("hello").stdout(Stdio::piped()).spawn().unwrap();
+        let copied = io::copy(p.stdout.as_mut().unwrap(), &mut writer).unwrap();
+        assert!(p.wait().unwrap().success());
+        assert_eq!(copied, 6);
+        assert_eq!(writer.seek(SeekFrom::Current(0)).unwrap(), 10);
+        assert_eq!(fs::read(&out).unwrap(), b"headhello\n");
+    }
+
+    #[test]
+    #[cfg(all(unix, not(target_os = "android")))]
+    fn test_io_copy_file_to_child_stdin() {
+        use crate::fs::{self, File};
+        use crate::io::{self, SeekFrom};
+        use crate::sys_common::io::test::tmpdir;
+        use crate::thread;
+
+        let tmpdir = tmpdir();
+        let input = tmpdir.join("in.txt");
+        let data = (0..100_000u32).map(|i| i as u8).collect::<Vec<_>>();
+        fs::write(&input, &data).unwrap();
+
+        let mut p = Command::new("cat")
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .spawn()
+            .unwrap();
+        // More than a pipe buffer is copied, so the output has to be read
+        // while the input is still being written.
+        let mut stdout = p.stdout.take().unwrap();
+        let receiver = thread::spawn(move || {
+            let mut v = Vec::new();
+            stdout.read_to_end(&mut v).unwrap();
+            v
+        });
+
+        let mut reader = File::open(&input).unwrap();
+        reader.seek(SeekFrom::Start(10)).unwrap();
+        let mut stdin = p.stdin.take().unwrap();
+        assert_eq!(io::copy(&mut reader, &mut stdin).unwrap(), data.len() as u64 - 10);
+        assert_eq!(reader.seek(SeekFrom::Current(0)).unwrap(), data.len() as u64);
+        drop(stdin);
+
+        assert_eq!(&receiver.join().unwrap()[..], &data[10..]);
+        assert!(p.wait().unwrap().success());
+    }
+
     #[test]
     fn test_command_implements_send() {
         fn take_send_type<T: Send>(_: T) {}
@@ -141,6 +141,9 @@
 //!   writer at a time. In some cases, this can be more efficient than
 //!   a mutex.
 //!
+//! - [`SyncOnceCell`] and [`SyncLazy`]: Used for thread-safe, one-time
+//!   initialization of a value, such as a lazily computed global.
+//!
 //! [`Arc`]: crate::sync::Arc
 //! [`Barrier`]: crate::sync::Barrier
 //! [`Condvar`]: crate::sync::Condvar
@@ -148,6 +151,8 @@
 //! [`Mutex`]: crate::sync::Mutex
 //! [`Once`]: crate::sync::Once
 //! [`RwLock`]: crate::sync::RwLock
+//! [`SyncLazy`]: crate::sync::SyncLazy
+//! [`SyncOnceCell`]: crate::sync::SyncOnceCell
 
 #![stable(feature = "rust1", since = "1.0.0")]
 
@@ -165,6 +170,8 @@ pub use self::mutex::{Mutex, MutexGuard};
 #[stable(feature = "rust1", since = "1.0.0")]
 #[allow(deprecated)]
 pub use self::once::{Once, OnceState, ONCE_INIT};
+#[unstable(feature = "once_cell", issue = "0")]
+pub use self::once_cell::{SyncLazy, SyncOnceCell};
 #[stable(feature = "rust1", since = "1.0.0")]
 pub use crate::sys_common::poison::{PoisonError, TryLockError, TryLockResult, LockResult};
 #[stable(feature = "rust1", since = "1.0.0")]
@@ -176,4 +183,5 @@ mod barrier;
 mod condvar;
 mod mutex;
 mod once;
+mod once_cell;
 mod rwlock;
@@ -1,11 +1,13 @@
 use crate::ffi::OsString;
 use crate::fmt;
 use crate::hash::{Hash, Hasher};
-use crate::io::{self, SeekFrom, IoSlice, IoSliceMut};
+use crate::io::{self, SeekFrom, IoSlice, IoSliceMut, ReadBuf};
 use crate::path::{Path, PathBuf};
 use crate::sys::time::SystemTime;
 use crate::sys::{unsupported, Void};
 
+pub use crate::sys_common::fs::Dir;
+
 pub struct File(Void);
 
 pub struct FileAttr(Void);
@@ -198,6 +200,10 @@ impl File {
         match self.0 {}
     }
 
+    pub fn read_buf(&self, _buf: &mut ReadBuf<'_>) -> io::Result<()> {
+        match self.0 {}
+    }
+
     pub fn read_vectored(&self, _bufs: &mut [IoSliceMut<'_>]) -> io::Result<usize> {
         match self.0 {}
     }
@@ -226,6 +232,26 @@ impl File {
         match self.0 {}
     }
 
+    pub fn lock_shared(&self) -> io::Result<()> {
+        match self.0 {}
+    }
+
+    pub fn lock_exclusive(&self) -> io::Result<()> {
+        match self.0 {}
+    }
+
+    pub fn try_lock_shared(&self) -> io::Result<bool> {
+        match self.0 {}
+    }
+
+    pub fn try_lock_exclusive(&self) -> io::Result<bool> {
+        match self.0 {}
+    }
+
+    pub fn unlock(&self) -> io::Result<()> {
+        match self.0 {}
+    }
+
     pub fn diverge(&self) -> ! {
         match self.0 {}
     }
@@ -2,7 +2,7 @@ use crate::os::unix::prelude::*;
 
 use crate::ffi::{OsString, OsStr};
 use crate::fmt;
-use crate::io::{self, Error, SeekFrom, IoSlice, IoSliceMut};
+use crate::io::{self, Error, SeekFrom, IoSlice, IoSliceMut, ReadBuf};
 use crate::path::{Path, PathBuf};
 use crate::sync::Arc;
 use crate::sys::fd::FileDesc;
@@ -11,7 +11,7 @@ use crate::sys::{cvt, syscall};
 use crate::sys_common::{AsInner, FromInner};
 
 pub use crate::sys_common::fs::copy;
-pub use crate::sys_common::fs::remove_dir_all;
+pub use crate::sys_common::fs::{remove_dir_all, Dir};
 
 pub struct File(FileDesc);
 
@@ -278,6 +278,10 @@ impl File {
         self.0.read(buf)
     }
 
+    pub fn read_buf(&self, buf: &mut ReadBuf<'_>) -> io::Result<()> {
+        crate::io::default_read_buf(|b| self.read(b), buf)
+    }
+
     pub fn read_vectored(&self, bufs: &mut [IoSliceMut<'_>]) -> io::Result<usize> {
         crate::io::default_read_vectored(|buf| self.read(buf), bufs)
     }
@@ -317,6 +321,26 @@ impl File {
         set_perm(&self.path()?, perm)
     }
 
+    pub fn lock_shared(&self) -> io::Result<()> {
+        Err(unsupported_lock())
+    }
+
+    pub fn lock_exclusive(&self) -> io::Result<()> {
+        Err(unsupported_lock())
+    }
+
+    pub fn try_lock_shared(&self) -> io::Result<bool> {
+        Err(unsupported_lock())
+    }
+
+    pub fn try_lock_exclusive(&self) -> io::Result<bool> {
+        Err(unsupported_lock())
+    }
+
+    pub fn unlock(&self) -> io::Result<()> {
+        Err(unsupported_lock())
+    }
+
     pub fn path(&self) -> io::Result<PathBuf> {
         let mut buf: [u8; 4096] = [0; 4096];
         let count = cvt(syscall::fpath(*self.fd().as_inner() as usize, &mut buf))?;
@@ -445,3 +469,7 @@ pub fn canonicalize(p: &Path) -> io::Result<PathBuf> {
     let file = File(FileDesc::new(fd));
     file.path()
 }
+
+fn unsupported_lock() -> io::Error {
+    io::Error::new(io::ErrorKind::Other, "file locking is not supported on this platform")
+}
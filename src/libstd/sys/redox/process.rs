@@ -53,6 +53,10 @@ pub struct Command {
     stderr: Option<Stdio>,
 }
 
+pub type CommandEnvs<'a> = crate::sys_common::process::CommandEnvs<'a, DefaultEnvKey>;
+
+pub type CommandArgs<'a> = crate::sys_common::process::CommandArgs<'a, String>;
+
 // passed back to std::process with the pipes connected to the child, if any
 // were requested
 pub struct StdioPipes {
@@ -117,6 +121,19 @@ impl Command {
         self.gid = Some(id);
     }
 
+    pub fn get_program(&self) -> &OsStr {
+        OsStr::new(&self.program)
+    }
+    pub fn get_args(&self) -> CommandArgs<'_> {
+        CommandArgs::new(&self.args)
+    }
+    pub fn get_envs(&self) -> CommandEnvs<'_> {
+        self.env.iter()
+    }
+    pub fn get_current_dir(&self) -> Option<&Path> {
+        self.cwd.as_ref().map(|cwd| Path::new(cwd))
+    }
+
     pub unsafe fn pre_exec(
         &mut self,
         f: Box<dyn FnMut() -> io::Result<()> + Send + Sync>,
@@ -1,6 +1,7 @@
-use crate::ffi::OsStr;
+use crate::ffi::{OsStr, OsString};
 use crate::fmt;
 use crate::io;
+use crate::path::Path;
 use crate::sys::fs::File;
 use crate::sys::pipe::AnonPipe;
 use crate::sys::{unsupported, Void};
@@ -11,7 +12,10 @@ use crate::sys_common::process::{CommandEnv, DefaultEnvKey};
 ////////////////////////////////////////////////////////////////////////////////
 
 pub struct Command {
-    env: CommandEnv<DefaultEnvKey>
+    program: OsString,
+    args: Vec<OsString>,
+    env: CommandEnv<DefaultEnvKey>,
+    cwd: Option<OsString>,
 }
 
 // passed back to std::process with the pipes connected to the child, if any
@@ -29,20 +33,25 @@ pub enum Stdio {
 }
 
 impl Command {
-    pub fn new(_program: &OsStr) -> Command {
+    pub fn new(program: &OsStr) -> Command {
         Command {
-            env: Default::default()
+            program: program.to_os_string(),
+            args: Vec::new(),
+            env: Default::default(),
+            cwd: None,
         }
     }
 
-    pub fn arg(&mut self, _arg: &OsStr) {
+    pub fn arg(&mut self, arg: &OsStr) {
+        self.args.push(arg.to_os_string());
     }
 
     pub fn env_mut(&mut self) -> &mut CommandEnv<DefaultEnvKey> {
         &mut self.env
     }
 
-    pub fn cwd(&mut self, _dir: &OsStr) {
+    pub fn cwd(&mut self, dir: &OsStr) {
+        self.cwd = Some(dir.to_os_string());
     }
 
     pub fn stdin(&mut self, _stdin: Stdio) {
@@ -54,12 +63,32 @@ impl Command {
     pub fn stderr(&mut self, _stderr: Stdio) {
     }
 
+    pub fn get_program(&self) -> &OsStr {
+        &self.program
+    }
+
+    pub fn get_args(&self) -> CommandArgs<'_> {
+        CommandArgs::new(&self.args)
+    }
+
+    pub fn get_envs(&self) -> CommandEnvs<'_> {
+        self.env.iter()
+    }
+
+    pub fn get_current_dir(&self) -> Option<&Path> {
+        self.cwd.as_ref().map(|cwd| Path::new(cwd))
+    }
+
     pub fn spawn(&mut self, _default: Stdio, _needs_stdin: bool)
         -> io::Result<(Process, StdioPipes)> {
         unsupported()
     }
 }
 
+pub type CommandEnvs<'a> = crate::sys_common::process::CommandEnvs<'a, DefaultEnvKey>;
+
+pub type CommandArgs<'a> = crate::sys_common::process::CommandArgs<'a, OsString>;
+
 impl From<AnonPipe> for Stdio {
     fn from(pipe: AnonPipe) -> Stdio {
         pipe.diverge()
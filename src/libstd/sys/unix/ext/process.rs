@@ -2,6 +2,7 @@
 
 #![stable(feature = "rust1", since = "1.0.0")]
 
+use crate::ffi::OsStr;
 use crate::io;
 use crate::os::unix::io::{FromRawFd, RawFd, AsRawFd, IntoRawFd};
 use crate::process;
@@ -103,6 +104,45 @@ pub trait CommandExt {
     /// cross-platform `spawn` instead.
     #[stable(feature = "process_exec2", since = "1.9.0")]
     fn exec(&mut self) -> io::Error;
+
+    /// Sets the first process argument, `argv[0]`, to something other than the
+    /// default executable path.
+    #[unstable(feature = "process_set_argv0", reason = "recently added", issue = "0")]
+    fn arg0<S>(&mut self, arg: S) -> &mut process::Command
+    where
+        S: AsRef<OsStr>;
+
+    /// Sets the process group ID of the child process. This translates to a
+    /// `setpgid` call in the child process, with `0` creating a new process
+    /// group whose ID is the PID of the child.
+    ///
+    /// This can't be combined with [`setsid`], as a session leader can't join
+    /// another process group. Spawning the child fails with an error of kind
+    /// [`InvalidInput`] if both are set.
+    ///
+    /// [`setsid`]: #tymethod.setsid
+    /// [`InvalidInput`]: ../../../../std/io/enum.ErrorKind.html#variant.InvalidInput
+    #[unstable(feature = "process_set_process_group", reason = "recently added", issue = "0")]
+    fn process_group(&mut self, pgroup: i32) -> &mut process::Command;
+
+    /// Sets whether the child process runs in a new session. This translates
+    /// to a `setsid` call in the child process, which also puts the child in
+    /// a new process group.
+    ///
+    /// This can't be combined with [`process_group`], and spawning the child
+    /// fails with an error of kind [`InvalidInput`] if both are set.
+    ///
+    /// [`process_group`]: #tymethod.process_group
+    /// [`InvalidInput`]: ../../../../std/io/enum.ErrorKind.html#variant.InvalidInput
+    #[unstable(feature = "process_setsid", reason = "recently added", issue = "0")]
+    fn setsid(&mut self, setsid: bool) -> &mut process::Command;
+
+    /// Sets the supplementary group IDs of the child process. This translates
+    /// to a `setgroups` call in the child process, which usually requires the
+    /// parent to be privileged. The groups are kept when the user ID is
+    /// changed with `uid`.
+    #[unstable(feature = "setgroups", reason = "recently added", issue = "0")]
+    fn groups(&mut self, groups: &[u32]) -> &mut process::Command;
 }
 
 #[stable(feature = "rust1", since = "1.0.0")]
@@ -127,6 +167,29 @@ impl CommandExt for process::Command {
     fn exec(&mut self) -> io::Error {
         self.as_inner_mut().exec(sys::process::Stdio::Inherit)
     }
+
+    fn arg0<S>(&mut self, arg: S) -> &mut process::Command
+    where
+        S: AsRef<OsStr>,
+    {
+        self.as_inner_mut().set_arg_0(arg.as_ref());
+        self
+    }
+
+    fn process_group(&mut self, pgroup: i32) -> &mut process::Command {
+        self.as_inner_mut().pgroup(pgroup);
+        self
+    }
+
+    fn setsid(&mut self, setsid: bool) -> &mut process::Command {
+        self.as_inner_mut().setsid(setsid);
+        self
+    }
+
+    fn groups(&mut self, groups: &[u32]) -> &mut process::Command {
+        self.as_inner_mut().groups(groups);
+        self
+    }
 }
 
 /// Unix-specific extensions to [`process::ExitStatus`].
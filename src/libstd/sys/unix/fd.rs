@@ -1,7 +1,7 @@
 #![unstable(reason = "not public", issue = "0", feature = "fd")]
 
 use crate::cmp;
-use crate::io::{self, Read, Initializer, IoSlice, IoSliceMut};
+use crate::io::{self, Read, Initializer, IoSlice, IoSliceMut, ReadBuf};
 use crate::mem;
 use crate::sync::atomic::{AtomicBool, Ordering};
 use crate::sys::cvt;
@@ -53,6 +53,22 @@ impl FileDesc {
         Ok(ret as usize)
     }
 
+    pub fn read_buf(&self, buf: &mut ReadBuf<'_>) -> io::Result<()> {
+        let ret = cvt(unsafe {
+            let unfilled = buf.unfilled_mut();
+            libc::read(self.fd,
+                       unfilled.as_mut_ptr() as *mut c_void,
+                       cmp::min(unfilled.len(), max_len()))
+        })?;
+
+        // `ret` bytes were written to the unfilled part of `buf`.
+        unsafe {
+            buf.assume_init(ret as usize);
+        }
+        buf.add_filled(ret as usize);
+        Ok(())
+    }
+
     pub fn read_vectored(&self, bufs: &mut [IoSliceMut<'_>]) -> io::Result<usize> {
         let ret = cvt(unsafe {
             libc::readv(self.fd,
@@ -282,6 +298,10 @@ impl<'a> Read for &'a FileDesc {
         (**self).read(buf)
     }
 
+    fn read_buf(&mut self, buf: &mut ReadBuf<'_>) -> io::Result<()> {
+        (**self).read_buf(buf)
+    }
+
     #[inline]
     unsafe fn initializer(&self) -> Initializer {
         Initializer::nop()
@@ -2,7 +2,7 @@ use crate::os::unix::prelude::*;
 
 use crate::ffi::{CString, CStr, OsString, OsStr};
 use crate::fmt;
-use crate::io::{self, Error, ErrorKind, SeekFrom, IoSlice, IoSliceMut};
+use crate::io::{self, Error, ErrorKind, SeekFrom, IoSlice, IoSliceMut, ReadBuf};
 use crate::mem;
 use crate::path::{Path, PathBuf};
 use crate::ptr;
@@ -16,7 +16,7 @@ use libc::{c_int, mode_t};
 
 #[cfg(any(target_os = "linux", target_os = "emscripten", target_os = "l4re"))]
 use libc::{stat64, fstat64, lstat64, off64_t, ftruncate64, lseek64, dirent64, readdir64_r, open64};
-#[cfg(any(target_os = "linux", target_os = "emscripten"))]
+#[cfg(any(target_os = "linux", target_os = "emscripten", target_os = "l4re"))]
 use libc::fstatat64;
 #[cfg(any(target_os = "linux", target_os = "emscripten", target_os = "android"))]
 use libc::dirfd;
@@ -29,6 +29,17 @@ use libc::{stat as stat64, fstat as fstat64, fstatat as fstatat64, lstat as lsta
               target_os = "android")))]
 use libc::{stat as stat64, fstat as fstat64, lstat as lstat64, off_t as off64_t,
            ftruncate as ftruncate64, lseek as lseek64, dirent as dirent64, open as open64};
+#[cfg(not(any(target_os = "linux",
+              target_os = "emscripten",
+              target_os = "l4re",
+              target_os = "android",
+              target_os = "macos",
+              target_os = "ios")))]
+use libc::fstatat as fstatat64;
+#[cfg(not(any(target_os = "macos", target_os = "ios")))]
+use libc::{fdopendir, mkdirat, openat, renameat, unlinkat};
+#[cfg(any(target_os = "macos", target_os = "ios"))]
+use self::at::{fdopendir, fstatat64, mkdirat, openat, renameat, unlinkat};
 #[cfg(not(any(target_os = "linux",
               target_os = "emscripten",
               target_os = "solaris",
@@ -36,7 +47,6 @@ use libc::{stat as stat64, fstat as fstat64, lstat as lstat64, off_t as off64_t,
               target_os = "fuchsia")))]
 use libc::{readdir_r as readdir64_r};
 
-pub use crate::sys_common::fs::remove_dir_all;
 
 pub struct File(FileDesc);
 
@@ -47,7 +57,7 @@ pub struct FileAttr {
 
 // all DirEntry's will have a reference to this struct
 struct InnerReadDir {
-    dirp: Dir,
+    dirp: DirStream,
     root: PathBuf,
 }
 
@@ -57,10 +67,18 @@ pub struct ReadDir {
     end_of_stream: bool,
 }
 
-struct Dir(*mut libc::DIR);
+struct DirStream(*mut libc::DIR);
 
-unsafe impl Send for Dir {}
-unsafe impl Sync for Dir {}
+unsafe impl Send for DirStream {}
+unsafe impl Sync for DirStream {}
+
+// An open directory, which the `*at` system calls resolve paths relative to.
+// The path it was opened with is only kept around to build the paths of
+// directory entries and for debugging.
+pub struct Dir {
+    fd: FileDesc,
+    root: PathBuf,
+}
 
 pub struct DirEntry {
     entry: dirent64,
@@ -287,7 +305,7 @@ impl Iterator for ReadDir {
     }
 }
 
-impl Drop for Dir {
+impl Drop for DirStream {
     fn drop(&mut self) {
         let r = unsafe { libc::closedir(self.0) };
         debug_assert_eq!(r, 0);
@@ -416,6 +434,13 @@ impl OpenOptions {
     pub fn custom_flags(&mut self, flags: i32) { self.custom_flags = flags; }
     pub fn mode(&mut self, mode: u32) { self.mode = mode as mode_t; }
 
+    fn get_flags(&self) -> io::Result<c_int> {
+        Ok(libc::O_CLOEXEC |
+           self.get_access_mode()? |
+           self.get_creation_mode()? |
+           (self.custom_flags as c_int & !libc::O_ACCMODE))
+    }
+
     fn get_access_mode(&self) -> io::Result<c_int> {
         match (self.read, self.write, self.append) {
             (true,  false, false) => Ok(libc::O_RDONLY),
@@ -457,10 +482,7 @@ impl File {
     }
 
     pub fn open_c(path: &CStr, opts: &OpenOptions) -> io::Result<File> {
-        let flags = libc::O_CLOEXEC |
-                    opts.get_access_mode()? |
-                    opts.get_creation_mode()? |
-                    (opts.custom_flags as c_int & !libc::O_ACCMODE);
+        let flags = opts.get_flags()?;
         let fd = cvt_r(|| unsafe {
             open64(path.as_ptr(), flags, opts.mode as c_int)
         })?;
@@ -563,6 +585,10 @@ impl File {
         self.0.read(buf)
     }
 
+    pub fn read_buf(&self, buf: &mut ReadBuf<'_>) -> io::Result<()> {
+        self.0.read_buf(buf)
+    }
+
     pub fn read_vectored(&self, bufs: &mut [IoSliceMut<'_>]) -> io::Result<usize> {
         self.0.read_vectored(bufs)
     }
@@ -599,6 +625,42 @@ impl File {
         Ok(n as u64)
     }
 
+    pub fn lock_shared(&self) -> io::Result<()> {
+        self.flock(libc::LOCK_SH).map(drop)
+    }
+
+    pub fn lock_exclusive(&self) -> io::Result<()> {
+        self.flock(libc::LOCK_EX).map(drop)
+    }
+
+    pub fn try_lock_shared(&self) -> io::Result<bool> {
+        self.flock(libc::LOCK_SH | libc::LOCK_NB)
+    }
+
+    pub fn try_lock_exclusive(&self) -> io::Result<bool> {
+        self.flock(libc::LOCK_EX | libc::LOCK_NB)
+    }
+
+    pub fn unlock(&self) -> io::Result<()> {
+        self.flock(libc::LOCK_UN).map(drop)
+    }
+
+    // Returns whether the lock was acquired, which can only fail with
+    // `LOCK_NB` as the other operations block instead.
+    #[cfg(not(target_os = "solaris"))]
+    fn flock(&self, operation: c_int) -> io::Result<bool> {
+        match cvt_r(|| unsafe { libc::flock(self.0.raw(), operation) }) {
+            Ok(_) => Ok(true),
+            Err(ref err) if err.raw_os_error() == Some(libc::EWOULDBLOCK) => Ok(false),
+            Err(err) => Err(err),
+        }
+    }
+
+    #[cfg(target_os = "solaris")]
+    fn flock(&self, _operation: c_int) -> io::Result<bool> {
+        Err(io::Error::new(ErrorKind::Other, "file locking is not supported on this platform"))
+    }
+
     pub fn duplicate(&self) -> io::Result<File> {
         self.0.duplicate().map(File)
     }
@@ -607,6 +669,51 @@ impl File {
 
     pub fn into_fd(self) -> FileDesc { self.0 }
 
+    #[cfg(target_os = "linux")]
+    pub fn allocate(&self, offset: u64, len: u64) -> io::Result<()> {
+        self.fallocate(0, offset, len)
+    }
+
+    #[cfg(target_os = "linux")]
+    pub fn punch_hole(&self, offset: u64, len: u64) -> io::Result<()> {
+        // Not in the `libc` version in use yet.
+        const FALLOC_FL_KEEP_SIZE: c_int = 0x01;
+        const FALLOC_FL_PUNCH_HOLE: c_int = 0x02;
+
+        // Punching a hole is only supported together with `KEEP_SIZE`, the
+        // file is never shrunk.
+        self.fallocate(FALLOC_FL_PUNCH_HOLE | FALLOC_FL_KEEP_SIZE, offset, len)
+    }
+
+    #[cfg(target_os = "linux")]
+    fn fallocate(&self, mode: c_int, offset: u64, len: u64) -> io::Result<()> {
+        use crate::convert::TryFrom;
+
+        let too_large = |_| io::Error::new(ErrorKind::InvalidInput, "file range is too large");
+        let offset = libc::off_t::try_from(offset).map_err(too_large)?;
+        let len = libc::off_t::try_from(len).map_err(too_large)?;
+        cvt_r(|| unsafe { libc::fallocate(self.0.raw(), mode, offset, len) })?;
+        Ok(())
+    }
+
+    // Makes this file share the data of `src`, which must be on the same
+    // filesystem, as if it had been copied.
+    #[cfg(target_os = "linux")]
+    pub fn ficlone(&self, src: &File) -> io::Result<()> {
+        // `_IOW(0x94, 9, int)`, which the `libc` version in use doesn't have.
+        #[cfg(any(target_arch = "mips", target_arch = "mips64",
+                  target_arch = "powerpc", target_arch = "powerpc64",
+                  target_arch = "sparc", target_arch = "sparc64"))]
+        const FICLONE: libc::c_ulong = 0x8004_9409;
+        #[cfg(not(any(target_arch = "mips", target_arch = "mips64",
+                      target_arch = "powerpc", target_arch = "powerpc64",
+                      target_arch = "sparc", target_arch = "sparc64")))]
+        const FICLONE: libc::c_ulong = 0x4004_9409;
+
+        cvt(unsafe { libc::ioctl(self.0.raw(), FICLONE as _, src.0.raw()) })?;
+        Ok(())
+    }
+
     pub fn set_permissions(&self, perm: FilePermissions) -> io::Result<()> {
         cvt_r(|| unsafe { libc::fchmod(self.0.raw(), perm.mode) })?;
         Ok(())
@@ -629,6 +736,199 @@ impl DirBuilder {
     }
 }
 
+impl Dir {
+    pub fn open(path: &Path) -> io::Result<Dir> {
+        Dir::open_c(libc::AT_FDCWD, &cstr(path)?, 0, path.to_path_buf())
+    }
+
+    // Opens a directory without following a symlink in the last component of
+    // `path`, which makes opening it fail instead.
+    fn open_nofollow(path: &Path) -> io::Result<Dir> {
+        Dir::open_c(libc::AT_FDCWD, &cstr(path)?, libc::O_NOFOLLOW, path.to_path_buf())
+    }
+
+    fn open_c(dirfd: c_int, path: &CStr, flags: c_int, root: PathBuf) -> io::Result<Dir> {
+        let flags = libc::O_RDONLY | libc::O_DIRECTORY | libc::O_CLOEXEC | flags;
+        let fd = cvt_r(|| unsafe { openat(dirfd, path.as_ptr(), flags, 0) })?;
+        Ok(Dir { fd: FileDesc::new(fd), root })
+    }
+
+    pub fn open_dir_at(&self, path: &Path) -> io::Result<Dir> {
+        Dir::open_c(self.fd.raw(), &cstr(path)?, 0, self.root.join(path))
+    }
+
+    pub fn open_file_at(&self, path: &Path, opts: &OpenOptions) -> io::Result<File> {
+        let path = cstr(path)?;
+        let flags = opts.get_flags()?;
+        let fd = cvt_r(|| unsafe {
+            openat(self.fd.raw(), path.as_ptr(), flags, opts.mode as c_int)
+        })?;
+        Ok(File(FileDesc::new(fd)))
+    }
+
+    pub fn create_dir_at(&self, path: &Path) -> io::Result<()> {
+        let path = cstr(path)?;
+        cvt(unsafe { mkdirat(self.fd.raw(), path.as_ptr(), 0o777) })?;
+        Ok(())
+    }
+
+    pub fn remove_file_at(&self, path: &Path) -> io::Result<()> {
+        unlink_at(self.fd.raw(), &cstr(path)?, 0)
+    }
+
+    pub fn remove_dir_at(&self, path: &Path) -> io::Result<()> {
+        unlink_at(self.fd.raw(), &cstr(path)?, libc::AT_REMOVEDIR)
+    }
+
+    pub fn rename_at(&self, from: &Path, to_dir: &Dir, to: &Path) -> io::Result<()> {
+        let from = cstr(from)?;
+        let to = cstr(to)?;
+        cvt(unsafe {
+            renameat(self.fd.raw(), from.as_ptr(), to_dir.fd.raw(), to.as_ptr())
+        })?;
+        Ok(())
+    }
+
+    pub fn symlink_metadata_at(&self, path: &Path) -> io::Result<FileAttr> {
+        let path = cstr(path)?;
+        let mut stat: stat64 = unsafe { mem::zeroed() };
+        cvt(unsafe {
+            fstatat64(self.fd.raw(), path.as_ptr(), &mut stat, libc::AT_SYMLINK_NOFOLLOW)
+        })?;
+        Ok(FileAttr { stat })
+    }
+
+    pub fn read_dir(&self) -> io::Result<ReadDir> {
+        // `fdopendir` takes ownership of the descriptor it is given and reads
+        // from its file offset, so give it a descriptor of its own.
+        let dir = Dir::open_c(self.fd.raw(), &cstr(Path::new("."))?, 0, self.root.clone())?;
+        dir.into_read_dir()
+    }
+
+    // Turns a freshly opened directory into a stream of its entries, which
+    // takes over the descriptor. The descriptor stays valid for as long as
+    // the stream is open.
+    fn into_read_dir(self) -> io::Result<ReadDir> {
+        let ptr = unsafe { fdopendir(self.fd.raw()) };
+        if ptr.is_null() {
+            return Err(Error::last_os_error())
+        }
+        self.fd.into_raw();
+        let inner = InnerReadDir { dirp: DirStream(ptr), root: self.root };
+        Ok(ReadDir {
+            inner: Arc::new(inner),
+            end_of_stream: false,
+        })
+    }
+
+    pub fn fd(&self) -> &FileDesc { &self.fd }
+}
+
+impl fmt::Debug for Dir {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Dir")
+            .field("fd", &self.fd.raw())
+            .field("path", &self.root)
+            .finish()
+    }
+}
+
+fn unlink_at(dirfd: c_int, path: &CStr, flags: c_int) -> io::Result<()> {
+    cvt(unsafe { unlinkat(dirfd, path.as_ptr(), flags) })?;
+    Ok(())
+}
+
+// macOS only has `openat` and the other `*at` functions since 10.10, while
+// older releases are still supported, so they're looked up at runtime there
+// and fail with `ENOSYS` if they're missing.
+#[cfg(any(target_os = "macos", target_os = "ios"))]
+mod at {
+    use crate::ptr;
+    use crate::sys::os;
+    use crate::sys::weak::Weak;
+    use libc::{c_char, c_int, stat, DIR};
+
+    // `openat` is variadic, which the `weak!` macro can't express.
+    static OPENAT: Weak<unsafe extern "C" fn(c_int, *const c_char, c_int, ...) -> c_int> =
+        Weak::new("openat\0");
+    static FDOPENDIR: Weak<unsafe extern "C" fn(c_int) -> *mut DIR> = Weak::new("fdopendir\0");
+    static FSTATAT: Weak<unsafe extern "C" fn(c_int, *const c_char, *mut stat, c_int) -> c_int> =
+        Weak::new("fstatat\0");
+    static MKDIRAT: Weak<unsafe extern "C" fn(c_int, *const c_char, libc::mode_t) -> c_int> =
+        Weak::new("mkdirat\0");
+    static RENAMEAT: Weak<
+        unsafe extern "C" fn(c_int, *const c_char, c_int, *const c_char) -> c_int,
+    > = Weak::new("renameat\0");
+    static UNLINKAT: Weak<unsafe extern "C" fn(c_int, *const c_char, c_int) -> c_int> =
+        Weak::new("unlinkat\0");
+
+    /// Whether the `*at` functions exist on this system. They were all added
+    /// in the same release.
+    pub fn available() -> bool {
+        OPENAT.get().is_some()
+    }
+
+    fn enosys() -> c_int {
+        os::set_errno(libc::ENOSYS);
+        -1
+    }
+
+    pub unsafe fn openat(dirfd: c_int, path: *const c_char, flags: c_int, mode: c_int) -> c_int {
+        match OPENAT.get() {
+            Some(f) => f(dirfd, path, flags, mode),
+            None => enosys(),
+        }
+    }
+
+    pub unsafe fn fdopendir(fd: c_int) -> *mut DIR {
+        match FDOPENDIR.get() {
+            Some(f) => f(fd),
+            None => {
+                enosys();
+                ptr::null_mut()
+            }
+        }
+    }
+
+    pub unsafe fn fstatat64(
+        dirfd: c_int,
+        path: *const c_char,
+        buf: *mut stat,
+        flags: c_int,
+    ) -> c_int {
+        match FSTATAT.get() {
+            Some(f) => f(dirfd, path, buf, flags),
+            None => enosys(),
+        }
+    }
+
+    pub unsafe fn mkdirat(dirfd: c_int, path: *const c_char, mode: libc::mode_t) -> c_int {
+        match MKDIRAT.get() {
+            Some(f) => f(dirfd, path, mode),
+            None => enosys(),
+        }
+    }
+
+    pub unsafe fn renameat(
+        from_dirfd: c_int,
+        from: *const c_char,
+        to_dirfd: c_int,
+        to: *const c_char,
+    ) -> c_int {
+        match RENAMEAT.get() {
+            Some(f) => f(from_dirfd, from, to_dirfd, to),
+            None => enosys(),
+        }
+    }
+
+    pub unsafe fn unlinkat(dirfd: c_int, path: *const c_char, flags: c_int) -> c_int {
+        match UNLINKAT.get() {
+            Some(f) => f(dirfd, path, flags),
+            None => enosys(),
+        }
+    }
+}
+
 fn cstr(path: &Path) -> io::Result<CString> {
     Ok(CString::new(path.as_os_str().as_bytes())?)
 }
@@ -713,7 +1013,7 @@ pub fn readdir(p: &Path) -> io::Result<ReadDir> {
         if ptr.is_null() {
             Err(Error::last_os_error())
         } else {
-            let inner = InnerReadDir { dirp: Dir(ptr), root };
+            let inner = InnerReadDir { dirp: DirStream(ptr), root };
             Ok(ReadDir{
                 inner: Arc::new(inner),
                 end_of_stream: false,
@@ -747,6 +1047,51 @@ pub fn rmdir(p: &Path) -> io::Result<()> {
     Ok(())
 }
 
+pub fn remove_dir_all(path: &Path) -> io::Result<()> {
+    // Without the `*at` functions, the tree can only be removed by path.
+    #[cfg(any(target_os = "macos", target_os = "ios"))]
+    {
+        if !at::available() {
+            return crate::sys_common::fs::remove_dir_all(path);
+        }
+    }
+
+    let filetype = lstat(path)?.file_type();
+    if filetype.is_symlink() {
+        unlink(path)
+    } else {
+        // The directory is opened without following symlinks, so it can't be
+        // swapped for a symlink after the check above.
+        remove_dir_all_recursive(Dir::open_nofollow(path)?)?;
+        rmdir(path)
+    }
+}
+
+// Everything below the top-level directory is removed relative to the handle
+// of its parent, and subdirectories are opened without following symlinks.
+// If an entry is replaced after its type has been read, this only makes the
+// removal fail instead of escaping the tree, as `unlinkat` does not remove
+// directories and opening a symlink fails.
+//
+// The entries are read through the descriptor of `dir` itself rather than a
+// duplicate of it, so that each level of the tree only holds one descriptor
+// open while its subdirectories are removed.
+fn remove_dir_all_recursive(dir: Dir) -> io::Result<()> {
+    let fd = dir.fd.raw();
+    for child in dir.into_read_dir()? {
+        let child = child?;
+        let name = CString::new(child.name_bytes())?;
+        if child.file_type()?.is_dir() {
+            let child_dir = Dir::open_c(fd, &name, libc::O_NOFOLLOW, child.path())?;
+            remove_dir_all_recursive(child_dir)?;
+            unlink_at(fd, &name, libc::AT_REMOVEDIR)?;
+        } else {
+            unlink_at(fd, &name, 0)?;
+        }
+    }
+    Ok(())
+}
+
 pub fn readlink(p: &Path) -> io::Result<PathBuf> {
     let c_path = cstr(p)?;
     let p = c_path.as_ptr();
@@ -871,89 +1216,12 @@ pub fn copy(from: &Path, to: &Path) -> io::Result<u64> {
 
 #[cfg(any(target_os = "linux", target_os = "android"))]
 pub fn copy(from: &Path, to: &Path) -> io::Result<u64> {
-    use crate::cmp;
-    use crate::sync::atomic::{AtomicBool, Ordering};
-
-    // Kernel prior to 4.5 don't have copy_file_range
-    // We store the availability in a global to avoid unnecessary syscalls
-    static HAS_COPY_FILE_RANGE: AtomicBool = AtomicBool::new(true);
-
-    unsafe fn copy_file_range(
-        fd_in: libc::c_int,
-        off_in: *mut libc::loff_t,
-        fd_out: libc::c_int,
-        off_out: *mut libc::loff_t,
-        len: libc::size_t,
-        flags: libc::c_uint,
-    ) -> libc::c_long {
-        libc::syscall(
-            libc::SYS_copy_file_range,
-            fd_in,
-            off_in,
-            fd_out,
-            off_out,
-            len,
-            flags,
-        )
-    }
-
     let (mut reader, reader_metadata) = open_from(from)?;
-    let len = reader_metadata.len();
     let (mut writer, _) = open_to_and_set_permissions(to, reader_metadata)?;
 
-    let has_copy_file_range = HAS_COPY_FILE_RANGE.load(Ordering::Relaxed);
-    let mut written = 0u64;
-    while written < len {
-        let copy_result = if has_copy_file_range {
-            let bytes_to_copy = cmp::min(len - written, usize::max_value() as u64) as usize;
-            let copy_result = unsafe {
-                // We actually don't have to adjust the offsets,
-                // because copy_file_range adjusts the file offset automatically
-                cvt(copy_file_range(
-                    reader.as_raw_fd(),
-                    ptr::null_mut(),
-                    writer.as_raw_fd(),
-                    ptr::null_mut(),
-                    bytes_to_copy,
-                    0,
-                ))
-            };
-            if let Err(ref copy_err) = copy_result {
-                match copy_err.raw_os_error() {
-                    Some(libc::ENOSYS) | Some(libc::EPERM) => {
-                        HAS_COPY_FILE_RANGE.store(false, Ordering::Relaxed);
-                    }
-                    _ => {}
-                }
-            }
-            copy_result
-        } else {
-            Err(io::Error::from_raw_os_error(libc::ENOSYS))
-        };
-        match copy_result {
-            Ok(ret) => written += ret as u64,
-            Err(err) => {
-                match err.raw_os_error() {
-                    Some(os_err)
-                    if os_err == libc::ENOSYS
-                        || os_err == libc::EXDEV
-                        || os_err == libc::EINVAL
-                        || os_err == libc::EPERM =>
-                        {
-                            // Try fallback io::copy if either:
-                            // - Kernel version is < 4.5 (ENOSYS)
-                            // - Files are mounted on different fs (EXDEV)
-                            // - copy_file_range is disallowed, for example by seccomp (EPERM)
-                            // - copy_file_range cannot be used with pipes or device nodes (EINVAL)
-                            assert_eq!(written, 0);
-                            return io::copy(&mut reader, &mut writer);
-                        }
-                    _ => return Err(err),
-                }
-            }
-        }
-    }
-    Ok(written)
+    // `io::copy` is specialized for files, and copies them in the kernel with
+    // `copy_file_range` or `sendfile` where possible.
+    io::copy(&mut reader, &mut writer)
 }
 
 #[cfg(any(target_os = "macos", target_os = "ios"))]
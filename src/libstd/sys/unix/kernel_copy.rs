@@ -0,0 +1,371 @@
+//! Copying data between file descriptors in the kernel.
+//!
+//! `io::copy` is specialized for readers and writers which are backed by a
+//! file descriptor, to avoid copying the data through a userspace buffer:
+//!
+//! * `copy_file_range` is used between two regular files. It may also share
+//!   the underlying blocks on filesystems which support reflinks, and works
+//!   across filesystems since Linux 5.3.
+//! * `sendfile` is used when the source is a regular file or a block device,
+//!   which includes copying a file into a socket.
+//! * `splice` is used when either side is a pipe.
+//!
+//! Each of these falls back to the next one, and finally to the generic
+//! read/write loop, when the kernel rejects the combination of file
+//! descriptors, or doesn't support the system call at all. All of them use
+//! and update the file offsets, so bytes already copied by one method are not
+//! copied again by the next.
+
+use crate::fs::{File, Metadata};
+use crate::io::{self, Read, Write};
+use crate::mem::ManuallyDrop;
+use crate::net::TcpStream;
+use crate::os::unix::fs::FileTypeExt;
+use crate::os::unix::io::{AsRawFd, FromRawFd, RawFd};
+use crate::os::unix::net::UnixStream;
+use crate::process::{ChildStderr, ChildStdin, ChildStdout};
+use crate::ptr;
+use crate::sync::atomic::{AtomicBool, Ordering};
+use crate::sys::cvt;
+
+pub fn copy_spec<R: Read + ?Sized, W: Write + ?Sized>(read: &mut R, write: &mut W)
+                                                      -> io::Result<u64> {
+    SpecCopy::copy(Copier { read, write })
+}
+
+/// What is known about a file descriptor, to decide which system calls can
+/// be used to copy from or to it.
+enum FdMeta {
+    Metadata(Metadata),
+    Socket,
+    Pipe,
+    /// The type of the file descriptor could not be determined.
+    NoneObtained,
+}
+
+impl FdMeta {
+    fn maybe_fifo(&self) -> bool {
+        match self {
+            FdMeta::Metadata(meta) => meta.file_type().is_fifo(),
+            FdMeta::Socket => false,
+            FdMeta::Pipe => true,
+            FdMeta::NoneObtained => true,
+        }
+    }
+
+    fn potential_sendfile_source(&self) -> bool {
+        match self {
+            FdMeta::Metadata(meta) => {
+                meta.file_type().is_file() || meta.file_type().is_block_device()
+            }
+            _ => false,
+        }
+    }
+
+    fn copy_file_range_candidate(&self) -> bool {
+        match self {
+            FdMeta::Metadata(meta) => meta.is_file(),
+            _ => false,
+        }
+    }
+}
+
+fn fd_to_meta<T: AsRawFd>(fd: &T) -> FdMeta {
+    // The file descriptor is only borrowed, so it must not be closed.
+    let file = ManuallyDrop::new(unsafe { File::from_raw_fd(fd.as_raw_fd()) });
+    match file.metadata() {
+        Ok(meta) => FdMeta::Metadata(meta),
+        Err(_) => FdMeta::NoneObtained,
+    }
+}
+
+/// Readers whose file descriptor can be copied from directly.
+trait CopyRead: Read {
+    fn properties(&self) -> (FdMeta, RawFd);
+}
+
+/// Writers whose file descriptor can be copied to directly.
+trait CopyWrite: Write {
+    fn properties(&self) -> (FdMeta, RawFd);
+}
+
+struct Copier<'a, 'b, R: Read + ?Sized, W: Write + ?Sized> {
+    read: &'a mut R,
+    write: &'b mut W,
+}
+
+trait SpecCopy {
+    fn copy(self) -> io::Result<u64>;
+}
+
+impl<R: Read + ?Sized, W: Write + ?Sized> SpecCopy for Copier<'_, '_, R, W> {
+    default fn copy(self) -> io::Result<u64> {
+        io::generic_copy(self.read, self.write)
+    }
+}
+
+impl<R: CopyRead, W: CopyWrite> SpecCopy for Copier<'_, '_, R, W> {
+    fn copy(self) -> io::Result<u64> {
+        let (input_meta, readfd) = self.read.properties();
+        let (output_meta, writefd) = self.write.properties();
+
+        let mut written = 0;
+
+        if input_meta.copy_file_range_candidate() && output_meta.copy_file_range_candidate() {
+            match copy_regular_files(readfd, writefd) {
+                CopyResult::Ended(bytes) => return Ok(bytes),
+                CopyResult::Error(err) => return Err(err),
+                CopyResult::Fallback(bytes) => written += bytes,
+            }
+        }
+
+        if input_meta.potential_sendfile_source() {
+            match sendfile_splice(SpliceMode::Sendfile, readfd, writefd) {
+                CopyResult::Ended(bytes) => return Ok(written + bytes),
+                CopyResult::Error(err) => return Err(err),
+                CopyResult::Fallback(bytes) => written += bytes,
+            }
+        }
+
+        if input_meta.maybe_fifo() || output_meta.maybe_fifo() {
+            match sendfile_splice(SpliceMode::Splice, readfd, writefd) {
+                CopyResult::Ended(bytes) => return Ok(written + bytes),
+                CopyResult::Error(err) => return Err(err),
+                CopyResult::Fallback(bytes) => written += bytes,
+            }
+        }
+
+        Ok(written + io::generic_copy(self.read, self.write)?)
+    }
+}
+
+impl CopyRead for File {
+    fn properties(&self) -> (FdMeta, RawFd) {
+        (fd_to_meta(self), self.as_raw_fd())
+    }
+}
+
+impl CopyRead for &File {
+    fn properties(&self) -> (FdMeta, RawFd) {
+        (fd_to_meta(*self), self.as_raw_fd())
+    }
+}
+
+impl CopyWrite for File {
+    fn properties(&self) -> (FdMeta, RawFd) {
+        (fd_to_meta(self), self.as_raw_fd())
+    }
+}
+
+impl CopyWrite for &File {
+    fn properties(&self) -> (FdMeta, RawFd) {
+        (fd_to_meta(*self), self.as_raw_fd())
+    }
+}
+
+impl CopyRead for TcpStream {
+    fn properties(&self) -> (FdMeta, RawFd) {
+        (FdMeta::Socket, self.as_raw_fd())
+    }
+}
+
+impl CopyRead for &TcpStream {
+    fn properties(&self) -> (FdMeta, RawFd) {
+        (FdMeta::Socket, self.as_raw_fd())
+    }
+}
+
+impl CopyWrite for TcpStream {
+    fn properties(&self) -> (FdMeta, RawFd) {
+        (FdMeta::Socket, self.as_raw_fd())
+    }
+}
+
+impl CopyWrite for &TcpStream {
+    fn properties(&self) -> (FdMeta, RawFd) {
+        (FdMeta::Socket, self.as_raw_fd())
+    }
+}
+
+impl CopyRead for UnixStream {
+    fn properties(&self) -> (FdMeta, RawFd) {
+        (FdMeta::Socket, self.as_raw_fd())
+    }
+}
+
+impl CopyRead for &UnixStream {
+    fn properties(&self) -> (FdMeta, RawFd) {
+        (FdMeta::Socket, self.as_raw_fd())
+    }
+}
+
+impl CopyWrite for UnixStream {
+    fn properties(&self) -> (FdMeta, RawFd) {
+        (FdMeta::Socket, self.as_raw_fd())
+    }
+}
+
+impl CopyWrite for &UnixStream {
+    fn properties(&self) -> (FdMeta, RawFd) {
+        (FdMeta::Socket, self.as_raw_fd())
+    }
+}
+
+impl CopyWrite for ChildStdin {
+    fn properties(&self) -> (FdMeta, RawFd) {
+        (FdMeta::Pipe, self.as_raw_fd())
+    }
+}
+
+impl CopyRead for ChildStdout {
+    fn properties(&self) -> (FdMeta, RawFd) {
+        (FdMeta::Pipe, self.as_raw_fd())
+    }
+}
+
+impl CopyRead for ChildStderr {
+    fn properties(&self) -> (FdMeta, RawFd) {
+        (FdMeta::Pipe, self.as_raw_fd())
+    }
+}
+
+enum CopyResult {
+    /// The whole input was copied, up to end of file.
+    Ended(u64),
+    /// Copying failed.
+    Error(io::Error),
+    /// The system call can't be used for these file descriptors, any other
+    /// method can continue after the given number of bytes.
+    Fallback(u64),
+}
+
+// The maximum number of bytes `sendfile` and `splice` transfer at once, and
+// the chunk size for `copy_file_range` so that a single call doesn't block
+// for too long.
+const MAX_CHUNK: usize = 0x7fff_f000;
+
+// Kernels prior to 4.5 don't have copy_file_range
+// We store the availability in a global to avoid unnecessary syscalls
+static HAS_COPY_FILE_RANGE: AtomicBool = AtomicBool::new(true);
+
+unsafe fn copy_file_range(
+    fd_in: libc::c_int,
+    off_in: *mut libc::loff_t,
+    fd_out: libc::c_int,
+    off_out: *mut libc::loff_t,
+    len: libc::size_t,
+    flags: libc::c_uint,
+) -> libc::c_long {
+    libc::syscall(
+        libc::SYS_copy_file_range,
+        fd_in,
+        off_in,
+        fd_out,
+        off_out,
+        len,
+        flags,
+    )
+}
+
+fn copy_regular_files(reader: RawFd, writer: RawFd) -> CopyResult {
+    if !HAS_COPY_FILE_RANGE.load(Ordering::Relaxed) {
+        return CopyResult::Fallback(0);
+    }
+
+    let mut written = 0u64;
+    loop {
+        // We actually don't have to adjust the offsets,
+        // because copy_file_range adjusts the file offset automatically
+        let result = cvt(unsafe {
+            copy_file_range(reader, ptr::null_mut(), writer, ptr::null_mut(), MAX_CHUNK, 0)
+        });
+        match result {
+            // Files in pseudo filesystems like procfs report a size of zero
+            // and copy_file_range copies nothing from them, so let the
+            // other methods try again instead of treating this as the end.
+            Ok(0) if written == 0 => return CopyResult::Fallback(0),
+            Ok(0) => return CopyResult::Ended(written),
+            Ok(ret) => written += ret as u64,
+            Err(err) => return match err.raw_os_error() {
+                // - Kernel version is < 4.5 (ENOSYS)
+                // - copy_file_range is disallowed, for example by seccomp (EPERM)
+                Some(libc::ENOSYS) | Some(libc::EPERM) => {
+                    HAS_COPY_FILE_RANGE.store(false, Ordering::Relaxed);
+                    CopyResult::Fallback(written)
+                }
+                // - Files are mounted on different fs, before Linux 5.3 (EXDEV)
+                // - The filesystem or file types aren't supported (EINVAL, EOPNOTSUPP)
+                // - The output was opened with O_APPEND (EBADF)
+                // - The output is a running executable (ETXTBSY)
+                // - The file offsets would overflow (EOVERFLOW)
+                Some(libc::EXDEV) | Some(libc::EINVAL) | Some(libc::EOPNOTSUPP) |
+                Some(libc::EBADF) | Some(libc::ETXTBSY) | Some(libc::EOVERFLOW) => {
+                    CopyResult::Fallback(written)
+                }
+                _ => CopyResult::Error(err),
+            },
+        }
+    }
+}
+
+#[derive(PartialEq)]
+enum SpliceMode {
+    Sendfile,
+    Splice,
+}
+
+static HAS_SENDFILE: AtomicBool = AtomicBool::new(true);
+static HAS_SPLICE: AtomicBool = AtomicBool::new(true);
+
+unsafe fn splice(
+    fd_in: libc::c_int,
+    off_in: *mut libc::loff_t,
+    fd_out: libc::c_int,
+    off_out: *mut libc::loff_t,
+    len: libc::size_t,
+    flags: libc::c_uint,
+) -> libc::c_long {
+    libc::syscall(libc::SYS_splice, fd_in, off_in, fd_out, off_out, len, flags)
+}
+
+/// Copies with `sendfile` or `splice` until the end of the input.
+fn sendfile_splice(mode: SpliceMode, reader: RawFd, writer: RawFd) -> CopyResult {
+    let available = match mode {
+        SpliceMode::Sendfile => &HAS_SENDFILE,
+        SpliceMode::Splice => &HAS_SPLICE,
+    };
+    if !available.load(Ordering::Relaxed) {
+        return CopyResult::Fallback(0);
+    }
+
+    let mut written = 0u64;
+    loop {
+        let result = match mode {
+            SpliceMode::Sendfile => cvt(unsafe {
+                libc::sendfile(writer, reader, ptr::null_mut(), MAX_CHUNK) as libc::c_long
+            }),
+            SpliceMode::Splice => cvt(unsafe {
+                splice(reader, ptr::null_mut(), writer, ptr::null_mut(), MAX_CHUNK, 0)
+            }),
+        };
+        match result {
+            Ok(0) => return CopyResult::Ended(written),
+            Ok(ret) => written += ret as u64,
+            Err(err) => return match err.raw_os_error() {
+                Some(libc::ENOSYS) | Some(libc::EPERM) => {
+                    available.store(false, Ordering::Relaxed);
+                    CopyResult::Fallback(written)
+                }
+                // The file descriptors can't be used with this system call,
+                // for example because neither is a pipe for `splice`.
+                Some(libc::EINVAL) => CopyResult::Fallback(written),
+                // `sendfile` can't go past the maximum file offset of the
+                // input, while reading it with `read` may still work.
+                Some(libc::EOVERFLOW) if mode == SpliceMode::Sendfile => {
+                    CopyResult::Fallback(written)
+                }
+                _ => CopyResult::Error(err),
+            },
+        }
+    }
+}
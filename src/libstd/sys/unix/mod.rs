@@ -34,6 +34,8 @@ pub mod ext;
 pub mod fast_thread_local;
 pub mod fd;
 pub mod fs;
+#[cfg(any(target_os = "linux", target_os = "android"))]
+pub mod kernel_copy;
 pub mod memchr;
 pub mod io;
 pub mod mutex;
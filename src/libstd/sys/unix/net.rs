@@ -1,5 +1,5 @@
 use crate::ffi::CStr;
-use crate::io::{self, IoSlice, IoSliceMut};
+use crate::io::{self, IoSlice, IoSliceMut, ReadBuf};
 use crate::mem;
 use crate::net::{SocketAddr, Shutdown};
 use crate::str;
@@ -240,6 +240,23 @@ impl Socket {
         self.recv_with_flags(buf, 0)
     }
 
+    pub fn read_buf(&self, buf: &mut ReadBuf<'_>) -> io::Result<()> {
+        let ret = cvt(unsafe {
+            let unfilled = buf.unfilled_mut();
+            libc::recv(self.0.raw(),
+                       unfilled.as_mut_ptr() as *mut c_void,
+                       unfilled.len(),
+                       0)
+        })?;
+
+        // `ret` bytes were written to the unfilled part of `buf`.
+        unsafe {
+            buf.assume_init(ret as usize);
+        }
+        buf.add_filled(ret as usize);
+        Ok(())
+    }
+
     pub fn peek(&self, buf: &mut [u8]) -> io::Result<usize> {
         self.recv_with_flags(buf, MSG_PEEK)
     }
@@ -1,5 +1,8 @@
-pub use self::process_common::{Command, ExitStatus, ExitCode, Stdio, StdioPipes};
+pub use self::process_common::{Command, CommandArgs, CommandEnvs, ExitStatus, ExitCode, Stdio,
+                                StdioPipes};
 pub use self::process_inner::Process;
+#[cfg(target_os = "linux")]
+pub use self::pidfd::PidFd;
 
 mod process_common;
 #[cfg(not(target_os = "fuchsia"))]
@@ -10,3 +13,5 @@ mod process_inner;
 mod process_inner;
 #[cfg(target_os = "fuchsia")]
 mod zircon;
+#[cfg(target_os = "linux")]
+mod pidfd;
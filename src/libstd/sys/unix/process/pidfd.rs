@@ -0,0 +1,159 @@
+//! Process file descriptors ("pidfds") on Linux.
+//!
+//! A pidfd refers to one specific process. Unlike its pid, it can't end up
+//! referring to an unrelated process once the original one has exited and
+//! been reaped. pidfds were added in Linux 5.3, older kernels fail all of the
+//! calls below with `ENOSYS`.
+
+use crate::io;
+use crate::mem;
+use crate::ptr;
+use crate::sync::atomic::{AtomicBool, Ordering};
+use crate::sys::fd::FileDesc;
+use crate::sys::process::ExitStatus;
+use crate::sys::{cvt, cvt_r};
+use crate::sys_common::{AsInner, FromInner, IntoInner};
+
+use libc::{c_int, c_long, pid_t};
+
+// The `libc` version in use doesn't know about pidfds yet. The system calls
+// added since Linux 5.1 have the same number on every architecture, apart
+// from the offset the MIPS and x32 ABIs add to all syscall numbers.
+#[cfg(target_arch = "mips")]
+const SYSCALL_BASE: c_long = 4000;
+#[cfg(target_arch = "mips64")]
+const SYSCALL_BASE: c_long = 5000;
+#[cfg(all(target_arch = "x86_64", target_pointer_width = "32"))]
+const SYSCALL_BASE: c_long = 0x4000_0000;
+#[cfg(not(any(target_arch = "mips",
+              target_arch = "mips64",
+              all(target_arch = "x86_64", target_pointer_width = "32"))))]
+const SYSCALL_BASE: c_long = 0;
+
+const SYS_PIDFD_SEND_SIGNAL: c_long = SYSCALL_BASE + 424;
+const SYS_PIDFD_OPEN: c_long = SYSCALL_BASE + 434;
+
+// The `idtype_t` of `waitid` for waiting on a pidfd.
+const P_PIDFD: c_int = 3;
+
+// The `si_code` values of a `SIGCHLD` siginfo.
+const CLD_EXITED: c_int = 1;
+const CLD_KILLED: c_int = 2;
+const CLD_DUMPED: c_int = 3;
+const CLD_TRAPPED: c_int = 4;
+const CLD_STOPPED: c_int = 5;
+const CLD_CONTINUED: c_int = 6;
+
+// Kernels prior to 5.3 don't have pidfds.
+// We store the availability in a global to avoid unnecessary syscalls
+static HAS_PIDFD: AtomicBool = AtomicBool::new(true);
+
+pub struct PidFd(FileDesc);
+
+impl PidFd {
+    /// Opens a pidfd for the process `pid`.
+    ///
+    /// This is only free of races if `pid` is a child of the calling process
+    /// which has not been reaped yet, otherwise the pid may already have been
+    /// reused by the time the pidfd is opened. Note that the kernel reaps
+    /// children by itself as soon as they exit while `SIGCHLD` is ignored.
+    pub fn open(pid: pid_t) -> io::Result<PidFd> {
+        if !HAS_PIDFD.load(Ordering::Relaxed) {
+            return Err(io::Error::from_raw_os_error(libc::ENOSYS));
+        }
+
+        // pidfds are always opened with `O_CLOEXEC` set.
+        match cvt(unsafe { libc::syscall(SYS_PIDFD_OPEN, pid, 0) }) {
+            Ok(fd) => Ok(PidFd(FileDesc::new(fd as c_int))),
+            Err(err) => {
+                if err.raw_os_error() == Some(libc::ENOSYS) {
+                    HAS_PIDFD.store(false, Ordering::Relaxed);
+                }
+                Err(err)
+            }
+        }
+    }
+
+    pub fn kill(&self) -> io::Result<()> {
+        cvt(unsafe {
+            libc::syscall(SYS_PIDFD_SEND_SIGNAL,
+                          self.0.raw(),
+                          libc::SIGKILL,
+                          ptr::null::<libc::siginfo_t>(),
+                          0)
+        }).map(|_| ())
+    }
+
+    pub fn wait(&self) -> io::Result<ExitStatus> {
+        let status = self.waitid(libc::WEXITED)?;
+        Ok(status.expect("waitid without WNOHANG returned no status"))
+    }
+
+    pub fn try_wait(&self) -> io::Result<Option<ExitStatus>> {
+        self.waitid(libc::WEXITED | libc::WNOHANG)
+    }
+
+    fn waitid(&self, options: c_int) -> io::Result<Option<ExitStatus>> {
+        let mut info: libc::siginfo_t = unsafe { mem::zeroed() };
+        cvt_r(|| unsafe {
+            libc::syscall(libc::SYS_waitid,
+                          P_PIDFD,
+                          self.0.raw(),
+                          &mut info as *mut libc::siginfo_t,
+                          options,
+                          ptr::null_mut::<libc::rusage>())
+        })?;
+
+        // With `WNOHANG` the siginfo is left zeroed if the process hasn't
+        // exited yet.
+        let (pid, status) = unsafe { siginfo_pid_status(&info) };
+        if pid == 0 {
+            return Ok(None);
+        }
+
+        // Translate the siginfo back into the status `waitpid` would have
+        // returned, which is what `ExitStatus` wraps.
+        let status = match info.si_code {
+            CLD_EXITED => (status & 0xff) << 8,
+            CLD_KILLED => status,
+            CLD_DUMPED => status | 0x80,
+            CLD_TRAPPED | CLD_STOPPED => ((status & 0xff) << 8) | 0x7f,
+            CLD_CONTINUED => 0xffff,
+            code => {
+                return Err(io::Error::new(
+                    io::ErrorKind::Other,
+                    format!("unexpected si_code {} returned by waitid", code),
+                ))
+            }
+        };
+        Ok(Some(ExitStatus::new(status)))
+    }
+}
+
+unsafe fn siginfo_pid_status(info: &libc::siginfo_t) -> (pid_t, c_int) {
+    #[repr(C)]
+    struct siginfo_t {
+        a: [c_int; 3], // si_signo, si_errno, si_code
+        // The union holding the fields specific to each signal is pointer
+        // aligned.
+        _align: [usize; 0],
+        si_pid: pid_t,
+        si_uid: libc::uid_t,
+        si_status: c_int,
+    }
+
+    let info = &*(info as *const libc::siginfo_t as *const siginfo_t);
+    (info.si_pid, info.si_status)
+}
+
+impl AsInner<FileDesc> for PidFd {
+    fn as_inner(&self) -> &FileDesc { &self.0 }
+}
+
+impl FromInner<FileDesc> for PidFd {
+    fn from_inner(fd: FileDesc) -> PidFd { PidFd(fd) }
+}
+
+impl IntoInner<FileDesc> for PidFd {
+    fn into_inner(self) -> FileDesc { self.0 }
+}
@@ -86,7 +86,7 @@ impl Command {
         zx_cvt(fdio_spawn_etc(
             0,
             FDIO_SPAWN_CLONE_JOB | FDIO_SPAWN_CLONE_LDSVC | FDIO_SPAWN_CLONE_NAMESPACE,
-            self.get_argv()[0], self.get_argv().as_ptr(), envp, 3, actions.as_ptr(),
+            self.get_program_cstr().as_ptr(), self.get_argv().as_ptr(), envp, 3, actions.as_ptr(),
             &mut process_handle,
             ptr::null_mut(),
         ))?;
@@ -6,10 +6,17 @@ use crate::sys;
 
 use libc::{c_int, gid_t, pid_t, uid_t};
 
+#[cfg(target_os = "linux")]
+use crate::sys::process::PidFd;
+
 ////////////////////////////////////////////////////////////////////////////////
 // Command
 ////////////////////////////////////////////////////////////////////////////////
 
+// `setsid` fails in a process group leader and a session leader can't be
+// moved into another process group, so asking for both can't work out.
+const PGROUP_AND_SETSID_MSG: &str = "a process group and a new session can't both be requested";
+
 impl Command {
     pub fn spawn(&mut self, default: Stdio, needs_stdin: bool)
                  -> io::Result<(Process, StdioPipes)> {
@@ -21,10 +28,14 @@ impl Command {
             return Err(io::Error::new(ErrorKind::InvalidInput,
                                       "nul byte found in provided data"));
         }
+        if self.get_pgroup().is_some() && self.get_setsid() {
+            return Err(io::Error::new(ErrorKind::InvalidInput, PGROUP_AND_SETSID_MSG));
+        }
 
         let (ours, theirs) = self.setup_io(default, needs_stdin)?;
 
-        if let Some(ret) = self.posix_spawn(&theirs, envp.as_ref())? {
+        if let Some(mut ret) = self.posix_spawn(&theirs, envp.as_ref())? {
+            self.open_pidfd(&mut ret);
             return Ok((ret, ours))
         }
 
@@ -67,14 +78,17 @@ impl Command {
             }
         };
 
-        let mut p = Process { pid: pid, status: None };
+        let mut p = Process::new(pid);
         drop(output);
         let mut bytes = [0; 8];
 
         // loop to handle EINTR
         loop {
             match input.read(&mut bytes) {
-                Ok(0) => return Ok((p, ours)),
+                Ok(0) => {
+                    self.open_pidfd(&mut p);
+                    return Ok((p, ours))
+                }
                 Ok(8) => {
                     assert!(combine(CLOEXEC_MSG_FOOTER) == combine(&bytes[4.. 8]),
                             "Validation on the CLOEXEC pipe failed: {:?}", bytes);
@@ -107,6 +121,27 @@ impl Command {
         }
     }
 
+    // Opens a pidfd for the freshly spawned child if one was requested. We
+    // haven't waited on the child yet, so unless `SIGCHLD` is ignored its pid
+    // can't have been reused. With `SIGCHLD` ignored the kernel reaps children
+    // as soon as they exit, and a child which exits right away may already be
+    // gone, in which case opening fails or, if the pid was reused in the
+    // meantime, refers to another process. The pid-based methods of `Child`
+    // have the same problem in that case.
+    //
+    // Failing to open one, for example on kernels older than 5.3, isn't an
+    // error as the child is already running; the missing pidfd is reported
+    // when it is asked for instead.
+    #[cfg(target_os = "linux")]
+    fn open_pidfd(&self, p: &mut Process) {
+        if self.get_create_pidfd() {
+            p.pidfd = PidFd::open(p.pid).ok();
+        }
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    fn open_pidfd(&self, _: &mut Process) {}
+
     pub fn exec(&mut self, default: Stdio) -> io::Error {
         let envp = self.capture_env();
 
@@ -114,6 +149,9 @@ impl Command {
             return io::Error::new(ErrorKind::InvalidInput,
                                   "nul byte found in provided data")
         }
+        if self.get_pgroup().is_some() && self.get_setsid() {
+            return io::Error::new(ErrorKind::InvalidInput, PGROUP_AND_SETSID_MSG)
+        }
 
         match self.setup_io(default, true) {
             Ok((_, theirs)) => {
@@ -179,6 +217,9 @@ impl Command {
         }
 
         if cfg!(not(any(target_os = "l4re"))) {
+            if let Some(groups) = self.get_groups() {
+                cvt(libc::setgroups(groups.len() as _, groups.as_ptr()))?;
+            }
             if let Some(u) = self.get_gid() {
                 cvt(libc::setgid(u as gid_t))?;
             }
@@ -189,8 +230,11 @@ impl Command {
                 // groups that enable us to do super-user things. This will
                 // fail if we aren't root, so don't bother checking the
                 // return value, this is just done as an optimistic
-                // privilege dropping function.
-                let _ = libc::setgroups(0, ptr::null());
+                // privilege dropping function. Groups which were set
+                // explicitly are kept though.
+                if self.get_groups().is_none() {
+                    let _ = libc::setgroups(0, ptr::null());
+                }
 
                 cvt(libc::setuid(u as uid_t))?;
             }
@@ -198,6 +242,12 @@ impl Command {
         if let Some(ref cwd) = *self.get_cwd() {
             cvt(libc::chdir(cwd.as_ptr()))?;
         }
+        if let Some(pgroup) = self.get_pgroup() {
+            cvt(libc::setpgid(0, pgroup))?;
+        }
+        if self.get_setsid() {
+            cvt(libc::setsid())?;
+        }
 
         // emscripten has no signal support.
         #[cfg(not(any(target_os = "emscripten")))]
@@ -252,7 +302,7 @@ impl Command {
             *sys::os::environ() = envp.as_ptr();
         }
 
-        libc::execvp(self.get_argv()[0], self.get_argv().as_ptr());
+        libc::execvp(self.get_program_cstr().as_ptr(), self.get_argv().as_ptr());
         Err(io::Error::last_os_error())
     }
 
@@ -276,6 +326,7 @@ impl Command {
 
         if self.get_gid().is_some() ||
             self.get_uid().is_some() ||
+            self.get_groups().is_some() ||
             self.env_saw_path() ||
             self.get_closures().len() != 0 {
             return Ok(None)
@@ -293,6 +344,24 @@ impl Command {
             }
         }
 
+        // Starting a new session is a non-POSIX extension, only glibc 2.26+
+        // supports it with the `POSIX_SPAWN_SETSID` flag.
+        #[cfg(all(target_os = "linux", target_env = "gnu"))]
+        const POSIX_SPAWN_SETSID: libc::c_int = 0x80;
+        #[cfg(not(all(target_os = "linux", target_env = "gnu")))]
+        const POSIX_SPAWN_SETSID: libc::c_int = 0;
+        if self.get_setsid() {
+            #[cfg(all(target_os = "linux", target_env = "gnu"))]
+            {
+                match sys::os::glibc_version() {
+                    Some(version) if version >= (2, 26) => {}
+                    _ => return Ok(None),
+                }
+            }
+            #[cfg(not(all(target_os = "linux", target_env = "gnu")))]
+            return Ok(None);
+        }
+
         // Solaris and glibc 2.29+ can set a new working directory, and maybe
         // others will gain this non-POSIX function too. We'll check for this
         // weak symbol as soon as it's needed, so we can return early otherwise
@@ -311,7 +380,7 @@ impl Command {
             None => None,
         };
 
-        let mut p = Process { pid: 0, status: None };
+        let mut p = Process::new(0);
 
         struct PosixSpawnFileActions(MaybeUninit<libc::posix_spawn_file_actions_t>);
 
@@ -367,8 +436,15 @@ impl Command {
             cvt(libc::posix_spawnattr_setsigdefault(attrs.0.as_mut_ptr(),
                                                     set.as_ptr()))?;
 
-            let flags = libc::POSIX_SPAWN_SETSIGDEF |
+            let mut flags = libc::POSIX_SPAWN_SETSIGDEF |
                 libc::POSIX_SPAWN_SETSIGMASK;
+            if let Some(pgroup) = self.get_pgroup() {
+                cvt(libc::posix_spawnattr_setpgroup(attrs.0.as_mut_ptr(), pgroup))?;
+                flags |= libc::POSIX_SPAWN_SETPGROUP;
+            }
+            if self.get_setsid() {
+                flags |= POSIX_SPAWN_SETSID as _;
+            }
             cvt(libc::posix_spawnattr_setflags(attrs.0.as_mut_ptr(), flags as _))?;
 
             // Make sure we synchronize access to the global `environ` resource
@@ -377,7 +453,7 @@ impl Command {
                 .unwrap_or_else(|| *sys::os::environ() as *const _);
             let ret = libc::posix_spawnp(
                 &mut p.pid,
-                self.get_argv()[0],
+                self.get_program_cstr().as_ptr(),
                 file_actions.0.as_ptr(),
                 attrs.0.as_ptr(),
                 self.get_argv().as_ptr() as *const _,
@@ -400,9 +476,22 @@ impl Command {
 pub struct Process {
     pid: pid_t,
     status: Option<ExitStatus>,
+    // The pidfd of the child, if one was requested and the kernel supports
+    // them.
+    #[cfg(target_os = "linux")]
+    pidfd: Option<PidFd>,
 }
 
 impl Process {
+    fn new(pid: pid_t) -> Process {
+        Process {
+            pid,
+            status: None,
+            #[cfg(target_os = "linux")]
+            pidfd: None,
+        }
+    }
+
     pub fn id(&self) -> u32 {
         self.pid as u32
     }
@@ -415,10 +504,26 @@ impl Process {
             Err(Error::new(ErrorKind::InvalidInput,
                            "invalid argument: can't kill an exited process"))
         } else {
+            #[cfg(target_os = "linux")]
+            {
+                if let Some(ref pidfd) = self.pidfd {
+                    return pidfd.kill();
+                }
+            }
             cvt(unsafe { libc::kill(self.pid, libc::SIGKILL) }).map(|_| ())
         }
     }
 
+    #[cfg(target_os = "linux")]
+    pub fn pidfd(&self) -> Option<&PidFd> {
+        self.pidfd.as_ref()
+    }
+
+    #[cfg(target_os = "linux")]
+    pub fn take_pidfd(&mut self) -> Option<PidFd> {
+        self.pidfd.take()
+    }
+
     pub fn wait(&mut self) -> io::Result<ExitStatus> {
         use crate::sys::cvt_r;
         if let Some(status) = self.status {
@@ -2,7 +2,7 @@ use crate::os::windows::prelude::*;
 
 use crate::ffi::OsString;
 use crate::fmt;
-use crate::io::{self, Error, SeekFrom, IoSlice, IoSliceMut};
+use crate::io::{self, Error, SeekFrom, IoSlice, IoSliceMut, ReadBuf};
 use crate::mem;
 use crate::path::{Path, PathBuf};
 use crate::ptr;
@@ -13,6 +13,8 @@ use crate::sys::time::SystemTime;
 use crate::sys::{c, cvt};
 use crate::sys_common::FromInner;
 
+pub use crate::sys_common::fs::Dir;
+
 use super::to_u16s;
 
 pub struct File { handle: Handle }
@@ -287,6 +289,62 @@ impl File {
         Ok(())
     }
 
+    pub fn lock_shared(&self) -> io::Result<()> {
+        self.lock_file(0)
+    }
+
+    pub fn lock_exclusive(&self) -> io::Result<()> {
+        self.lock_file(c::LOCKFILE_EXCLUSIVE_LOCK)
+    }
+
+    pub fn try_lock_shared(&self) -> io::Result<bool> {
+        self.try_lock_file(0)
+    }
+
+    pub fn try_lock_exclusive(&self) -> io::Result<bool> {
+        self.try_lock_file(c::LOCKFILE_EXCLUSIVE_LOCK)
+    }
+
+    fn try_lock_file(&self, flags: c::DWORD) -> io::Result<bool> {
+        match self.lock_file(flags | c::LOCKFILE_FAIL_IMMEDIATELY) {
+            Ok(()) => Ok(true),
+            Err(ref err) if err.raw_os_error() == Some(c::ERROR_LOCK_VIOLATION as i32) => {
+                Ok(false)
+            }
+            Err(err) => Err(err),
+        }
+    }
+
+    // Locks the whole file, including any part it may be extended by.
+    fn lock_file(&self, flags: c::DWORD) -> io::Result<()> {
+        unsafe {
+            let mut overlapped: c::OVERLAPPED = mem::zeroed();
+            cvt(c::LockFileEx(self.handle.raw(),
+                              flags,
+                              0,
+                              c::DWORD::max_value(),
+                              c::DWORD::max_value(),
+                              &mut overlapped))?;
+        }
+        Ok(())
+    }
+
+    pub fn unlock(&self) -> io::Result<()> {
+        let ret = cvt(unsafe {
+            c::UnlockFile(self.handle.raw(),
+                          0,
+                          0,
+                          c::DWORD::max_value(),
+                          c::DWORD::max_value())
+        });
+        match ret {
+            Ok(_) => Ok(()),
+            // Like on Unix, unlocking a file which isn't locked succeeds.
+            Err(ref err) if err.raw_os_error() == Some(c::ERROR_NOT_LOCKED as i32) => Ok(()),
+            Err(err) => Err(err),
+        }
+    }
+
     pub fn file_attr(&self) -> io::Result<FileAttr> {
         unsafe {
             let mut info: c::BY_HANDLE_FILE_INFORMATION = mem::zeroed();
@@ -314,6 +372,10 @@ impl File {
         self.handle.read(buf)
     }
 
+    pub fn read_buf(&self, buf: &mut ReadBuf<'_>) -> io::Result<()> {
+        crate::io::default_read_buf(|b| self.read(b), buf)
+    }
+
     pub fn read_vectored(&self, bufs: &mut [IoSliceMut<'_>]) -> io::Result<usize> {
         self.handle.read_vectored(bufs)
     }
@@ -75,6 +75,10 @@ pub struct Command {
     stderr: Option<Stdio>,
 }
 
+pub type CommandEnvs<'a> = crate::sys_common::process::CommandEnvs<'a, WindowsEnvKey>;
+
+pub type CommandArgs<'a> = crate::sys_common::process::CommandArgs<'a, OsString>;
+
 pub enum Stdio {
     Inherit,
     Null,
@@ -129,6 +133,19 @@ impl Command {
         self.flags = flags;
     }
 
+    pub fn get_program(&self) -> &OsStr {
+        &self.program
+    }
+    pub fn get_args(&self) -> CommandArgs<'_> {
+        CommandArgs::new(&self.args)
+    }
+    pub fn get_envs(&self) -> CommandEnvs<'_> {
+        self.env.iter()
+    }
+    pub fn get_current_dir(&self) -> Option<&Path> {
+        self.cwd.as_ref().map(|cwd| Path::new(cwd))
+    }
+
     pub fn spawn(&mut self, default: Stdio, needs_stdin: bool)
                  -> io::Result<(Process, StdioPipes)> {
         let maybe_env = self.env.capture_if_changed();
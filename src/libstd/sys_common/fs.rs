@@ -1,8 +1,10 @@
 #![allow(dead_code)] // not used on all platforms
 
-use crate::path::Path;
+use crate::fmt;
+use crate::path::{Path, PathBuf};
 use crate::fs;
 use crate::io::{self, Error, ErrorKind};
+use crate::sys::fs as fs_imp;
 
 pub fn copy(from: &Path, to: &Path) -> io::Result<u64> {
     if !from.is_file() {
@@ -39,3 +41,59 @@ fn remove_dir_all_recursive(path: &Path) -> io::Result<()> {
     }
     fs::remove_dir(path)
 }
+
+// A directory handle for platforms without `*at` system calls, which resolves
+// paths by joining them onto the path the directory was opened with. Unlike
+// a real handle, this does not keep referring to the same directory if it is
+// moved or replaced.
+pub struct Dir {
+    root: PathBuf,
+}
+
+impl Dir {
+    pub fn open(path: &Path) -> io::Result<Dir> {
+        if !fs_imp::stat(path)?.file_type().is_dir() {
+            return Err(Error::new(ErrorKind::Other, "the path is not a directory"))
+        }
+        Ok(Dir { root: path.to_path_buf() })
+    }
+
+    pub fn open_dir_at(&self, path: &Path) -> io::Result<Dir> {
+        Dir::open(&self.root.join(path))
+    }
+
+    pub fn open_file_at(&self, path: &Path, opts: &fs_imp::OpenOptions)
+                        -> io::Result<fs_imp::File> {
+        fs_imp::File::open(&self.root.join(path), opts)
+    }
+
+    pub fn create_dir_at(&self, path: &Path) -> io::Result<()> {
+        fs_imp::DirBuilder::new().mkdir(&self.root.join(path))
+    }
+
+    pub fn remove_file_at(&self, path: &Path) -> io::Result<()> {
+        fs_imp::unlink(&self.root.join(path))
+    }
+
+    pub fn remove_dir_at(&self, path: &Path) -> io::Result<()> {
+        fs_imp::rmdir(&self.root.join(path))
+    }
+
+    pub fn rename_at(&self, from: &Path, to_dir: &Dir, to: &Path) -> io::Result<()> {
+        fs_imp::rename(&self.root.join(from), &to_dir.root.join(to))
+    }
+
+    pub fn symlink_metadata_at(&self, path: &Path) -> io::Result<fs_imp::FileAttr> {
+        fs_imp::lstat(&self.root.join(path))
+    }
+
+    pub fn read_dir(&self) -> io::Result<fs_imp::ReadDir> {
+        fs_imp::readdir(&self.root)
+    }
+}
+
+impl fmt::Debug for Dir {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Dir").field("path", &self.root).finish()
+    }
+}
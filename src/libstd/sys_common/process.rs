@@ -3,8 +3,9 @@
 
 use crate::ffi::{OsStr, OsString};
 use crate::env;
-use crate::collections::BTreeMap;
+use crate::collections::{btree_map, BTreeMap};
 use crate::borrow::Borrow;
+use crate::slice;
 
 pub trait EnvKey:
     From<OsString> + Into<OsString> +
@@ -115,6 +116,9 @@ impl<K: EnvKey> CommandEnv<K> {
         self.clear = true;
         self.vars.clear();
     }
+    pub fn iter(&self) -> CommandEnvs<'_, K> {
+        CommandEnvs { iter: self.vars.iter() }
+    }
     pub fn have_changed_path(&self) -> bool {
         self.saw_path || self.clear
     }
@@ -124,3 +128,55 @@ impl<K: EnvKey> CommandEnv<K> {
         }
     }
 }
+
+/// An iterator over the environment variable changes of a command, where a
+/// value of `None` means the variable is removed.
+#[derive(Debug)]
+pub struct CommandEnvs<'a, K> {
+    iter: btree_map::Iter<'a, K, Option<OsString>>,
+}
+
+impl<'a, K: EnvKey> Iterator for CommandEnvs<'a, K> {
+    type Item = (&'a OsStr, Option<&'a OsStr>);
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter.next().map(|(key, value)| (key.as_ref(), value.as_ref().map(|v| &**v)))
+    }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<'a, K: EnvKey> ExactSizeIterator for CommandEnvs<'a, K> {
+    fn len(&self) -> usize {
+        self.iter.len()
+    }
+}
+
+/// An iterator over the arguments of a command, for the platforms which
+/// store them as a list of strings.
+#[derive(Debug)]
+pub struct CommandArgs<'a, T> {
+    iter: slice::Iter<'a, T>,
+}
+
+impl<'a, T> CommandArgs<'a, T> {
+    pub fn new(args: &'a [T]) -> Self {
+        CommandArgs { iter: args.iter() }
+    }
+}
+
+impl<'a, T: AsRef<OsStr>> Iterator for CommandArgs<'a, T> {
+    type Item = &'a OsStr;
+    fn next(&mut self) -> Option<&'a OsStr> {
+        self.iter.next().map(|arg| arg.as_ref())
+    }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<'a, T: AsRef<OsStr>> ExactSizeIterator for CommandArgs<'a, T> {
+    fn len(&self) -> usize {
+        self.iter.len()
+    }
+}
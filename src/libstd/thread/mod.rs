@@ -161,6 +161,7 @@ use crate::cell::UnsafeCell;
 use crate::ffi::{CStr, CString};
 use crate::fmt;
 use crate::io;
+use crate::marker::PhantomData;
 use crate::mem;
 use crate::num::NonZeroU64;
 use crate::panic;
@@ -203,6 +204,15 @@ pub use self::local::{LocalKey, AccessError};
 #[unstable(feature = "libstd_thread_internals", issue = "0")]
 #[doc(hidden)] pub use self::local::os::Key as __OsLocalKeyInner;
 
+////////////////////////////////////////////////////////////////////////////////
+// Scoped threads
+////////////////////////////////////////////////////////////////////////////////
+
+mod scoped;
+
+#[unstable(feature = "scoped_threads", issue = "0")]
+pub use self::scoped::{scope, Scope, ScopedJoinHandle};
+
 ////////////////////////////////////////////////////////////////////////////////
 // Builder
 ////////////////////////////////////////////////////////////////////////////////
@@ -447,6 +457,76 @@ impl Builder {
     #[unstable(feature = "thread_spawn_unchecked", issue = "55132")]
     pub unsafe fn spawn_unchecked<'a, F, T>(self, f: F) -> io::Result<JoinHandle<T>> where
         F: FnOnce() -> T, F: Send + 'a, T: Send + 'a
+    {
+        Ok(JoinHandle(self.spawn_unchecked_(f, None)?))
+    }
+
+    /// Spawns a new scoped thread using the settings set through this `Builder`.
+    ///
+    /// Unlike [`Scope::spawn`], this method yields an [`io::Result`] to
+    /// capture any failure to create the thread at the OS level.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a thread name was set and it contained null bytes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(scoped_threads)]
+    /// use std::thread;
+    ///
+    /// let mut a = vec![1, 2, 3];
+    /// let mut x = 0;
+    ///
+    /// thread::scope(|s| {
+    ///     thread::Builder::new()
+    ///         .name("first".to_string())
+    ///         .spawn_scoped(s, || {
+    ///             println!("hello from the {:?} scoped thread", thread::current().name());
+    ///             // We can borrow `a` here.
+    ///             dbg!(&a);
+    ///         })
+    ///         .unwrap();
+    ///     thread::Builder::new()
+    ///         .name("second".to_string())
+    ///         .spawn_scoped(s, || {
+    ///             println!("hello from the {:?} scoped thread", thread::current().name());
+    ///             // We can even mutably borrow `x` here,
+    ///             // because no other threads are using it.
+    ///             x += a[0] + a[2];
+    ///         })
+    ///         .unwrap();
+    ///     println!("hello from the main thread");
+    /// });
+    ///
+    /// // After the scope, we can modify and access our variables again:
+    /// a.push(4);
+    /// assert_eq!(x, a.len());
+    /// ```
+    ///
+    /// [`Scope::spawn`]: ../../std/thread/struct.Scope.html#method.spawn
+    /// [`io::Result`]: ../../std/io/type.Result.html
+    #[unstable(feature = "scoped_threads", issue = "0")]
+    pub fn spawn_scoped<'scope, 'env, F, T>(
+        self,
+        scope: &'scope Scope<'scope, 'env>,
+        f: F,
+    ) -> io::Result<ScopedJoinHandle<'scope, T>> where
+        F: FnOnce() -> T, F: Send + 'scope, T: Send + 'scope
+    {
+        // `scope` joins the thread before `'scope` ends, which is all that
+        // `spawn_unchecked_` requires.
+        let inner = unsafe { self.spawn_unchecked_(f, Some(scope.data.clone()))? };
+        Ok(ScopedJoinHandle(inner))
+    }
+
+    unsafe fn spawn_unchecked_<'a, 'scope, F, T>(
+        self,
+        f: F,
+        scope_data: Option<Arc<scoped::ScopeData>>,
+    ) -> io::Result<JoinInner<'scope, T>> where
+        F: FnOnce() -> T, F: Send + 'a, T: Send + 'a, 'scope: 'a
     {
         let Builder { name, stack_size } = self;
 
@@ -455,8 +535,11 @@ impl Builder {
         let my_thread = Thread::new(name);
         let their_thread = my_thread.clone();
 
-        let my_packet : Arc<UnsafeCell<Option<Result<T>>>>
-            = Arc::new(UnsafeCell::new(None));
+        let my_packet: Arc<Packet<'scope, T>> = Arc::new(Packet {
+            scope: scope_data,
+            result: UnsafeCell::new(None),
+            _marker: PhantomData,
+        });
         let their_packet = my_packet.clone();
 
         let main = move || {
@@ -471,10 +554,17 @@ impl Builder {
             }));
             #[cfg(not(feature = "backtrace"))]
             let try_result = panic::catch_unwind(panic::AssertUnwindSafe(f));
-            *their_packet.get() = Some(try_result);
+            *their_packet.result.get() = Some(try_result);
+            // Dropping the packet here, rather than when the closure is
+            // dropped, lets a scope know that this thread is done with it.
+            drop(their_packet);
         };
 
-        Ok(JoinHandle(JoinInner {
+        if let Some(scope_data) = &my_packet.scope {
+            scope_data.increment_num_running_threads();
+        }
+
+        Ok(JoinInner {
             // `imp::Thread::new` takes a closure with a `'static` lifetime, since it's passed
             // through FFI or otherwise used with low-level threading primitives that have no
             // notion of or way to enforce lifetimes.
@@ -493,8 +583,8 @@ impl Builder {
                 )),
             )?),
             thread: my_thread,
-            packet: Packet(my_packet),
-        }))
+            packet: my_packet,
+        })
     }
 }
 
@@ -1304,29 +1394,59 @@ pub type Result<T> = crate::result::Result<T, Box<dyn Any + Send + 'static>>;
 // parent thread never reads this packet until the child has exited).
 //
 // This packet itself is then stored into a `JoinInner` which in turns is placed
-// in `JoinHandle` and `JoinGuard`. Due to the usage of `UnsafeCell` we need to
-// manually worry about impls like Send and Sync. The type `T` should
+// in `JoinHandle` and `ScopedJoinHandle`. Due to the usage of `UnsafeCell` we
+// need to manually worry about impls like Send and Sync. The type `T` should
 // already always be Send (otherwise the thread could not have been created) and
 // this type is inherently Sync because no methods take &self. Regardless,
 // however, we add inheriting impls for Send/Sync to this type to ensure it's
 // Send/Sync and that future modifications will still appropriately classify it.
-struct Packet<T>(Arc<UnsafeCell<Option<Result<T>>>>);
+//
+// The packet of a scoped thread also tells the scope when it's dropped, which
+// happens once the thread has exited and its `ScopedJoinHandle` is gone, so the
+// result, which may borrow from the scope, never outlives it.
+struct Packet<'scope, T> {
+    scope: Option<Arc<scoped::ScopeData>>,
+    result: UnsafeCell<Option<Result<T>>>,
+    _marker: PhantomData<Option<&'scope scoped::ScopeData>>,
+}
 
-unsafe impl<T: Send> Send for Packet<T> {}
-unsafe impl<T: Sync> Sync for Packet<T> {}
+unsafe impl<'scope, T: Send> Send for Packet<'scope, T> {}
+unsafe impl<'scope, T: Sync> Sync for Packet<'scope, T> {}
+
+impl<'scope, T> Drop for Packet<'scope, T> {
+    fn drop(&mut self) {
+        // A panic which nobody joined on is reported by the scope.
+        let unhandled_panic = match unsafe { &*self.result.get() } {
+            Some(Err(_)) => true,
+            _ => false,
+        };
+        // Drop the result without unwinding, as we may be outside of the
+        // outermost `catch_unwind` of the thread. This only happens for
+        // threads which weren't joined, as `join` takes the result.
+        let dropped = panic::catch_unwind(panic::AssertUnwindSafe(|| unsafe {
+            *self.result.get() = None;
+        }));
+        if dropped.is_err() {
+            rtabort!("thread result panicked on drop");
+        }
+        if let Some(scope) = &self.scope {
+            scope.decrement_num_running_threads(unhandled_panic);
+        }
+    }
+}
 
-/// Inner representation for JoinHandle
-struct JoinInner<T> {
+/// Inner representation for JoinHandle and ScopedJoinHandle
+struct JoinInner<'scope, T> {
     native: Option<imp::Thread>,
     thread: Thread,
-    packet: Packet<T>,
+    packet: Arc<Packet<'scope, T>>,
 }
 
-impl<T> JoinInner<T> {
+impl<'scope, T> JoinInner<'scope, T> {
     fn join(&mut self) -> Result<T> {
         self.native.take().unwrap().join();
         unsafe {
-            (*self.packet.0.get()).take().unwrap()
+            (*self.packet.result.get()).take().unwrap()
         }
     }
 }
@@ -1395,7 +1515,7 @@ impl<T> JoinInner<T> {
 /// [`thread::spawn`]: fn.spawn.html
 /// [`thread::Builder::spawn`]: struct.Builder.html#method.spawn
 #[stable(feature = "rust1", since = "1.0.0")]
-pub struct JoinHandle<T>(JoinInner<T>);
+pub struct JoinHandle<T>(JoinInner<'static, T>);
 
 #[stable(feature = "joinhandle_impl_send_sync", since = "1.29.0")]
 unsafe impl<T> Send for JoinHandle<T> {}
@@ -0,0 +1,398 @@
+//! Scoped threads, which can borrow non-`'static` data from the stack.
+
+use super::{current, park, Builder, JoinInner, Result, Thread};
+use crate::fmt;
+use crate::marker::PhantomData;
+use crate::panic::{catch_unwind, resume_unwind, AssertUnwindSafe};
+use crate::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use crate::sync::Arc;
+use crate::usize;
+
+/// A scope to spawn scoped threads in.
+///
+/// See [`scope`] for details.
+///
+/// [`scope`]: fn.scope.html
+#[unstable(feature = "scoped_threads", issue = "0")]
+pub struct Scope<'scope, 'env: 'scope> {
+    pub(super) data: Arc<ScopeData>,
+    /// Invariance over 'scope, to make sure 'scope cannot shrink,
+    /// which is necessary for soundness.
+    ///
+    /// Without invariance, this would compile fine but be unsound:
+    ///
+    /// ```compile_fail
+    /// #![feature(scoped_threads)]
+    ///
+    /// std::thread::scope(|s| {
+    ///     s.spawn(|| {
+    ///         let a = String::from("abcd");
+    ///         s.spawn(|| println!("{:?}", a)); // might run after `a` is dropped
+    ///     });
+    /// });
+    /// ```
+    scope: PhantomData<&'scope mut &'scope ()>,
+    env: PhantomData<&'env mut &'env ()>,
+}
+
+/// An owned permission to join on a scoped thread (block on its termination).
+///
+/// See [`Scope::spawn`] for details.
+///
+/// [`Scope::spawn`]: struct.Scope.html#method.spawn
+#[unstable(feature = "scoped_threads", issue = "0")]
+pub struct ScopedJoinHandle<'scope, T>(pub(super) JoinInner<'scope, T>);
+
+#[unstable(feature = "scoped_threads", issue = "0")]
+unsafe impl<'scope, T> Send for ScopedJoinHandle<'scope, T> {}
+#[unstable(feature = "scoped_threads", issue = "0")]
+unsafe impl<'scope, T> Sync for ScopedJoinHandle<'scope, T> {}
+
+/// The state shared by a scope and the packets of the threads spawned in it.
+pub(super) struct ScopeData {
+    num_running_threads: AtomicUsize,
+    a_thread_panicked: AtomicBool,
+    main_thread: Thread,
+}
+
+impl ScopeData {
+    pub(super) fn increment_num_running_threads(&self) {
+        // We check for 'overflow' with usize::MAX / 2, to make sure there's no
+        // chance it overflows to 0, which would result in unsoundness.
+        if self.num_running_threads.fetch_add(1, Ordering::Relaxed) > usize::MAX / 2 {
+            // This can only reasonably happen by mem::forget()'ing many
+            // ScopedJoinHandles.
+            self.decrement_num_running_threads(false);
+            panic!("too many running threads in thread scope");
+        }
+    }
+
+    pub(super) fn decrement_num_running_threads(&self, panic: bool) {
+        if panic {
+            self.a_thread_panicked.store(true, Ordering::Relaxed);
+        }
+        if self.num_running_threads.fetch_sub(1, Ordering::Release) == 1 {
+            self.main_thread.unpark();
+        }
+    }
+}
+
+/// Creates a scope for spawning scoped threads.
+///
+/// The function passed to `scope` will be provided a [`Scope`] object,
+/// through which scoped threads can be [spawned][`Scope::spawn`].
+///
+/// Unlike non-scoped threads, scoped threads can borrow non-`'static` data,
+/// as the scope guarantees all threads will be joined at the end of the scope.
+///
+/// All threads spawned within the scope that haven't been manually joined
+/// will be automatically joined before this function returns.
+///
+/// # Panics
+///
+/// If any of the automatically joined threads panicked, this function will panic.
+///
+/// If you want to handle panics from spawned threads,
+/// [`join`][`ScopedJoinHandle::join`] them before the end of the scope.
+///
+/// # Examples
+///
+/// ```
+/// #![feature(scoped_threads)]
+/// use std::thread;
+///
+/// let mut a = vec![1, 2, 3];
+/// let mut x = 0;
+///
+/// thread::scope(|s| {
+///     s.spawn(|| {
+///         println!("hello from the first scoped thread");
+///         // We can borrow `a` here.
+///         dbg!(&a);
+///     });
+///     s.spawn(|| {
+///         println!("hello from the second scoped thread");
+///         // We can even mutably borrow `x` here,
+///         // because no other threads are using it.
+///         x += a[0] + a[2];
+///     });
+///     println!("hello from the main thread");
+/// });
+///
+/// // After the scope, we can modify and access our variables again:
+/// a.push(4);
+/// assert_eq!(x, a.len());
+/// ```
+///
+/// # Lifetimes
+///
+/// Scoped threads involve two lifetimes: `'scope` and `'env`.
+///
+/// The `'scope` lifetime represents the lifetime of the scope itself.
+/// That is: the time during which new scoped threads may be spawned,
+/// and also the time during which they might still be running.
+/// Once this lifetime ends, all scoped threads are joined.
+/// This lifetime starts within the `scope` function, before `f` (the argument to `scope`) starts.
+/// It ends after `f` returns and all scoped threads have been joined, but before `scope` returns.
+///
+/// The `'env` lifetime represents the lifetime of whatever is borrowed by the scoped threads.
+/// This lifetime must outlast the call to `scope`, and thus cannot be smaller than `'scope`.
+/// It can be as small as the call to `scope`, meaning that anything that outlives this call,
+/// such as local variables defined right before the scope, can be borrowed by the scoped threads.
+///
+/// The `'env: 'scope` bound is part of the definition of the `Scope` type.
+///
+/// [`Scope`]: struct.Scope.html
+/// [`Scope::spawn`]: struct.Scope.html#method.spawn
+/// [`ScopedJoinHandle::join`]: struct.ScopedJoinHandle.html#method.join
+#[unstable(feature = "scoped_threads", issue = "0")]
+pub fn scope<'env, F, T>(f: F) -> T
+where
+    F: for<'scope> FnOnce(&'scope Scope<'scope, 'env>) -> T,
+{
+    let scope = Scope {
+        data: Arc::new(ScopeData {
+            num_running_threads: AtomicUsize::new(0),
+            a_thread_panicked: AtomicBool::new(false),
+            main_thread: current(),
+        }),
+        env: PhantomData,
+        scope: PhantomData,
+    };
+
+    // Run `f`, but catch panics so we can make sure to wait for all the threads to join.
+    let result = catch_unwind(AssertUnwindSafe(|| f(&scope)));
+
+    // Wait until all the threads are finished.
+    while scope.data.num_running_threads.load(Ordering::Acquire) != 0 {
+        park();
+    }
+
+    // Throw any panic from `f`, or the return value of `f` if no thread panicked.
+    match result {
+        Err(e) => resume_unwind(e),
+        Ok(_) if scope.data.a_thread_panicked.load(Ordering::Relaxed) => {
+            panic!("a scoped thread panicked")
+        }
+        Ok(result) => result,
+    }
+}
+
+impl<'scope, 'env> Scope<'scope, 'env> {
+    /// Spawns a new thread within a scope, returning a [`ScopedJoinHandle`] for it.
+    ///
+    /// Unlike non-scoped threads, threads spawned with this function may
+    /// borrow non-`'static` data from the outside the scope. See [`scope`] for
+    /// details.
+    ///
+    /// The join handle provides a [`join`] method that can be used to join the spawned
+    /// thread. If the spawned thread panics, [`join`] will return an [`Err`] containing
+    /// the panic payload.
+    ///
+    /// If the join handle is dropped, the spawned thread will be implicitly joined at the
+    /// end of the scope. In that case, if the spawned thread panics, [`scope`] will
+    /// panic after all threads are joined.
+    ///
+    /// This call will create a thread using default parameters of [`Builder`].
+    /// If you want to specify the stack size or the name of the thread, use
+    /// [`Builder::spawn_scoped`] instead.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the OS fails to create a thread; use [`Builder::spawn_scoped`]
+    /// to recover from such errors.
+    ///
+    /// [`ScopedJoinHandle`]: struct.ScopedJoinHandle.html
+    /// [`scope`]: fn.scope.html
+    /// [`join`]: struct.ScopedJoinHandle.html#method.join
+    /// [`Err`]: ../../std/result/enum.Result.html#variant.Err
+    /// [`Builder`]: struct.Builder.html
+    /// [`Builder::spawn_scoped`]: struct.Builder.html#method.spawn_scoped
+    #[unstable(feature = "scoped_threads", issue = "0")]
+    pub fn spawn<F, T>(&'scope self, f: F) -> ScopedJoinHandle<'scope, T>
+    where
+        F: FnOnce() -> T + Send + 'scope,
+        T: Send + 'scope,
+    {
+        Builder::new().spawn_scoped(self, f).expect("failed to spawn thread")
+    }
+}
+
+impl<'scope, T> ScopedJoinHandle<'scope, T> {
+    /// Extracts a handle to the underlying thread.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(scoped_threads)]
+    /// use std::thread;
+    ///
+    /// thread::scope(|s| {
+    ///     let t = s.spawn(|| {
+    ///         println!("hello");
+    ///     });
+    ///     println!("thread id: {:?}", t.thread().id());
+    /// });
+    /// ```
+    #[unstable(feature = "scoped_threads", issue = "0")]
+    pub fn thread(&self) -> &Thread {
+        &self.0.thread
+    }
+
+    /// Waits for the associated thread to finish.
+    ///
+    /// This function will return immediately if the associated thread has already finished.
+    ///
+    /// In terms of [atomic memory orderings], the completion of the associated
+    /// thread synchronizes with this function returning.
+    /// In other words, all operations performed by that thread
+    /// [happen before](https://doc.rust-lang.org/nomicon/atomics.html#data-accesses)
+    /// all operations that happen after `join` returns.
+    ///
+    /// If the associated thread panics, [`Err`] is returned with the panic payload,
+    /// and the panic isn't propagated by the scope.
+    ///
+    /// [atomic memory orderings]: ../../std/sync/atomic/index.html
+    /// [`Err`]: ../../std/result/enum.Result.html#variant.Err
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(scoped_threads)]
+    /// use std::thread;
+    ///
+    /// thread::scope(|s| {
+    ///     let t = s.spawn(|| {
+    ///         panic!("oh no");
+    ///     });
+    ///     assert!(t.join().is_err());
+    /// });
+    /// ```
+    #[unstable(feature = "scoped_threads", issue = "0")]
+    pub fn join(mut self) -> Result<T> {
+        self.0.join()
+    }
+}
+
+#[unstable(feature = "scoped_threads", issue = "0")]
+impl<'scope, 'env> fmt::Debug for Scope<'scope, 'env> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Scope")
+            .field("num_running_threads", &self.data.num_running_threads.load(Ordering::Relaxed))
+            .field("a_thread_panicked", &self.data.a_thread_panicked.load(Ordering::Relaxed))
+            .field("main_thread", &self.data.main_thread)
+            .finish()
+    }
+}
+
+#[unstable(feature = "scoped_threads", issue = "0")]
+impl<'scope, T> fmt::Debug for ScopedJoinHandle<'scope, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad("ScopedJoinHandle { .. }")
+    }
+}
+
+#[cfg(all(test, not(target_os = "emscripten")))]
+mod tests {
+    use crate::sync::atomic::{AtomicUsize, Ordering};
+    use crate::thread;
+    use crate::time::Duration;
+
+    #[test]
+    fn scope_borrows_from_the_stack() {
+        let mut data = vec![1, 2, 3];
+        let sum = AtomicUsize::new(0);
+        thread::scope(|s| {
+            for x in &data {
+                let sum = &sum;
+                s.spawn(move || {
+                    sum.fetch_add(*x, Ordering::SeqCst);
+                });
+            }
+        });
+        assert_eq!(sum.load(Ordering::SeqCst), 6);
+
+        // The borrows ended with the scope.
+        data.push(4);
+        assert_eq!(data.len(), 4);
+    }
+
+    #[test]
+    fn scope_joins_unjoined_threads() {
+        let done = AtomicUsize::new(0);
+        thread::scope(|s| {
+            s.spawn(|| {
+                thread::sleep(Duration::from_millis(50));
+                done.fetch_add(1, Ordering::SeqCst);
+            });
+        });
+        assert_eq!(done.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn scope_returns_value_and_results() {
+        let data = [1, 2, 3];
+        let (a, b) = thread::scope(|s| {
+            let a = s.spawn(|| data.iter().sum::<i32>());
+            let b = s.spawn(|| data.len());
+            (a.join().unwrap(), b.join().unwrap())
+        });
+        assert_eq!((a, b), (6, 3));
+    }
+
+    #[test]
+    fn nested_spawn() {
+        let count = AtomicUsize::new(0);
+        let count = &count;
+        thread::scope(|s| {
+            s.spawn(move || {
+                s.spawn(move || count.fetch_add(1, Ordering::SeqCst));
+                count.fetch_add(1, Ordering::SeqCst);
+            });
+        });
+        assert_eq!(count.load(Ordering::SeqCst), 2);
+    }
+
+    #[test]
+    fn joined_panic_is_not_propagated() {
+        thread::scope(|s| {
+            let t = s.spawn(|| -> () { panic!("oh no") });
+            assert!(t.join().is_err());
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "a scoped thread panicked")]
+    fn unjoined_panic_is_propagated() {
+        thread::scope(|s| {
+            s.spawn(|| -> () { panic!("oh no") });
+        });
+    }
+
+    #[test]
+    fn panic_in_scope_waits_for_threads() {
+        let done = AtomicUsize::new(0);
+        let result = crate::panic::catch_unwind(crate::panic::AssertUnwindSafe(|| {
+            thread::scope(|s| {
+                s.spawn(|| {
+                    thread::sleep(Duration::from_millis(50));
+                    done.fetch_add(1, Ordering::SeqCst);
+                });
+                panic!("scope panicked");
+            })
+        }));
+        assert!(result.is_err());
+        assert_eq!(done.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn named_scoped_thread() {
+        thread::scope(|s| {
+            let t = thread::Builder::new()
+                .name("scoped".to_string())
+                .spawn_scoped(s, || thread::current().name().map(|n| n.to_string()))
+                .unwrap();
+            assert_eq!(t.join().unwrap(), Some("scoped".to_string()));
+        });
+    }
+}
@@ -53,6 +53,8 @@ const EXPLAIN_GLOBAL_ASM: &str =
     "`global_asm!` is not stable enough for use and is subject to change";
 const EXPLAIN_CUSTOM_TEST_FRAMEWORKS: &str =
     "custom test frameworks are an unstable feature";
+const EXPLAIN_TEST_FIXTURES: &str =
+    "`#[test_setup]` and `#[test_teardown]` are an unstable feature";
 const EXPLAIN_LOG_SYNTAX: &str =
     "`log_syntax!` is not stable enough for use and is subject to change";
 const EXPLAIN_CONCAT_IDENTS: &str =
@@ -144,6 +146,28 @@ pub fn register_builtins(resolver: &mut dyn syntax::ext::base::Resolver,
             SyntaxExtensionKind::LegacyAttr(Box::new(test::expand_test)), edition
         )
     });
+    register(sym::test_setup, SyntaxExtension {
+        stability: Some(Stability::unstable(
+            sym::test_fixtures,
+            Some(Symbol::intern(EXPLAIN_TEST_FIXTURES)),
+            0,
+        )),
+        allow_internal_unstable: allow_internal_unstable.clone(),
+        ..SyntaxExtension::default(
+            SyntaxExtensionKind::LegacyAttr(Box::new(test::expand_test_setup)), edition
+        )
+    });
+    register(sym::test_teardown, SyntaxExtension {
+        stability: Some(Stability::unstable(
+            sym::test_fixtures,
+            Some(Symbol::intern(EXPLAIN_TEST_FIXTURES)),
+            0,
+        )),
+        allow_internal_unstable: allow_internal_unstable.clone(),
+        ..SyntaxExtension::default(
+            SyntaxExtensionKind::LegacyAttr(Box::new(test::expand_test_teardown)), edition
+        )
+    });
     register(sym::bench, SyntaxExtension {
         allow_internal_unstable,
         ..SyntaxExtension::default(
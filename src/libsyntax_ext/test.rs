@@ -179,6 +179,133 @@ pub fn expand_test_or_bench(
     ]
 }
 
+pub fn expand_test_setup(
+    cx: &mut ExtCtxt<'_>,
+    attr_sp: Span,
+    _meta_item: &ast::MetaItem,
+    item: Annotatable,
+) -> Vec<Annotatable> {
+    expand_fixture(cx, attr_sp, item, "test_setup", "StaticSetupFn")
+}
+
+pub fn expand_test_teardown(
+    cx: &mut ExtCtxt<'_>,
+    attr_sp: Span,
+    _meta_item: &ast::MetaItem,
+    item: Annotatable,
+) -> Vec<Annotatable> {
+    expand_fixture(cx, attr_sp, item, "test_teardown", "StaticTeardownFn")
+}
+
+/// Fixtures are registered with libtest like tests are, with a `testfn` telling
+/// them apart. Their name is used to find the module they apply to.
+fn expand_fixture(
+    cx: &mut ExtCtxt<'_>,
+    attr_sp: Span,
+    item: Annotatable,
+    attr_name: &str,
+    fixture_fn: &str,
+) -> Vec<Annotatable> {
+    // If we're not in test configuration, remove the annotated item
+    if !cx.ecfg.should_test { return vec![]; }
+
+    let item =
+        if let Annotatable::Item(i) = item { i }
+        else {
+            cx.parse_sess.span_diagnostic.span_fatal(item.span(),
+                &format!("#[{}] attribute is only allowed on non associated functions",
+                         attr_name)).raise();
+        };
+
+    if !has_test_signature(cx, &item) {
+        return vec![Annotatable::Item(item)];
+    }
+
+    let ctxt = SyntaxContext::empty().apply_mark(cx.current_expansion.mark);
+    let (sp, attr_sp) = (item.span.with_ctxt(ctxt), attr_sp.with_ctxt(ctxt));
+
+    // Gensym "test" so we can extern crate without conflicting with any local names
+    let test_id = cx.ident_of("test").gensym();
+
+    // creates test::$name
+    let test_path = |name| {
+        cx.path(sp, vec![test_id, cx.ident_of(name)])
+    };
+
+    // creates $name: $expr
+    let field = |name, expr| cx.field_imm(sp, cx.ident_of(name), expr);
+
+    let fixture_const = cx.item(sp, ast::Ident::new(item.ident.name, sp).gensym(),
+        vec![
+            // #[cfg(test)]
+            cx.attribute(attr_sp, cx.meta_list(attr_sp, sym::cfg, vec![
+                cx.meta_list_item_word(attr_sp, sym::test)
+            ])),
+            // #[rustc_test_marker]
+            cx.attribute(attr_sp, cx.meta_word(attr_sp, sym::rustc_test_marker)),
+        ],
+        // const $ident: test::TestDescAndFn =
+        ast::ItemKind::Const(cx.ty(sp, ast::TyKind::Path(None, test_path("TestDescAndFn"))),
+            // test::TestDescAndFn {
+            cx.expr_struct(sp, test_path("TestDescAndFn"), vec![
+                // desc: test::TestDesc {
+                field("desc", cx.expr_struct(sp, test_path("TestDesc"), vec![
+                    // name: "path::to::fixture"
+                    field("name", cx.expr_call(sp, cx.expr_path(test_path("StaticTestName")),
+                        vec![
+                            cx.expr_str(sp, Symbol::intern(&item_path(
+                                // skip the name of the root module
+                                &cx.current_expansion.module.mod_path[1..],
+                                &item.ident
+                            )))
+                        ])),
+                    field("ignore", cx.expr_bool(sp, false)),
+                    field("allow_fail", cx.expr_bool(sp, false)),
+                    // should_panic: test::ShouldPanic::No
+                    field("should_panic", cx.expr_path(cx.path(sp, vec![
+                        test_id, cx.ident_of("ShouldPanic"), cx.ident_of("No")
+                    ]))),
+                // },
+                ])),
+                // testfn: test::StaticSetupFn(...) | test::StaticTeardownFn(...)
+                field("testfn", cx.expr_call(sp, cx.expr_path(test_path(fixture_fn)), vec![
+                    // || {
+                    cx.lambda0(sp,
+                        // test::assert_test_result(
+                        cx.expr_call(sp, cx.expr_path(test_path("assert_test_result")), vec![
+                            // $fixture_fn()
+                            cx.expr_call(sp,
+                                cx.expr_path(cx.path(sp, vec![item.ident])), vec![])
+                        // )
+                        ])
+                    // }
+                    )
+                // )
+                ]))
+            // }
+            ])
+        // }
+        )).map(|mut fc| { fc.vis.node = ast::VisibilityKind::Public; fc });
+
+    // extern crate test as test_gensym
+    let test_extern = cx.item(sp,
+        test_id,
+        vec![],
+        ast::ItemKind::ExternCrate(Some(sym::test))
+    );
+
+    log::debug!("Synthetic fixture item:\n{}\n", pprust::item_to_string(&fixture_const));
+
+    vec![
+        // Access to libtest under a gensymed name
+        Annotatable::Item(test_extern),
+        // The generated fixture
+        Annotatable::Item(fixture_const),
+        // The original item
+        Annotatable::Item(item)
+    ]
+}
+
 fn item_path(mod_path: &[ast::Ident], item_ident: &ast::Ident) -> String {
     mod_path.iter().chain(iter::once(item_ident))
         .map(|x| x.to_string()).collect::<Vec<String>>().join("::")
@@ -655,8 +655,11 @@ symbols! {
         test_2018_feature,
         test_accepted_feature,
         test_case,
+        test_fixtures,
         test_removed_feature,
         test_runner,
+        test_setup,
+        test_teardown,
         then_with,
         thread_local,
         tool_attributes,
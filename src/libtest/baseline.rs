@@ -0,0 +1,186 @@
+//! Persisting benchmark samples between runs (`--save-baseline`) and comparing
+//! later runs against them (`--baseline`).
+//!
+//! A baseline is a plain text file holding the per-iteration timings of each
+//! benchmark, one benchmark per line. Tabs, line breaks and backslashes in
+//! benchmark names are escaped with a backslash. By default, baselines are stored next to
+//! the benchmark executable; the `RUST_BENCH_BASELINE_DIR` environment variable
+//! selects another directory.
+
+use crate::stats::{self, Stats};
+use std::collections::BTreeMap;
+use std::env;
+use std::fs::{self, File};
+use std::io::{self, prelude::*, BufReader};
+use std::path::{Path, PathBuf};
+
+const BASELINE_DIR_ENV_NAME: &str = "RUST_BENCH_BASELINE_DIR";
+const BASELINE_HEADER: &str = "# libtest benchmark baseline v1";
+
+/// Z-score above which a change is considered significant, which corresponds
+/// to a two-sided test at the 5% level.
+const SIGNIFICANT_Z_SCORE: f64 = 1.96;
+
+/// Change of a benchmark's timings relative to a saved baseline.
+#[derive(Clone, Debug, PartialEq)]
+pub struct BaselineComparison {
+    /// Change of the median time per iteration, in percent. Positive values
+    /// mean that the benchmark got slower.
+    pub change_pct: f64,
+    /// Whether the two sets of samples differ significantly, according to a
+    /// Mann-Whitney U test.
+    pub significant: bool,
+}
+
+/// Benchmark samples, keyed by benchmark name.
+pub(crate) struct Baseline {
+    samples: BTreeMap<String, Vec<f64>>,
+}
+
+impl Baseline {
+    pub fn new() -> Baseline {
+        Baseline { samples: BTreeMap::new() }
+    }
+
+    /// Returns whether `name` can be used as the name of a baseline.
+    pub fn is_valid_name(name: &str) -> bool {
+        !name.is_empty() && !name.contains(|c| c == '/' || c == '\\') && name != ".." && name != "."
+    }
+
+    /// Returns the path of the file holding the baseline called `name`.
+    pub fn path(name: &str) -> io::Result<PathBuf> {
+        let dir = match env::var_os(BASELINE_DIR_ENV_NAME) {
+            Some(dir) => PathBuf::from(dir),
+            None => {
+                let mut exe = env::current_exe()?;
+                exe.pop();
+                exe
+            }
+        };
+        Ok(dir.join(format!("{}.baseline", name)))
+    }
+
+    pub fn load(path: &Path) -> io::Result<Baseline> {
+        let file = File::open(path).map_err(|e| {
+            io::Error::new(e.kind(), format!("couldn't open baseline {}: {}", path.display(), e))
+        })?;
+
+        let invalid = |line: usize| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("malformed baseline file {} (line {})", path.display(), line + 1),
+            )
+        };
+
+        let mut baseline = Baseline::new();
+        for (i, line) in BufReader::new(file).lines().enumerate() {
+            let line = line?;
+            if i == 0 {
+                if line != BASELINE_HEADER {
+                    return Err(invalid(i));
+                }
+                continue;
+            }
+
+            let mut parts = line.splitn(2, '\t');
+            let (bench, samples) = match (parts.next(), parts.next()) {
+                (Some(bench), Some(samples)) => (bench, samples),
+                _ => return Err(invalid(i)),
+            };
+            let bench = unescape_name(bench).ok_or_else(|| invalid(i))?;
+            let samples = samples
+                .split(',')
+                .map(|s| s.parse::<f64>())
+                .collect::<Result<Vec<_>, _>>()
+                .map_err(|_| invalid(i))?;
+            baseline.samples.insert(bench, samples);
+        }
+
+        Ok(baseline)
+    }
+
+    /// Writes the samples of this baseline to the file at `path`. The samples
+    /// of benchmarks which weren't run this time are kept, so that a baseline
+    /// can be built up over several filtered runs.
+    pub fn save(self, path: &Path) -> io::Result<()> {
+        let mut merged = match Baseline::load(path) {
+            Ok(existing) => existing,
+            Err(ref e) if e.kind() == io::ErrorKind::NotFound => Baseline::new(),
+            Err(e) => return Err(e),
+        };
+        merged.samples.extend(self.samples);
+
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let mut out = io::BufWriter::new(File::create(path)?);
+        writeln!(out, "{}", BASELINE_HEADER)?;
+        for (bench, samples) in &merged.samples {
+            let samples = samples.iter().map(|s| s.to_string()).collect::<Vec<_>>();
+            writeln!(out, "{}\t{}", escape_name(bench), samples.join(","))?;
+        }
+        out.flush()
+    }
+
+    pub fn insert(&mut self, bench: &str, samples: &[f64]) {
+        if !samples.is_empty() {
+            self.samples.insert(bench.to_owned(), samples.to_vec());
+        }
+    }
+
+    /// Compares `samples` of the benchmark called `bench` with the ones saved
+    /// in this baseline, if any.
+    pub fn compare(&self, bench: &str, samples: &[f64]) -> Option<BaselineComparison> {
+        let old = self.samples.get(bench)?;
+        if old.is_empty() || samples.is_empty() {
+            return None;
+        }
+
+        let old_median = old[..].median();
+        let new_median = samples.median();
+        let change_pct = if old_median == 0.0 {
+            0.0
+        } else {
+            (new_median - old_median) / old_median * 100.0
+        };
+        let z = stats::mann_whitney_u(samples, old);
+
+        Some(BaselineComparison { change_pct, significant: z.abs() > SIGNIFICANT_Z_SCORE })
+    }
+}
+
+/// Escapes the characters of a benchmark name which would break up the line
+/// holding its samples.
+fn escape_name(name: &str) -> String {
+    let mut escaped = String::with_capacity(name.len());
+    for c in name.chars() {
+        match c {
+            '\\' => escaped.push_str("\\\\"),
+            '\t' => escaped.push_str("\\t"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Reverses `escape_name`, returning `None` for unknown escapes.
+fn unescape_name(escaped: &str) -> Option<String> {
+    let mut name = String::with_capacity(escaped.len());
+    let mut chars = escaped.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            name.push(c);
+            continue;
+        }
+        name.push(match chars.next()? {
+            '\\' => '\\',
+            't' => '\t',
+            'n' => '\n',
+            'r' => '\r',
+            _ => return None,
+        });
+    }
+    Some(name)
+}
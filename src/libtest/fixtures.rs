@@ -0,0 +1,223 @@
+//! Setup and teardown functions, marked with `#[test_setup]` and
+//! `#[test_teardown]`.
+//!
+//! A fixture applies to the tests of the module it's defined in, including
+//! its submodules; a fixture at the crate root applies to the whole test
+//! binary. Setup functions run once, right before the first test in their
+//! scope starts, and teardown functions once, right after the last test in
+//! their scope finished, as long as any test in their scope is run. Tests
+//! whose setup failed aren't run, and are reported as failed instead.
+//!
+//! With `--isolate`, fixtures still run once, in the main process, around the
+//! child processes of the tests in their scope. They can prepare state the
+//! children see, like files, but not state living in the test process.
+
+use super::*;
+
+/// Whether a fixture runs before or after the tests of its module.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum FixtureKind {
+    Setup,
+    Teardown,
+}
+
+impl fmt::Display for FixtureKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match *self {
+            FixtureKind::Setup => "setup",
+            FixtureKind::Teardown => "teardown",
+        })
+    }
+}
+
+struct Fixture {
+    desc: TestDesc,
+    kind: FixtureKind,
+    f: fn(),
+    /// What the fixture still waits for before it runs: for a setup, `1`
+    /// until it ran, and for a teardown, the number of tests in its scope
+    /// which haven't finished yet.
+    pending: usize,
+}
+
+impl Fixture {
+    /// The path of the module this fixture applies to, empty for the crate
+    /// root.
+    fn scope(&self) -> &str {
+        let name = self.desc.name.as_slice();
+        match name.rfind("::") {
+            Some(idx) => &name[..idx],
+            None => "",
+        }
+    }
+
+    fn applies_to(&self, name: &str) -> bool {
+        is_in_scope(self.scope(), name)
+    }
+
+    fn depth(&self) -> usize {
+        self.desc.name.as_slice().matches("::").count()
+    }
+}
+
+fn is_in_scope(scope: &str, name: &str) -> bool {
+    scope.is_empty() || (name.starts_with(scope) && name[scope.len()..].starts_with("::"))
+}
+
+pub(crate) struct Fixtures {
+    setups: Vec<Fixture>,
+    teardowns: Vec<Fixture>,
+    /// Scopes whose setup failed, with the name of that setup.
+    failed_scopes: Vec<(String, String)>,
+}
+
+impl Fixtures {
+    /// Takes the fixtures out of `tests`.
+    pub fn split(tests: Vec<TestDescAndFn>) -> (Fixtures, Vec<TestDescAndFn>) {
+        let mut fixtures = Fixtures {
+            setups: Vec::new(),
+            teardowns: Vec::new(),
+            failed_scopes: Vec::new(),
+        };
+        let mut remaining = Vec::with_capacity(tests.len());
+        for test in tests {
+            match test.testfn {
+                StaticSetupFn(f) => fixtures.setups.push(Fixture {
+                    desc: test.desc,
+                    kind: FixtureKind::Setup,
+                    f,
+                    pending: 1,
+                }),
+                StaticTeardownFn(f) => fixtures.teardowns.push(Fixture {
+                    desc: test.desc,
+                    kind: FixtureKind::Teardown,
+                    f,
+                    pending: 0,
+                }),
+                _ => remaining.push(test),
+            }
+        }
+
+        // Outer setups run first and outer teardowns last, and fixtures of
+        // the same module run in alphabetical order.
+        fixtures.setups.sort_by(|a, b| {
+            (a.depth(), a.desc.name.as_slice()).cmp(&(b.depth(), b.desc.name.as_slice()))
+        });
+        fixtures.teardowns.sort_by(|a, b| {
+            (b.depth(), a.desc.name.as_slice()).cmp(&(a.depth(), b.desc.name.as_slice()))
+        });
+
+        (fixtures, remaining)
+    }
+
+    /// Drops the fixtures which don't apply to any of the tests which are
+    /// going to be run, so that filtering out all tests of a module also
+    /// skips its fixtures, and counts the tests each teardown waits for.
+    ///
+    /// Every test named here has to be passed to `run_setups` before it runs
+    /// and to `run_teardowns` once it finished, other tests to neither.
+    pub fn retain_used<'a, I>(&mut self, test_names: I)
+    where
+        I: Iterator<Item = &'a str> + Clone,
+    {
+        let is_used = |fixture: &Fixture| test_names.clone().any(|name| fixture.applies_to(name));
+        self.setups.retain(&is_used);
+        self.teardowns.retain(&is_used);
+        for teardown in &mut self.teardowns {
+            teardown.pending = test_names.clone().filter(|name| teardown.applies_to(name)).count();
+        }
+    }
+
+    /// Returns the name of the failed setup which prevents the test called
+    /// `name` from running, if any.
+    pub fn failed_setup(&self, name: &str) -> Option<&str> {
+        self.failed_scopes
+            .iter()
+            .find(|&&(ref scope, _)| is_in_scope(scope, name))
+            .map(|&(_, ref setup)| &**setup)
+    }
+
+    /// Runs the setups which apply to the test called `name` and haven't run
+    /// yet, outer ones first. The test must not be run if one of them failed,
+    /// which `failed_setup` tells.
+    pub fn run_setups<F>(&mut self, name: &str, nocapture: bool, callback: &mut F) -> io::Result<()>
+    where
+        F: FnMut(TestEvent) -> io::Result<()>,
+    {
+        for i in 0..self.setups.len() {
+            let setup = &self.setups[i];
+            // Setups of modules nested in one whose setup failed are skipped.
+            if setup.pending == 0
+                || !setup.applies_to(name)
+                || self.failed_setup(setup.desc.name.as_slice()).is_some()
+            {
+                continue;
+            }
+
+            let (result, output) = run_fixture(setup, nocapture);
+            if result != TrOk {
+                self.failed_scopes
+                    .push((setup.scope().to_owned(), setup.desc.name.as_slice().to_owned()));
+            }
+            callback(TeFixtureResult(setup.desc.clone(), setup.kind, result, output))?;
+            self.setups[i].pending = 0;
+        }
+        Ok(())
+    }
+
+    /// Records that the test called `name` finished, whether or not it ran,
+    /// and runs the teardowns which were only waiting for it, inner ones
+    /// first.
+    pub fn run_teardowns<F>(
+        &mut self,
+        name: &str,
+        nocapture: bool,
+        callback: &mut F,
+    ) -> io::Result<()>
+    where
+        F: FnMut(TestEvent) -> io::Result<()>,
+    {
+        for i in 0..self.teardowns.len() {
+            let teardown = &mut self.teardowns[i];
+            if teardown.pending == 0 || !teardown.applies_to(name) {
+                continue;
+            }
+            teardown.pending -= 1;
+            // There's nothing to tear down if the setup failed.
+            let teardown = &self.teardowns[i];
+            if teardown.pending > 0 || self.failed_setup(teardown.desc.name.as_slice()).is_some() {
+                continue;
+            }
+
+            let (result, output) = run_fixture(teardown, nocapture);
+            callback(TeFixtureResult(teardown.desc.clone(), teardown.kind, result, output))?;
+        }
+        Ok(())
+    }
+}
+
+/// Runs a fixture on the current thread, capturing its output like the one
+/// of a test.
+fn run_fixture(fixture: &Fixture, nocapture: bool) -> (TestResult, TestOutput) {
+    let data = Arc::new(Mutex::new(TestOutput::new()));
+    let oldio = if !nocapture {
+        Some((
+            io::set_print(Some(Box::new(Sink::stdout(data.clone())))),
+            io::set_panic(Some(Box::new(Sink::stderr(data.clone())))),
+        ))
+    } else {
+        None
+    };
+
+    let f = fixture.f;
+    let result = catch_unwind(AssertUnwindSafe(|| __rust_begin_short_backtrace(f)));
+
+    if let Some((printio, panicio)) = oldio {
+        io::set_print(printio);
+        io::set_panic(panicio);
+    };
+
+    let result = calc_result_untimed(&fixture.desc, result);
+    let output = data.lock().unwrap().clone();
+    (result, output)
+}
@@ -1,12 +1,24 @@
 use super::*;
 
+/// The version of the event schema written by this formatter. It's bumped
+/// whenever an event or field is removed or changes its meaning; new fields
+/// and events may be added without bumping it. The schema is documented in
+/// the rustc book, in `src/doc/rustc/src/tests/json-output.md`.
+const SCHEMA_VERSION: u32 = 1;
+
 pub(crate) struct JsonFormatter<T> {
     out: OutputLocation<T>,
+    suite_start: Option<Instant>,
 }
 
 impl<T: Write> JsonFormatter<T> {
     pub fn new(out: OutputLocation<T>) -> Self {
-        Self { out }
+        Self { out, suite_start: None }
+    }
+
+    #[cfg(test)]
+    pub fn output_location(&self) -> &OutputLocation<T> {
+        &self.out
     }
 
     fn write_message(&mut self, s: &str) -> io::Result<()> {
@@ -21,34 +33,58 @@ impl<T: Write> JsonFormatter<T> {
         ty: &str,
         name: &str,
         evt: &str,
+        exec_time: Option<&TestExecTime>,
+        output: &TestOutput,
         extra: Option<String>,
     ) -> io::Result<()> {
-        if let Some(extras) = extra {
-            self.write_message(&*format!(
-                r#"{{ "type": "{}", "name": "{}", "event": "{}", {} }}"#,
-                ty, name, evt, extras
-            ))
-        } else {
-            self.write_message(&*format!(
-                r#"{{ "type": "{}", "name": "{}", "event": "{}" }}"#,
-                ty, name, evt
-            ))
+        let mut fields = format!(
+            r#""type": "{}", "name": "{}", "event": "{}""#,
+            ty,
+            EscapedString(name),
+            evt
+        );
+        if let Some(time) = exec_time {
+            fields.push_str(&format!(r#", "exec_time": {}"#, fmt_exec_time(time)));
         }
+        if let Some(extra) = extra {
+            fields.push_str(", ");
+            fields.push_str(&extra);
+        }
+        let streams = [("stdout", output.stdout()), ("stderr", output.stderr())];
+        for &(stream, ref captured) in &streams {
+            if !captured.is_empty() {
+                fields.push_str(&format!(
+                    r#", "{}": "{}""#,
+                    stream,
+                    EscapedString(String::from_utf8_lossy(captured))
+                ));
+            }
+        }
+
+        self.write_message(&*format!("{{ {} }}", fields))
     }
 }
 
 impl<T: Write> OutputFormatter for JsonFormatter<T> {
-    fn write_run_start(&mut self, test_count: usize) -> io::Result<()> {
+    fn write_run_start(&mut self, test_count: usize, shuffle_seed: Option<u64>) -> io::Result<()> {
+        self.suite_start = Some(Instant::now());
+        let shuffle_seed_json = match shuffle_seed {
+            Some(seed) => format!(r#", "shuffle_seed": {}"#, seed),
+            None => String::new(),
+        };
         self.write_message(&*format!(
-            r#"{{ "type": "suite", "event": "started", "test_count": {} }}"#,
-            test_count
+            "{{ \"type\": \"suite\", \
+             \"event\": \"started\", \
+             \"schema_version\": {}, \
+             \"test_count\": {}{} }}",
+            SCHEMA_VERSION, test_count, shuffle_seed_json
         ))
     }
 
     fn write_test_start(&mut self, desc: &TestDesc) -> io::Result<()> {
         self.write_message(&*format!(
             r#"{{ "type": "test", "event": "started", "name": "{}" }}"#,
-            desc.name
+            EscapedString(desc.name.as_slice())
         ))
     }
 
@@ -56,35 +92,46 @@ impl<T: Write> OutputFormatter for JsonFormatter<T> {
         &mut self,
         desc: &TestDesc,
         result: &TestResult,
-        stdout: &[u8],
+        exec_time: Option<&TestExecTime>,
+        output: &TestOutput,
     ) -> io::Result<()> {
+        let name = desc.name.as_slice();
         match *result {
-            TrOk => self.write_event("test", desc.name.as_slice(), "ok", None),
-
-            TrFailed => {
-                let extra_data = if stdout.len() > 0 {
-                    Some(format!(
-                        r#""stdout": "{}""#,
-                        EscapedString(String::from_utf8_lossy(stdout))
-                    ))
-                } else {
-                    None
-                };
+            TrOk => self.write_event("test", name, "ok", exec_time, output, None),
 
-                self.write_event("test", desc.name.as_slice(), "failed", extra_data)
-            }
+            TrFailed => self.write_event("test", name, "failed", exec_time, output, None),
 
             TrFailedMsg(ref m) => self.write_event(
                 "test",
-                desc.name.as_slice(),
+                name,
                 "failed",
+                exec_time,
+                output,
                 Some(format!(r#""message": "{}""#, EscapedString(m))),
             ),
 
-            TrIgnored => self.write_event("test", desc.name.as_slice(), "ignored", None),
+            TrTimedFail => self.write_event(
+                "test",
+                name,
+                "failed",
+                exec_time,
+                output,
+                Some(r#""reason": "time limit exceeded""#.to_owned()),
+            ),
+
+            TrFlaky(attempts) => self.write_event(
+                "test",
+                name,
+                "flaky",
+                exec_time,
+                output,
+                Some(format!(r#""attempts": {}"#, attempts)),
+            ),
+
+            TrIgnored => self.write_event("test", name, "ignored", exec_time, output, None),
 
             TrAllowedFail => {
-                self.write_event("test", desc.name.as_slice(), "allowed_failure", None)
+                self.write_event("test", name, "allowed_failure", exec_time, output, None)
             }
 
             TrBench(ref bs) => {
@@ -97,12 +144,24 @@ impl<T: Write> OutputFormatter for JsonFormatter<T> {
                     format!(r#", "mib_per_second": {}"#, bs.mb_s)
                 };
 
+                let baseline = match bs.baseline {
+                    Some(ref comparison) => format!(
+                        r#", "baseline_change_pct": {:.2}, "baseline_significant": {}"#,
+                        comparison.change_pct, comparison.significant
+                    ),
+                    None => String::new(),
+                };
+
                 let line = format!(
                     "{{ \"type\": \"bench\", \
                      \"name\": \"{}\", \
                      \"median\": {}, \
-                     \"deviation\": {}{} }}",
-                    desc.name, median, deviation, mbps
+                     \"deviation\": {}{}{} }}",
+                    EscapedString(name),
+                    median,
+                    deviation,
+                    mbps,
+                    baseline
                 );
 
                 self.write_message(&*line)
@@ -113,33 +172,70 @@ impl<T: Write> OutputFormatter for JsonFormatter<T> {
     fn write_timeout(&mut self, desc: &TestDesc) -> io::Result<()> {
         self.write_message(&*format!(
             r#"{{ "type": "test", "event": "timeout", "name": "{}" }}"#,
-            desc.name
+            EscapedString(desc.name.as_slice())
         ))
     }
 
+    fn write_fixture_result(
+        &mut self,
+        desc: &TestDesc,
+        kind: FixtureKind,
+        result: &TestResult,
+        output: &TestOutput,
+    ) -> io::Result<()> {
+        let ty = kind.to_string();
+        let name = desc.name.as_slice();
+        match *result {
+            TrOk => self.write_event(&ty, name, "ok", None, output, None),
+            TrFailedMsg(ref m) => self.write_event(
+                &ty,
+                name,
+                "failed",
+                None,
+                output,
+                Some(format!(r#""message": "{}""#, EscapedString(m))),
+            ),
+            _ => self.write_event(&ty, name, "failed", None, output, None),
+        }
+    }
+
     fn write_run_finish(&mut self, state: &ConsoleTestState) -> io::Result<bool> {
+        let suite_time = self
+            .suite_start
+            .map(|start| start.elapsed())
+            .unwrap_or_default();
+
         self.write_message(&*format!(
             "{{ \"type\": \"suite\", \
              \"event\": \"{}\", \
              \"passed\": {}, \
              \"failed\": {}, \
              \"allowed_fail\": {}, \
+             \"flaky\": {}, \
              \"ignored\": {}, \
              \"measured\": {}, \
-             \"filtered_out\": {} }}",
-            if state.failed == 0 { "ok" } else { "failed" },
+             \"filtered_out\": {}, \
+             \"exec_time\": {} }}",
+            if state.is_success() { "ok" } else { "failed" },
             state.passed,
             state.failed + state.allowed_fail,
             state.allowed_fail,
+            state.flaky,
             state.ignored,
             state.measured,
-            state.filtered_out
+            state.filtered_out,
+            fmt_exec_time(&TestExecTime(suite_time))
         ))?;
 
-        Ok(state.failed == 0)
+        Ok(state.is_success())
     }
 }
 
+/// Formats an execution time as a number of seconds, with millisecond precision.
+fn fmt_exec_time(exec_time: &TestExecTime) -> String {
+    format!("{}.{:03}", exec_time.0.as_secs(), exec_time.0.subsec_millis())
+}
+
 /// A formatting utility used to print strings with characters in need of escaping.
 /// Base code taken form `libserialize::json::escape_str`
 struct EscapedString<S: AsRef<str>>(S);
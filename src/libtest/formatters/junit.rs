@@ -0,0 +1,280 @@
+use super::*;
+
+/// Buffers every test result and writes a single JUnit XML document once the
+/// run has finished, since the `<testsuite>` element has to carry the totals.
+pub(crate) struct JunitFormatter<T> {
+    out: OutputLocation<T>,
+    suite_name: String,
+    suite_start: Option<Instant>,
+    shuffle_seed: Option<u64>,
+    results: Vec<(TestDesc, TestResult, Duration, TestOutput)>,
+    fixture_errors: Vec<(TestDesc, FixtureKind, TestResult, TestOutput)>,
+}
+
+impl<T: Write> JunitFormatter<T> {
+    pub fn new(out: OutputLocation<T>) -> Self {
+        let suite_name = env::current_exe()
+            .ok()
+            .and_then(|exe| exe.file_stem().map(|s| s.to_string_lossy().into_owned()))
+            .unwrap_or_else(|| "test".to_owned());
+
+        Self {
+            out,
+            suite_name,
+            suite_start: None,
+            shuffle_seed: None,
+            results: Vec::new(),
+            fixture_errors: Vec::new(),
+        }
+    }
+
+    #[cfg(test)]
+    pub fn output_location(&self) -> &OutputLocation<T> {
+        &self.out
+    }
+
+    fn write_message(&mut self, s: &str) -> io::Result<()> {
+        self.out.write_all(s.as_ref())?;
+        self.out.write_all(b"\n")
+    }
+
+    fn write_testcase(
+        &mut self,
+        desc: &TestDesc,
+        result: &TestResult,
+        duration: Duration,
+        output: &TestOutput,
+    ) -> io::Result<()> {
+        let child = match *result {
+            TrOk | TrBench(_) => None,
+            // Follows the Maven Surefire extension to JUnit, with one element
+            // for every failed attempt of a test which eventually passed.
+            TrFlaky(attempts) => Some(
+                (1..attempts)
+                    .map(|attempt| {
+                        format!(
+                            r#"<flakyFailure type="assert" message="failed attempt {}"/>"#,
+                            attempt
+                        )
+                    })
+                    .collect::<Vec<_>>()
+                    .join(""),
+            ),
+            TrFailed => Some(r#"<failure type="assert"/>"#.to_owned()),
+            TrFailedMsg(ref msg) => Some(format!(
+                r#"<failure type="assert" message="{}"/>"#,
+                XmlEscaped(msg)
+            )),
+            TrTimedFail => {
+                Some(r#"<failure type="timeout" message="time limit exceeded"/>"#.to_owned())
+            }
+            TrIgnored => Some("<skipped/>".to_owned()),
+            TrAllowedFail => Some(r#"<skipped message="failed (allowed)"/>"#.to_owned()),
+        };
+        self.write_element(desc, duration, child, output)
+    }
+
+    /// Failed fixtures are reported as errors, as they aren't a failure of
+    /// the tests themselves.
+    fn write_fixture_error(
+        &mut self,
+        desc: &TestDesc,
+        kind: FixtureKind,
+        result: &TestResult,
+        output: &TestOutput,
+    ) -> io::Result<()> {
+        let child = match *result {
+            TrFailedMsg(ref msg) => {
+                format!(r#"<error type="{}" message="{}"/>"#, kind, XmlEscaped(msg))
+            }
+            _ => format!(r#"<error type="{}"/>"#, kind),
+        };
+        self.write_element(desc, Duration::default(), Some(child), output)
+    }
+
+    fn write_element(
+        &mut self,
+        desc: &TestDesc,
+        duration: Duration,
+        child: Option<String>,
+        output: &TestOutput,
+    ) -> io::Result<()> {
+        let (class_name, test_name) = split_class_name(&self.suite_name, desc.name.as_slice());
+        let header = format!(
+            r#"<testcase classname="{}" name="{}" time="{}""#,
+            XmlEscaped(class_name),
+            XmlEscaped(test_name),
+            fmt_secs(duration)
+        );
+
+        if child.is_none() && output.is_empty() {
+            return self.write_message(&*format!("{}/>", header));
+        }
+
+        self.write_message(&*format!("{}>", header))?;
+        if let Some(child) = child {
+            self.write_message(&*child)?;
+        }
+        self.write_captured("system-out", &output.stdout())?;
+        self.write_captured("system-err", &output.stderr())?;
+        self.write_message("</testcase>")
+    }
+
+    fn write_captured(&mut self, element: &str, captured: &[u8]) -> io::Result<()> {
+        if captured.is_empty() {
+            return Ok(());
+        }
+        self.write_message(&*format!(
+            "<{}>{}</{}>",
+            element,
+            XmlEscaped(String::from_utf8_lossy(captured)),
+            element
+        ))
+    }
+}
+
+impl<T: Write> OutputFormatter for JunitFormatter<T> {
+    fn write_run_start(&mut self, _test_count: usize, shuffle_seed: Option<u64>) -> io::Result<()> {
+        self.suite_start = Some(Instant::now());
+        self.shuffle_seed = shuffle_seed;
+        Ok(())
+    }
+
+    fn write_test_start(&mut self, _desc: &TestDesc) -> io::Result<()> {
+        Ok(())
+    }
+
+    fn write_result(
+        &mut self,
+        desc: &TestDesc,
+        result: &TestResult,
+        exec_time: Option<&TestExecTime>,
+        output: &TestOutput,
+    ) -> io::Result<()> {
+        let duration = exec_time.map(|time| time.0).unwrap_or_default();
+        self.results
+            .push((desc.clone(), result.clone(), duration, output.clone()));
+        Ok(())
+    }
+
+    fn write_timeout(&mut self, _desc: &TestDesc) -> io::Result<()> {
+        // JUnit has no notion of a warning, the final duration is reported instead.
+        Ok(())
+    }
+
+    fn write_fixture_result(
+        &mut self,
+        desc: &TestDesc,
+        kind: FixtureKind,
+        result: &TestResult,
+        output: &TestOutput,
+    ) -> io::Result<()> {
+        if *result != TrOk {
+            self.fixture_errors
+                .push((desc.clone(), kind, result.clone(), output.clone()));
+        }
+        Ok(())
+    }
+
+    fn write_run_finish(&mut self, state: &ConsoleTestState) -> io::Result<bool> {
+        let suite_time = self
+            .suite_start
+            .map(|start| start.elapsed())
+            .unwrap_or_default();
+
+        self.write_message(r#"<?xml version="1.0" encoding="UTF-8"?>"#)?;
+        self.write_message("<testsuites>")?;
+        self.write_message(&*format!(
+            "<testsuite name=\"{}\" package=\"{}\" id=\"0\" \
+             errors=\"{}\" \
+             failures=\"{}\" \
+             tests=\"{}\" \
+             skipped=\"{}\" \
+             time=\"{}\">",
+            XmlEscaped(&self.suite_name),
+            XmlEscaped(&self.suite_name),
+            self.fixture_errors.len(),
+            state.failed,
+            state.total,
+            state.ignored + state.allowed_fail,
+            fmt_secs(suite_time)
+        ))?;
+        if let Some(seed) = self.shuffle_seed {
+            self.write_message("<properties>")?;
+            self.write_message(&*format!(r#"<property name="shuffle_seed" value="{}"/>"#, seed))?;
+            self.write_message("</properties>")?;
+        }
+
+        let mut results = std::mem::replace(&mut self.results, Vec::new());
+        results.sort_by(|a, b| a.0.name.as_slice().cmp(b.0.name.as_slice()));
+        for (desc, result, duration, output) in &results {
+            self.write_testcase(desc, result, *duration, output)?;
+        }
+        let fixture_errors = std::mem::replace(&mut self.fixture_errors, Vec::new());
+        for (desc, kind, result, output) in &fixture_errors {
+            self.write_fixture_error(desc, *kind, result, output)?;
+        }
+
+        self.write_message("</testsuite>")?;
+        self.write_message("</testsuites>")?;
+
+        Ok(state.is_success())
+    }
+}
+
+/// Splits a test path such as `foo::bar::baz` into the JUnit class name
+/// (`foo::bar`) and test name (`baz`). Tests at the crate root are attributed
+/// to the suite itself.
+fn split_class_name<'a>(suite_name: &'a str, test_name: &'a str) -> (&'a str, &'a str) {
+    match test_name.rfind("::") {
+        Some(idx) => (&test_name[..idx], &test_name[idx + 2..]),
+        None => (suite_name, test_name),
+    }
+}
+
+fn fmt_secs(duration: Duration) -> String {
+    format!("{}.{:03}", duration.as_secs(), duration.subsec_millis())
+}
+
+/// A formatting utility used to print strings as XML character data or
+/// attribute values. Line breaks and tabs are written as character
+/// references, so every element stays on one line and attribute values keep
+/// them, and characters which can't appear in an XML 1.0 document at all are
+/// replaced with U+FFFD.
+struct XmlEscaped<S: AsRef<str>>(S);
+
+impl<S: AsRef<str>> ::std::fmt::Display for XmlEscaped<S> {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+        let s = self.0.as_ref();
+        let mut start = 0;
+
+        for (i, c) in s.char_indices() {
+            let escaped = match c {
+                '<' => "&lt;",
+                '>' => "&gt;",
+                '&' => "&amp;",
+                '"' => "&quot;",
+                '\'' => "&apos;",
+                '\t' => "&#9;",
+                '\n' => "&#10;",
+                '\r' => "&#13;",
+                '\x00'..='\x1f' | '\u{fffe}' | '\u{ffff}' => "\u{fffd}",
+                _ => continue,
+            };
+
+            if start < i {
+                f.write_str(&s[start..i])?;
+            }
+
+            f.write_str(escaped)?;
+
+            start = i + c.len_utf8();
+        }
+
+        if start != s.len() {
+            f.write_str(&s[start..])?;
+        }
+
+        Ok(())
+    }
+}
@@ -2,21 +2,31 @@ use super::*;
 
 mod pretty;
 mod json;
+mod junit;
 mod terse;
 
 pub(crate) use self::pretty::PrettyFormatter;
 pub(crate) use self::json::JsonFormatter;
+pub(crate) use self::junit::JunitFormatter;
 pub(crate) use self::terse::TerseFormatter;
 
 pub(crate) trait OutputFormatter {
-    fn write_run_start(&mut self, test_count: usize) -> io::Result<()>;
+    fn write_run_start(&mut self, test_count: usize, shuffle_seed: Option<u64>) -> io::Result<()>;
     fn write_test_start(&mut self, desc: &TestDesc) -> io::Result<()>;
     fn write_timeout(&mut self, desc: &TestDesc) -> io::Result<()>;
     fn write_result(
         &mut self,
         desc: &TestDesc,
         result: &TestResult,
-        stdout: &[u8],
+        exec_time: Option<&TestExecTime>,
+        output: &TestOutput,
+    ) -> io::Result<()>;
+    fn write_fixture_result(
+        &mut self,
+        desc: &TestDesc,
+        kind: FixtureKind,
+        result: &TestResult,
+        output: &TestOutput,
     ) -> io::Result<()>;
     fn write_run_finish(&mut self, state: &ConsoleTestState) -> io::Result<bool>;
 }
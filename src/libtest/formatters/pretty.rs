@@ -8,6 +8,8 @@ pub(crate) struct PrettyFormatter<T> {
     max_name_len: usize,
 
     is_multithreaded: bool,
+
+    time_options: Option<TestTimeOptions>,
 }
 
 impl<T: Write> PrettyFormatter<T> {
@@ -16,12 +18,14 @@ impl<T: Write> PrettyFormatter<T> {
         use_color: bool,
         max_name_len: usize,
         is_multithreaded: bool,
+        time_options: Option<TestTimeOptions>,
     ) -> Self {
         PrettyFormatter {
             out,
             use_color,
             max_name_len,
             is_multithreaded,
+            time_options,
         }
     }
 
@@ -42,10 +46,18 @@ impl<T: Write> PrettyFormatter<T> {
         self.write_short_result("ignored", term::color::YELLOW)
     }
 
+    pub fn write_flaky(&mut self) -> io::Result<()> {
+        self.write_short_result("ok (flaky)", term::color::YELLOW)
+    }
+
     pub fn write_allowed_fail(&mut self) -> io::Result<()> {
         self.write_short_result("FAILED (allowed)", term::color::YELLOW)
     }
 
+    pub fn write_time_failed(&mut self) -> io::Result<()> {
+        self.write_short_result("FAILED (time limit exceeded)", term::color::RED)
+    }
+
     pub fn write_bench(&mut self) -> io::Result<()> {
         self.write_pretty("bench", term::color::CYAN)
     }
@@ -55,8 +67,7 @@ impl<T: Write> PrettyFormatter<T> {
         result: &str,
         color: term::color::Color,
     ) -> io::Result<()> {
-        self.write_pretty(result, color)?;
-        self.write_plain("\n")
+        self.write_pretty(result, color)
     }
 
     pub fn write_pretty(&mut self, word: &str, color: term::color::Color) -> io::Result<()> {
@@ -110,6 +121,57 @@ impl<T: Write> PrettyFormatter<T> {
         Ok(())
     }
 
+    pub fn write_flaky_tests(&mut self, state: &ConsoleTestState) -> io::Result<()> {
+        self.write_plain("\nflaky tests:\n")?;
+        let mut flaky = Vec::new();
+        let mut flaky_out = String::new();
+        for &(ref f, ref stdout) in &state.flaky_tests {
+            flaky.push(f.name.to_string());
+            if !stdout.is_empty() {
+                flaky_out.push_str(&format!("---- {} stdout (failed attempt) ----\n", f.name));
+                let output = String::from_utf8_lossy(stdout);
+                flaky_out.push_str(&output);
+                flaky_out.push_str("\n");
+            }
+        }
+        if !flaky_out.is_empty() {
+            self.write_plain("\n")?;
+            self.write_plain(&flaky_out)?;
+        }
+
+        self.write_plain("\nflaky tests:\n")?;
+        flaky.sort();
+        for name in &flaky {
+            self.write_plain(&format!("    {}\n", name))?;
+        }
+        Ok(())
+    }
+
+    pub fn write_fixture_failures(&mut self, state: &ConsoleTestState) -> io::Result<()> {
+        self.write_plain("\nfixture failures:\n")?;
+        let mut failures = Vec::new();
+        let mut fail_out = String::new();
+        for &(ref f, kind, ref stdout) in &state.fixture_failures {
+            failures.push(format!("{} ({})", f.name, kind));
+            if !stdout.is_empty() {
+                fail_out.push_str(&format!("---- {} ({}) stdout ----\n", f.name, kind));
+                let output = String::from_utf8_lossy(stdout);
+                fail_out.push_str(&output);
+                fail_out.push_str("\n");
+            }
+        }
+        if !fail_out.is_empty() {
+            self.write_plain("\n")?;
+            self.write_plain(&fail_out)?;
+        }
+
+        self.write_plain("\nfixture failures:\n")?;
+        for name in &failures {
+            self.write_plain(&format!("    {}\n", name))?;
+        }
+        Ok(())
+    }
+
     pub fn write_failures(&mut self, state: &ConsoleTestState) -> io::Result<()> {
         self.write_plain("\nfailures:\n")?;
         let mut failures = Vec::new();
@@ -136,6 +198,31 @@ impl<T: Write> PrettyFormatter<T> {
         Ok(())
     }
 
+    fn write_time(&mut self, exec_time: Option<&TestExecTime>) -> io::Result<()> {
+        if let (Some(opts), Some(time)) = (self.time_options, exec_time) {
+            let time_str = format!(" <{}>", time);
+
+            let color = if opts.colored {
+                if opts.is_critical(time) {
+                    Some(term::color::RED)
+                } else if opts.is_warn(time) {
+                    Some(term::color::YELLOW)
+                } else {
+                    None
+                }
+            } else {
+                None
+            };
+
+            match color {
+                Some(color) => self.write_pretty(&time_str, color)?,
+                None => self.write_plain(&time_str)?,
+            }
+        }
+
+        Ok(())
+    }
+
     fn write_test_name(&mut self, desc: &TestDesc) -> io::Result<()> {
         let name = desc.padded_name(self.max_name_len, desc.name.padding());
         self.write_plain(&format!("test {} ... ", name))?;
@@ -145,9 +232,13 @@ impl<T: Write> PrettyFormatter<T> {
 }
 
 impl<T: Write> OutputFormatter for PrettyFormatter<T> {
-    fn write_run_start(&mut self, test_count: usize) -> io::Result<()> {
+    fn write_run_start(&mut self, test_count: usize, shuffle_seed: Option<u64>) -> io::Result<()> {
         let noun = if test_count != 1 { "tests" } else { "test" };
-        self.write_plain(&format!("\nrunning {} {}\n", test_count, noun))
+        let shuffle_seed_msg = match shuffle_seed {
+            Some(seed) => format!(" (shuffle seed: {})", seed),
+            None => String::new(),
+        };
+        self.write_plain(&format!("\nrunning {} {}{}\n", test_count, noun, shuffle_seed_msg))
     }
 
     fn write_test_start(&mut self, desc: &TestDesc) -> io::Result<()> {
@@ -162,21 +253,32 @@ impl<T: Write> OutputFormatter for PrettyFormatter<T> {
         Ok(())
     }
 
-    fn write_result(&mut self, desc: &TestDesc, result: &TestResult, _: &[u8]) -> io::Result<()> {
+    fn write_result(
+        &mut self,
+        desc: &TestDesc,
+        result: &TestResult,
+        exec_time: Option<&TestExecTime>,
+        _: &TestOutput,
+    ) -> io::Result<()> {
         if self.is_multithreaded {
             self.write_test_name(desc)?;
         }
 
         match *result {
-            TrOk => self.write_ok(),
-            TrFailed | TrFailedMsg(_) => self.write_failed(),
-            TrIgnored => self.write_ignored(),
-            TrAllowedFail => self.write_allowed_fail(),
+            TrOk => self.write_ok()?,
+            TrFlaky(_) => self.write_flaky()?,
+            TrFailed | TrFailedMsg(_) => self.write_failed()?,
+            TrIgnored => self.write_ignored()?,
+            TrAllowedFail => self.write_allowed_fail()?,
+            TrTimedFail => self.write_time_failed()?,
             TrBench(ref bs) => {
                 self.write_bench()?;
-                self.write_plain(&format!(": {}\n", fmt_bench_samples(bs)))
+                self.write_plain(&format!(": {}", fmt_bench_samples(bs)))?;
             }
         }
+
+        self.write_time(exec_time)?;
+        self.write_plain("\n")
     }
 
     fn write_timeout(&mut self, desc: &TestDesc) -> io::Result<()> {
@@ -190,14 +292,35 @@ impl<T: Write> OutputFormatter for PrettyFormatter<T> {
         ))
     }
 
+    fn write_fixture_result(
+        &mut self,
+        desc: &TestDesc,
+        kind: FixtureKind,
+        result: &TestResult,
+        _: &TestOutput,
+    ) -> io::Result<()> {
+        self.write_plain(&format!("{} {} ... ", kind, desc.name))?;
+        match *result {
+            TrOk => self.write_ok()?,
+            _ => self.write_failed()?,
+        }
+        self.write_plain("\n")
+    }
+
     fn write_run_finish(&mut self, state: &ConsoleTestState) -> io::Result<bool> {
         if state.options.display_output {
             self.write_successes(state)?;
         }
-        let success = state.failed == 0;
-        if !success {
+        if !state.flaky_tests.is_empty() {
+            self.write_flaky_tests(state)?;
+        }
+        if !state.fixture_failures.is_empty() {
+            self.write_fixture_failures(state)?;
+        }
+        if state.failed != 0 {
             self.write_failures(state)?;
         }
+        let success = state.is_success();
 
         self.write_plain("\ntest result: ")?;
 
@@ -208,10 +331,15 @@ impl<T: Write> OutputFormatter for PrettyFormatter<T> {
             self.write_pretty("FAILED", term::color::RED)?;
         }
 
+        let passed = if state.flaky > 0 {
+            format!("{} passed ({} flaky)", state.passed, state.flaky)
+        } else {
+            format!("{} passed", state.passed)
+        };
         let s = if state.allowed_fail > 0 {
             format!(
-                ". {} passed; {} failed ({} allowed); {} ignored; {} measured; {} filtered out\n\n",
-                state.passed,
+                ". {}; {} failed ({} allowed); {} ignored; {} measured; {} filtered out\n\n",
+                passed,
                 state.failed + state.allowed_fail,
                 state.allowed_fail,
                 state.ignored,
@@ -220,8 +348,8 @@ impl<T: Write> OutputFormatter for PrettyFormatter<T> {
             )
         } else {
             format!(
-                ". {} passed; {} failed; {} ignored; {} measured; {} filtered out\n\n",
-                state.passed, state.failed, state.ignored, state.measured, state.filtered_out
+                ". {}; {} failed; {} ignored; {} measured; {} filtered out\n\n",
+                passed, state.failed, state.ignored, state.measured, state.filtered_out
             )
         };
 
@@ -9,6 +9,11 @@ pub(crate) struct TerseFormatter<T> {
 
     test_count: usize,
     total_test_count: usize,
+
+    time_options: Option<TestTimeOptions>,
+    /// Tests which exceeded the warning time threshold, reported at the end
+    /// of the run since there's no room for them on the progress line
+    slow_tests: Vec<(TestDesc, TestExecTime)>,
 }
 
 impl<T: Write> TerseFormatter<T> {
@@ -17,6 +22,7 @@ impl<T: Write> TerseFormatter<T> {
         use_color: bool,
         max_name_len: usize,
         is_multithreaded: bool,
+        time_options: Option<TestTimeOptions>,
     ) -> Self {
         TerseFormatter {
             out,
@@ -25,6 +31,8 @@ impl<T: Write> TerseFormatter<T> {
             is_multithreaded,
             test_count: 0,
             total_test_count: 0, // initialized later, when write_run_start is called
+            time_options,
+            slow_tests: Vec::new(),
         }
     }
 
@@ -40,6 +48,10 @@ impl<T: Write> TerseFormatter<T> {
         self.write_short_result("i", term::color::YELLOW)
     }
 
+    pub fn write_flaky(&mut self) -> io::Result<()> {
+        self.write_short_result("r", term::color::YELLOW)
+    }
+
     pub fn write_allowed_fail(&mut self) -> io::Result<()> {
         self.write_short_result("a", term::color::YELLOW)
     }
@@ -117,6 +129,57 @@ impl<T: Write> TerseFormatter<T> {
         Ok(())
     }
 
+    pub fn write_flaky_tests(&mut self, state: &ConsoleTestState) -> io::Result<()> {
+        self.write_plain("\nflaky tests:\n")?;
+        let mut flaky = Vec::new();
+        let mut flaky_out = String::new();
+        for &(ref f, ref stdout) in &state.flaky_tests {
+            flaky.push(f.name.to_string());
+            if !stdout.is_empty() {
+                flaky_out.push_str(&format!("---- {} stdout (failed attempt) ----\n", f.name));
+                let output = String::from_utf8_lossy(stdout);
+                flaky_out.push_str(&output);
+                flaky_out.push_str("\n");
+            }
+        }
+        if !flaky_out.is_empty() {
+            self.write_plain("\n")?;
+            self.write_plain(&flaky_out)?;
+        }
+
+        self.write_plain("\nflaky tests:\n")?;
+        flaky.sort();
+        for name in &flaky {
+            self.write_plain(&format!("    {}\n", name))?;
+        }
+        Ok(())
+    }
+
+    pub fn write_fixture_failures(&mut self, state: &ConsoleTestState) -> io::Result<()> {
+        self.write_plain("\nfixture failures:\n")?;
+        let mut failures = Vec::new();
+        let mut fail_out = String::new();
+        for &(ref f, kind, ref stdout) in &state.fixture_failures {
+            failures.push(format!("{} ({})", f.name, kind));
+            if !stdout.is_empty() {
+                fail_out.push_str(&format!("---- {} ({}) stdout ----\n", f.name, kind));
+                let output = String::from_utf8_lossy(stdout);
+                fail_out.push_str(&output);
+                fail_out.push_str("\n");
+            }
+        }
+        if !fail_out.is_empty() {
+            self.write_plain("\n")?;
+            self.write_plain(&fail_out)?;
+        }
+
+        self.write_plain("\nfixture failures:\n")?;
+        for name in &failures {
+            self.write_plain(&format!("    {}\n", name))?;
+        }
+        Ok(())
+    }
+
     pub fn write_failures(&mut self, state: &ConsoleTestState) -> io::Result<()> {
         self.write_plain("\nfailures:\n")?;
         let mut failures = Vec::new();
@@ -143,6 +206,16 @@ impl<T: Write> TerseFormatter<T> {
         Ok(())
     }
 
+    pub fn write_slow_tests(&mut self) -> io::Result<()> {
+        self.write_plain("\nslow tests:\n")?;
+        let mut slow_tests = std::mem::replace(&mut self.slow_tests, Vec::new());
+        slow_tests.sort_by(|a, b| a.0.name.as_slice().cmp(b.0.name.as_slice()));
+        for (desc, exec_time) in &slow_tests {
+            self.write_plain(&format!("    {} <{}>\n", desc.name, exec_time))?;
+        }
+        Ok(())
+    }
+
     fn write_test_name(&mut self, desc: &TestDesc) -> io::Result<()> {
         let name = desc.padded_name(self.max_name_len, desc.name.padding());
         self.write_plain(&format!("test {} ... ", name))?;
@@ -152,10 +225,14 @@ impl<T: Write> TerseFormatter<T> {
 }
 
 impl<T: Write> OutputFormatter for TerseFormatter<T> {
-    fn write_run_start(&mut self, test_count: usize) -> io::Result<()> {
+    fn write_run_start(&mut self, test_count: usize, shuffle_seed: Option<u64>) -> io::Result<()> {
         self.total_test_count = test_count;
         let noun = if test_count != 1 { "tests" } else { "test" };
-        self.write_plain(&format!("\nrunning {} {}\n", test_count, noun))
+        let shuffle_seed_msg = match shuffle_seed {
+            Some(seed) => format!(" (shuffle seed: {})", seed),
+            None => String::new(),
+        };
+        self.write_plain(&format!("\nrunning {} {}{}\n", test_count, noun, shuffle_seed_msg))
     }
 
     fn write_test_start(&mut self, desc: &TestDesc) -> io::Result<()> {
@@ -170,10 +247,23 @@ impl<T: Write> OutputFormatter for TerseFormatter<T> {
         Ok(())
     }
 
-    fn write_result(&mut self, desc: &TestDesc, result: &TestResult, _: &[u8]) -> io::Result<()> {
+    fn write_result(
+        &mut self,
+        desc: &TestDesc,
+        result: &TestResult,
+        exec_time: Option<&TestExecTime>,
+        _: &TestOutput,
+    ) -> io::Result<()> {
+        if let (Some(opts), Some(time)) = (self.time_options, exec_time) {
+            if opts.is_warn(time) {
+                self.slow_tests.push((desc.clone(), time.clone()));
+            }
+        }
+
         match *result {
             TrOk => self.write_ok(),
-            TrFailed | TrFailedMsg(_) => self.write_failed(),
+            TrFlaky(_) => self.write_flaky(),
+            TrFailed | TrFailedMsg(_) | TrTimedFail => self.write_failed(),
             TrIgnored => self.write_ignored(),
             TrAllowedFail => self.write_allowed_fail(),
             TrBench(ref bs) => {
@@ -193,14 +283,34 @@ impl<T: Write> OutputFormatter for TerseFormatter<T> {
         ))
     }
 
+    fn write_fixture_result(
+        &mut self,
+        _: &TestDesc,
+        _: FixtureKind,
+        _: &TestResult,
+        _: &TestOutput,
+    ) -> io::Result<()> {
+        // Failed fixtures are listed at the end of the run.
+        Ok(())
+    }
+
     fn write_run_finish(&mut self, state: &ConsoleTestState) -> io::Result<bool> {
         if state.options.display_output {
             self.write_outputs(state)?;
         }
-        let success = state.failed == 0;
-        if !success {
+        if !self.slow_tests.is_empty() {
+            self.write_slow_tests()?;
+        }
+        if !state.flaky_tests.is_empty() {
+            self.write_flaky_tests(state)?;
+        }
+        if !state.fixture_failures.is_empty() {
+            self.write_fixture_failures(state)?;
+        }
+        if state.failed != 0 {
             self.write_failures(state)?;
         }
+        let success = state.is_success();
 
         self.write_plain("\ntest result: ")?;
 
@@ -211,10 +321,15 @@ impl<T: Write> OutputFormatter for TerseFormatter<T> {
             self.write_pretty("FAILED", term::color::RED)?;
         }
 
+        let passed = if state.flaky > 0 {
+            format!("{} passed ({} flaky)", state.passed, state.flaky)
+        } else {
+            format!("{} passed", state.passed)
+        };
         let s = if state.allowed_fail > 0 {
             format!(
-                ". {} passed; {} failed ({} allowed); {} ignored; {} measured; {} filtered out\n\n",
-                state.passed,
+                ". {}; {} failed ({} allowed); {} ignored; {} measured; {} filtered out\n\n",
+                passed,
                 state.failed + state.allowed_fail,
                 state.allowed_fail,
                 state.ignored,
@@ -223,8 +338,8 @@ impl<T: Write> OutputFormatter for TerseFormatter<T> {
             )
         } else {
             format!(
-                ". {} passed; {} failed; {} ignored; {} measured; {} filtered out\n\n",
-                state.passed, state.failed, state.ignored, state.measured, state.filtered_out
+                ". {}; {} failed; {} ignored; {} measured; {} filtered out\n\n",
+                passed, state.failed, state.ignored, state.measured, state.filtered_out
             )
         };
 
@@ -56,38 +56,61 @@ pub use self::TestResult::*;
 use std::any::Any;
 use std::borrow::Cow;
 use std::cmp;
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, HashMap};
 use std::env;
 use std::fmt;
 use std::fs::File;
 use std::io;
 use std::io::prelude::*;
+use std::ops::Range;
 use std::panic::{catch_unwind, AssertUnwindSafe};
 use std::path::PathBuf;
 use std::process;
-use std::process::Termination;
+use std::process::{Stdio, Termination};
 use std::sync::mpsc::{channel, Sender};
 use std::sync::{Arc, Mutex};
 use std::thread;
-use std::time::{Duration, Instant};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 
 const TEST_WARN_TIMEOUT_S: u64 = 60;
+const TEST_TIME_THRESHOLD_ENV_NAME: &str = "RUST_TEST_TIME_THRESHOLD";
+
+// When set, the test binary runs the single test named by this variable
+// instead of the whole suite; used by `--isolate` to re-execute itself.
+const SECONDARY_TEST_INVOKER_VAR: &str = "__RUST_TEST_INVOKE";
+
+// Exit codes of a test run by a secondary invocation. They're distinct from
+// 0 and 101 so that a test calling `process::exit` or panicking outside of
+// the test harness isn't mistaken for a result.
+const TR_OK: i32 = 50;
+const TR_FAILED: i32 = 51;
 const QUIET_MODE_MAX_COLUMN: usize = 100; // insert a '\n' after 100 tests in quiet mode
 
 // to be used by rustc to compile tests in libtest
 pub mod test {
     pub use crate::{
         assert_test_result, filter_tests, parse_opts, run_test, test_main, test_main_static,
-        Bencher, DynTestFn, DynTestName, Metric, MetricMap, Options, RunIgnored, ShouldPanic,
-        StaticBenchFn, StaticTestFn, StaticTestName, TestDesc, TestDescAndFn, TestName, TestOpts,
-        TestResult, TrFailed, TrFailedMsg, TrIgnored, TrOk,
+        BaselineComparison, Bencher, DynTestFn, DynTestName, Metric, MetricMap, Options,
+        RunIgnored, ShouldPanic, StaticBenchFn, StaticTestFn, StaticTestName, TestDesc,
+        TestDescAndFn, TestExecTime, TestName, TestOpts, TestOutput, TestResult, TestSelector,
+        TestShard, TestTimeOptions, TimeThreshold, TrFailed, TrFailedMsg, TrFlaky, TrIgnored, TrOk,
+        TrTimedFail,
     };
 }
 
+mod baseline;
+mod fixtures;
 mod formatters;
 pub mod stats;
 
-use crate::formatters::{JsonFormatter, OutputFormatter, PrettyFormatter, TerseFormatter};
+pub use crate::baseline::BaselineComparison;
+pub use crate::fixtures::FixtureKind;
+
+use crate::baseline::Baseline;
+use crate::fixtures::Fixtures;
+use crate::formatters::{
+    JsonFormatter, JunitFormatter, OutputFormatter, PrettyFormatter, TerseFormatter,
+};
 
 /// Whether to execute tests concurrently or not
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
@@ -174,6 +197,10 @@ pub enum TestFn {
     StaticBenchFn(fn(&mut Bencher)),
     DynTestFn(Box<dyn FnOnce() + Send>),
     DynBenchFn(Box<dyn TDynBenchFn + 'static>),
+    /// A `#[test_setup]` function, run before the tests of its module.
+    StaticSetupFn(fn()),
+    /// A `#[test_teardown]` function, run after the tests of its module.
+    StaticTeardownFn(fn()),
 }
 
 impl TestFn {
@@ -183,6 +210,7 @@ impl TestFn {
             StaticBenchFn(..) => PadOnRight,
             DynTestFn(..) => PadNone,
             DynBenchFn(..) => PadOnRight,
+            StaticSetupFn(..) | StaticTeardownFn(..) => PadNone,
         }
     }
 }
@@ -194,6 +222,8 @@ impl fmt::Debug for TestFn {
             StaticBenchFn(..) => "StaticBenchFn(..)",
             DynTestFn(..) => "DynTestFn(..)",
             DynBenchFn(..) => "DynBenchFn(..)",
+            StaticSetupFn(..) => "StaticSetupFn(..)",
+            StaticTeardownFn(..) => "StaticTeardownFn(..)",
         })
     }
 }
@@ -207,6 +237,7 @@ impl fmt::Debug for TestFn {
 pub struct Bencher {
     mode: BenchMode,
     summary: Option<stats::Summary>,
+    samples: Vec<f64>,
     pub bytes: u64,
 }
 
@@ -273,6 +304,11 @@ impl Options {
 // The default console test runner. It accepts the command line
 // arguments and a vector of test_descs.
 pub fn test_main(args: &[String], tests: Vec<TestDescAndFn>, options: Options) {
+    if let Ok(name) = env::var(SECONDARY_TEST_INVOKER_VAR) {
+        env::remove_var(SECONDARY_TEST_INVOKER_VAR);
+        run_test_in_spawned_subprocess(&name, tests);
+    }
+
     let mut opts = match parse_opts(args) {
         Some(Ok(o)) => o,
         Some(Err(msg)) => {
@@ -320,6 +356,14 @@ pub fn test_main_static(tests: &[&TestDescAndFn]) {
                 testfn: StaticBenchFn(f),
                 desc: t.desc.clone(),
             },
+            StaticSetupFn(f) => TestDescAndFn {
+                testfn: StaticSetupFn(f),
+                desc: t.desc.clone(),
+            },
+            StaticTeardownFn(f) => TestDescAndFn {
+                testfn: StaticTeardownFn(f),
+                desc: t.desc.clone(),
+            },
             _ => panic!("non-static tests passed to test::test_main_static"),
         })
         .collect();
@@ -351,6 +395,15 @@ pub enum OutputFormat {
     Pretty,
     Terse,
     Json,
+    Junit,
+}
+
+impl OutputFormat {
+    // Whether the format includes the duration of every test, which then
+    // has to be measured even without `--report-time`.
+    fn records_exec_time(self) -> bool {
+        self == OutputFormat::Json || self == OutputFormat::Junit
+    }
 }
 
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
@@ -360,10 +413,194 @@ pub enum RunIgnored {
     Only,
 }
 
+/// A property of a test that `--select` and `--deselect` choose tests by.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum TestSelector {
+    /// The test is marked `#[should_panic]`.
+    ShouldPanic,
+    /// The test is marked `#[ignore]`.
+    Ignore,
+    /// The test is marked `#[allow_fail]`.
+    AllowFail,
+    /// The test is defined inside the given module, or one of its submodules.
+    Module(String),
+}
+
+impl TestSelector {
+    fn parse(s: &str) -> Result<TestSelector, String> {
+        match s {
+            "should_panic" => Ok(TestSelector::ShouldPanic),
+            "ignore" => Ok(TestSelector::Ignore),
+            "allow_fail" => Ok(TestSelector::AllowFail),
+            _ if s.starts_with("module=") => {
+                let path = s["module=".len()..].trim_end_matches("::");
+                if path.is_empty() {
+                    Err("the module path of a selector must not be empty".to_string())
+                } else {
+                    Ok(TestSelector::Module(path.to_string()))
+                }
+            }
+            _ => Err(format!(
+                "test selectors must be should_panic, ignore, allow_fail, or module=PATH (was {})",
+                s
+            )),
+        }
+    }
+
+    pub fn matches(&self, desc: &TestDesc) -> bool {
+        match *self {
+            TestSelector::ShouldPanic => desc.should_panic != ShouldPanic::No,
+            TestSelector::Ignore => desc.ignore,
+            TestSelector::AllowFail => desc.allow_fail,
+            TestSelector::Module(ref path) => {
+                let name = desc.name.as_slice();
+                name.starts_with(&**path) && name[path.len()..].starts_with("::")
+            }
+        }
+    }
+}
+
+/// One of several disjoint parts that `--shard` splits the tests into, so
+/// that they can be run on different machines.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct TestShard {
+    /// The 1-based index of the part to run.
+    pub index: usize,
+    /// The number of parts the tests are split into.
+    pub count: usize,
+}
+
+impl TestShard {
+    fn parse(s: &str) -> Result<TestShard, String> {
+        let err = || format!("argument for --shard must be of the form INDEX/COUNT (was {})", s);
+        let mut parts = s.splitn(2, '/');
+        let (index, count) = match (parts.next(), parts.next()) {
+            (Some(index), Some(count)) => (index, count),
+            _ => return Err(err()),
+        };
+        let index = index.parse::<usize>().map_err(|_| err())?;
+        let count = count.parse::<usize>().map_err(|_| err())?;
+        if count == 0 || index == 0 || index > count {
+            return Err(format!(
+                "the shard index given to --shard must be between 1 and the shard count \
+                 (was {})",
+                s
+            ));
+        }
+        Ok(TestShard { index, count })
+    }
+
+    /// Returns whether the test at `position` in the sorted list of tests
+    /// belongs to this shard. Tests are dealt out in turn, so that tests of
+    /// the same module end up spread over all shards.
+    fn contains(&self, position: usize) -> bool {
+        position % self.count == self.index - 1
+    }
+}
+
+/// The measured execution time of a unit test.
+#[derive(Clone, PartialEq)]
+pub struct TestExecTime(Duration);
+
+impl fmt::Display for TestExecTime {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{:03}s", self.0.as_secs(), self.0.subsec_millis())
+    }
+}
+
+/// Structure denoting time limits for test execution.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct TimeThreshold {
+    pub warn: Duration,
+    pub critical: Duration,
+}
+
+impl TimeThreshold {
+    /// Creates a new `TimeThreshold` instance with provided durations.
+    pub fn new(warn: Duration, critical: Duration) -> Self {
+        Self { warn, critical }
+    }
+
+    /// Attempts to create a `TimeThreshold` instance with values obtained
+    /// from the `RUST_TEST_TIME_THRESHOLD` environment variable.
+    ///
+    /// The variable is expected to be of the form `WARN_MS,CRITICAL_MS`, e.g.
+    /// `RUST_TEST_TIME_THRESHOLD=100,200`.
+    ///
+    /// Returns `None` if the variable is not set.
+    ///
+    /// Panics if the variable is set but its value is malformed.
+    pub fn from_env_var() -> Option<Self> {
+        let durations_str = env::var(TEST_TIME_THRESHOLD_ENV_NAME).ok()?;
+        let (warn_str, critical_str) = {
+            let mut durations = durations_str.splitn(2, ',');
+            match (durations.next(), durations.next()) {
+                (Some(warn), Some(critical)) => (warn, critical),
+                _ => panic!(
+                    "{} is `{}`, should be of the form `WARN_MS,CRITICAL_MS`",
+                    TEST_TIME_THRESHOLD_ENV_NAME, durations_str
+                ),
+            }
+        };
+
+        let parse_u64 = |v: &str| {
+            v.trim().parse::<u64>().unwrap_or_else(|_| {
+                panic!(
+                    "{} is `{}`, `{}` is not a number of milliseconds",
+                    TEST_TIME_THRESHOLD_ENV_NAME, durations_str, v
+                )
+            })
+        };
+
+        let warn = Duration::from_millis(parse_u64(warn_str));
+        let critical = Duration::from_millis(parse_u64(critical_str));
+        assert!(
+            warn <= critical,
+            "{} is `{}`, the warn threshold must not be greater than the critical one",
+            TEST_TIME_THRESHOLD_ENV_NAME,
+            durations_str
+        );
+
+        Some(Self::new(warn, critical))
+    }
+}
+
+impl Default for TimeThreshold {
+    fn default() -> Self {
+        Self::new(Duration::from_millis(500), Duration::from_secs(1))
+    }
+}
+
+/// Options controlling the reporting and enforcement of test execution time.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct TestTimeOptions {
+    /// Denotes if the test critical execution time limit excess should be
+    /// considered a test failure.
+    pub error_on_excess: bool,
+    pub colored: bool,
+    pub threshold: TimeThreshold,
+}
+
+impl TestTimeOptions {
+    pub fn new_from_env(error_on_excess: bool, colored: bool) -> Self {
+        let threshold = TimeThreshold::from_env_var().unwrap_or_default();
+
+        Self { error_on_excess, colored, threshold }
+    }
+
+    pub fn is_warn(&self, exec_time: &TestExecTime) -> bool {
+        exec_time.0 >= self.threshold.warn
+    }
+
+    pub fn is_critical(&self, exec_time: &TestExecTime) -> bool {
+        exec_time.0 >= self.threshold.critical
+    }
+}
+
 #[derive(Debug)]
 pub struct TestOpts {
     pub list: bool,
-    pub filter: Option<String>,
+    pub filters: Vec<String>,
     pub filter_exact: bool,
     pub exclude_should_panic: bool,
     pub run_ignored: RunIgnored,
@@ -375,6 +612,16 @@ pub struct TestOpts {
     pub format: OutputFormat,
     pub test_threads: Option<usize>,
     pub skip: Vec<String>,
+    pub select: Vec<TestSelector>,
+    pub deselect: Vec<TestSelector>,
+    pub shard: Option<TestShard>,
+    pub time_options: Option<TestTimeOptions>,
+    pub shuffle: bool,
+    pub shuffle_seed: Option<u64>,
+    pub isolate: bool,
+    pub retries: usize,
+    pub save_baseline: Option<String>,
+    pub baseline: Option<String>,
     pub options: Options,
 }
 
@@ -383,7 +630,7 @@ impl TestOpts {
     fn new() -> TestOpts {
         TestOpts {
             list: false,
-            filter: None,
+            filters: vec![],
             filter_exact: false,
             exclude_should_panic: false,
             run_ignored: RunIgnored::No,
@@ -395,6 +642,16 @@ impl TestOpts {
             format: OutputFormat::Pretty,
             test_threads: None,
             skip: vec![],
+            select: vec![],
+            deselect: vec![],
+            shard: None,
+            time_options: None,
+            shuffle: false,
+            shuffle_seed: None,
+            isolate: false,
+            retries: 0,
+            save_baseline: None,
+            baseline: None,
             options: Options::new(),
         }
     }
@@ -407,6 +664,14 @@ fn optgroups() -> getopts::Options {
     let mut opts = getopts::Options::new();
     opts.optflag("", "include-ignored", "Run ignored and not ignored tests")
         .optflag("", "ignored", "Run only ignored tests")
+        .optopt(
+            "",
+            "shard",
+            "Only run the INDEX-th of COUNT disjoint parts of the tests, where
+            INDEX starts at 1. Running every part covers every test exactly
+            once",
+            "INDEX/COUNT",
+        )
         .optflag("", "exclude-should-panic", "Excludes tests marked as should_panic")
         .optflag("", "test", "Run tests and not benchmarks")
         .optflag("", "bench", "Run benchmarks instead of tests")
@@ -439,6 +704,24 @@ fn optgroups() -> getopts::Options {
              be used multiple times)",
             "FILTER",
         )
+        .optmulti(
+            "",
+            "select",
+            "Only run tests with the given property (this flag can be used
+            multiple times, to run tests with any of the properties):
+            should_panic = tests marked #[should_panic];
+            ignore       = tests marked #[ignore];
+            allow_fail   = tests marked #[allow_fail];
+            module=PATH  = tests defined in the module PATH or below it",
+            "PROPERTY",
+        )
+        .optmulti(
+            "",
+            "deselect",
+            "Skip tests with the given property, see --select (this flag
+            can be used multiple times)",
+            "PROPERTY",
+        )
         .optflag(
             "q",
             "quiet",
@@ -465,8 +748,68 @@ fn optgroups() -> getopts::Options {
             "Configure formatting of output:
             pretty = Print verbose output;
             terse  = Display one character per test;
-            json   = Output a json document",
-            "pretty|terse|json",
+            json   = Output one JSON event per line (see the JSON
+                     output chapter of the rustc book);
+            junit  = Output a JUnit document",
+            "pretty|terse|json|junit",
+        )
+        .optflagopt(
+            "",
+            "report-time",
+            "Show execution time of each test. Available values:
+            plain   = do not colorize the execution time (default);
+            colored = colorize output according to the time thresholds.
+
+            Threshold values can be configured via the
+            RUST_TEST_TIME_THRESHOLD environment variable, in the form
+            WARN_MS,CRITICAL_MS (500ms and 1000ms by default)",
+            "plain|colored",
+        )
+        .optflag(
+            "",
+            "ensure-time",
+            "Treat excess of the critical test execution time limit as a
+            test failure. Implies --report-time",
+        )
+        .optflag(
+            "",
+            "shuffle",
+            "Run tests in random order. The seed used is printed at the start
+            of the run",
+        )
+        .optopt(
+            "",
+            "shuffle-seed",
+            "Run tests in random order, shuffled with the given seed. Use this
+            to replay the order printed by a previous --shuffle run",
+            "SEED",
+        )
+        .optflag(
+            "",
+            "isolate",
+            "Run each test in its own process, so that a test which crashes
+            or exits is reported as a failure instead of ending the run",
+        )
+        .optopt(
+            "",
+            "retries",
+            "Run failed tests again, up to N times. Tests which pass on a
+            later attempt are reported as flaky instead of failed",
+            "N",
+        )
+        .optopt(
+            "",
+            "save-baseline",
+            "Save the timings of the benchmarks run under the given name, for
+            later comparison with --baseline",
+            "NAME",
+        )
+        .optopt(
+            "",
+            "baseline",
+            "Compare the timings of the benchmarks run with the ones saved
+            under the given name, and report the relative change",
+            "NAME",
         )
         .optopt(
             "Z",
@@ -479,21 +822,46 @@ fn optgroups() -> getopts::Options {
 }
 
 fn usage(binary: &str, options: &getopts::Options) {
-    let message = format!("Usage: {} [OPTIONS] [FILTER]", binary);
+    let message = format!("Usage: {} [OPTIONS] [FILTERS...]", binary);
     println!(
         r#"{usage}
 
 The FILTER string is tested against the name of all tests, and only those
-tests whose names contain the filter are run.
+tests whose names contain the filter are run. Multiple filter strings may
+be passed, which will run all tests matching any of the filters.
+
+Tests can also be chosen by their attributes and by the module they're
+defined in, with --select and --deselect. A test has to match both the
+filters and the selections, if any are given.
+
+The tests left after filtering can be split between several machines with
+--shard INDEX/COUNT, which runs every COUNT-th test starting with the INDEX-th
+one in alphabetical order.
 
 By default, all tests are run in parallel. This can be altered with the
 --test-threads flag or the RUST_TEST_THREADS environment variable when running
 tests (set it to 1).
 
+Tests are run in alphabetical order by default. The --shuffle flag or setting
+the RUST_TEST_SHUFFLE environment variable to a value other than "0" runs them
+in a random order instead; the seed is printed and can be passed back with
+--shuffle-seed or RUST_TEST_SHUFFLE_SEED to reproduce that order.
+
 All tests have their standard output and standard error captured by default.
 This can be overridden with the --nocapture flag or setting RUST_TEST_NOCAPTURE
 environment variable to a value other than "0". Logging is not captured by default.
 
+Failed tests can be run again with --retries N. A test which passes on one of
+its retries is reported as flaky, which doesn't fail the run. Only tests which
+can be run more than once are retried: #[test] functions, and with --isolate,
+any test.
+
+Benchmark timings can be saved with --save-baseline NAME. Later runs given
+--baseline NAME report how much the median time per iteration of each benchmark
+changed, and whether that change is statistically significant. Baselines are
+stored next to the benchmark executable, or in the directory named by the
+RUST_BENCH_BASELINE_DIR environment variable.
+
 Test Attributes:
 
     #[test]        - Indicates a function is a test to be run. This function
@@ -554,10 +922,38 @@ pub fn parse_opts(args: &[String]) -> Option<OptRes> {
         return None;
     }
 
-    let filter = if !matches.free.is_empty() {
-        Some(matches.free[0].clone())
-    } else {
-        None
+    let filters = matches.free.clone();
+
+    let parse_selectors = |flag: &str| -> Result<Vec<TestSelector>, String> {
+        let selectors = matches.opt_strs(flag);
+        if !allow_unstable && !selectors.is_empty() {
+            return Err(format!(
+                "The \"{}\" flag is only accepted on the nightly compiler",
+                flag
+            ));
+        }
+        selectors.iter().map(|s| TestSelector::parse(s)).collect()
+    };
+    let select = match parse_selectors("select") {
+        Ok(select) => select,
+        Err(e) => return Some(Err(e)),
+    };
+    let deselect = match parse_selectors("deselect") {
+        Ok(deselect) => deselect,
+        Err(e) => return Some(Err(e)),
+    };
+
+    let shard = match matches.opt_str("shard") {
+        Some(_) if !allow_unstable => {
+            return Some(Err(
+                "The \"shard\" flag is only accepted on the nightly compiler".into(),
+            ));
+        }
+        Some(s) => match TestShard::parse(&s) {
+            Ok(shard) => Some(shard),
+            Err(e) => return Some(Err(e)),
+        },
+        None => None,
     };
 
     let exclude_should_panic = matches.opt_present("exclude-should-panic");
@@ -602,6 +998,115 @@ pub fn parse_opts(args: &[String]) -> Option<OptRes> {
         };
     }
 
+    let report_time = matches.opt_present("report-time");
+    if !allow_unstable && report_time {
+        return Some(Err(
+            "The \"report-time\" flag is only accepted on the nightly compiler".into(),
+        ));
+    }
+
+    let ensure_time = matches.opt_present("ensure-time");
+    if !allow_unstable && ensure_time {
+        return Some(Err(
+            "The \"ensure-time\" flag is only accepted on the nightly compiler".into(),
+        ));
+    }
+
+    let colored_time = match matches.opt_str("report-time").as_ref().map(|s| &**s) {
+        Some("plain") | None => false,
+        Some("colored") => true,
+        Some(v) => {
+            return Some(Err(format!(
+                "argument for --report-time must be plain or colored (was {})",
+                v
+            )));
+        }
+    };
+
+    let mut shuffle = matches.opt_present("shuffle");
+    if !allow_unstable && shuffle {
+        return Some(Err(
+            "The \"shuffle\" flag is only accepted on the nightly compiler".into(),
+        ));
+    }
+    if !shuffle && allow_unstable {
+        shuffle = match env::var("RUST_TEST_SHUFFLE") {
+            Ok(val) => &val != "0",
+            Err(_) => false,
+        };
+    }
+
+    let shuffle_seed = match matches.opt_str("shuffle-seed") {
+        Some(n_str) => match n_str.parse::<u64>() {
+            Ok(n) => Some(n),
+            Err(e) => {
+                return Some(Err(format!(
+                    "argument for --shuffle-seed must be a number \
+                     (error: {})",
+                    e
+                )));
+            }
+        },
+        None => None,
+    };
+    if !allow_unstable && shuffle_seed.is_some() {
+        return Some(Err(
+            "The \"shuffle-seed\" flag is only accepted on the nightly compiler".into(),
+        ));
+    }
+    let shuffle_seed = match shuffle_seed {
+        None if allow_unstable => match env::var("RUST_TEST_SHUFFLE_SEED") {
+            Ok(val) => match val.parse::<u64>() {
+                Ok(n) => Some(n),
+                Err(_) => panic!("RUST_TEST_SHUFFLE_SEED is `{}`, should be a number.", val),
+            },
+            Err(_) => None,
+        },
+        seed => seed,
+    };
+
+    let isolate = matches.opt_present("isolate");
+    if !allow_unstable && isolate {
+        return Some(Err(
+            "The \"isolate\" flag is only accepted on the nightly compiler".into(),
+        ));
+    }
+
+    let retries = match matches.opt_str("retries") {
+        Some(_) if !allow_unstable => {
+            return Some(Err(
+                "The \"retries\" flag is only accepted on the nightly compiler".into(),
+            ));
+        }
+        Some(n_str) => match n_str.parse::<usize>() {
+            Ok(n) => n,
+            Err(e) => {
+                return Some(Err(format!(
+                    "argument for --retries must be a number \
+                     (error: {})",
+                    e
+                )));
+            }
+        },
+        None => 0,
+    };
+
+    let save_baseline = matches.opt_str("save-baseline");
+    let baseline = matches.opt_str("baseline");
+    for (flag, name) in &[("save-baseline", &save_baseline), ("baseline", &baseline)] {
+        if let Some(name) = name {
+            if !allow_unstable {
+                return Some(Err(format!(
+                    "The \"{}\" flag is only accepted on the nightly compiler",
+                    flag
+                )));
+            }
+            if !Baseline::is_valid_name(name) {
+                return Some(Err(format!("invalid baseline name for --{}: `{}`", flag, name)));
+            }
+        }
+    }
+
     let test_threads = match matches.opt_str("test-threads") {
         Some(n_str) => match n_str.parse::<usize>() {
             Ok(0) => return Some(Err("argument for --test-threads must not be 0".to_string())),
@@ -635,27 +1140,37 @@ pub fn parse_opts(args: &[String]) -> Option<OptRes> {
         None if quiet => OutputFormat::Terse,
         Some("pretty") | None => OutputFormat::Pretty,
         Some("terse") => OutputFormat::Terse,
-        Some("json") => {
+        Some("json") => OutputFormat::Json,
+        Some("junit") => {
             if !allow_unstable {
                 return Some(Err(
-                    "The \"json\" format is only accepted on the nightly compiler".into(),
+                    "The \"junit\" format is only accepted on the nightly compiler".into(),
                 ));
             }
-            OutputFormat::Json
+            OutputFormat::Junit
         }
 
         Some(v) => {
             return Some(Err(format!(
-                "argument for --format must be pretty, terse, or json (was \
+                "argument for --format must be pretty, terse, json, or junit (was \
                  {})",
                 v
             )));
         }
     };
 
+    // JSON and JUnit output carry per-test durations regardless, but the
+    // thresholds are only needed, and only read from the environment, when
+    // asked for.
+    let time_options = if report_time || ensure_time {
+        Some(TestTimeOptions::new_from_env(ensure_time, colored_time))
+    } else {
+        None
+    };
+
     let test_opts = TestOpts {
         list,
-        filter,
+        filters,
         filter_exact: exact,
         exclude_should_panic,
         run_ignored,
@@ -667,6 +1182,16 @@ pub fn parse_opts(args: &[String]) -> Option<OptRes> {
         format,
         test_threads,
         skip: matches.opt_strs("skip"),
+        select,
+        deselect,
+        shard,
+        time_options,
+        shuffle,
+        shuffle_seed,
+        isolate,
+        retries,
+        save_baseline,
+        baseline,
         options: Options::new(),
     };
 
@@ -677,6 +1202,8 @@ pub fn parse_opts(args: &[String]) -> Option<OptRes> {
 pub struct BenchSamples {
     ns_iter_summ: stats::Summary,
     mb_s: usize,
+    samples: Vec<f64>,
+    baseline: Option<BaselineComparison>,
 }
 
 #[derive(Clone, PartialEq)]
@@ -686,6 +1213,10 @@ pub enum TestResult {
     TrFailedMsg(String),
     TrIgnored,
     TrAllowedFail,
+    TrTimedFail,
+    /// The test failed, but passed when run again with `--retries`. Holds
+    /// the number of times the test was run.
+    TrFlaky(usize),
     TrBench(BenchSamples),
 }
 
@@ -719,11 +1250,18 @@ struct ConsoleTestState {
     failed: usize,
     ignored: usize,
     allowed_fail: usize,
+    flaky: usize,
     filtered_out: usize,
     measured: usize,
     metrics: MetricMap,
     failures: Vec<(TestDesc, Vec<u8>)>,
     not_failures: Vec<(TestDesc, Vec<u8>)>,
+    /// Flaky tests, with the output of their last failed attempt
+    flaky_tests: Vec<(TestDesc, Vec<u8>)>,
+    /// Failed setup and teardown functions, with their output
+    fixture_failures: Vec<(TestDesc, FixtureKind, Vec<u8>)>,
+    baseline: Option<Baseline>,
+    new_baseline: Option<Baseline>,
     options: Options,
 }
 
@@ -741,11 +1279,16 @@ impl ConsoleTestState {
             failed: 0,
             ignored: 0,
             allowed_fail: 0,
+            flaky: 0,
             filtered_out: 0,
             measured: 0,
             metrics: MetricMap::new(),
             failures: Vec::new(),
             not_failures: Vec::new(),
+            flaky_tests: Vec::new(),
+            fixture_failures: Vec::new(),
+            baseline: None,
+            new_baseline: None,
             options: opts.options,
         })
     }
@@ -767,12 +1310,30 @@ impl ConsoleTestState {
                 TrFailedMsg(ref msg) => format!("failed: {}", msg),
                 TrIgnored => "ignored".to_owned(),
                 TrAllowedFail => "failed (allowed)".to_owned(),
+                TrTimedFail => "failed (time limit exceeded)".to_owned(),
+                TrFlaky(attempts) => format!("ok (flaky, passed on attempt {})", attempts),
                 TrBench(ref bs) => fmt_bench_samples(bs),
             },
             test.name
         ))
     }
 
+    /// Records the samples of a benchmark for `--save-baseline`, and fills in
+    /// its comparison with the `--baseline` being compared to.
+    fn process_bench_samples(&mut self, test: &TestDesc, bs: &mut BenchSamples) {
+        if let Some(ref baseline) = self.baseline {
+            bs.baseline = baseline.compare(test.name.as_slice(), &bs.samples);
+        }
+        if let Some(ref mut new_baseline) = self.new_baseline {
+            new_baseline.insert(test.name.as_slice(), &bs.samples);
+        }
+    }
+
+    /// Whether no test and no fixture failed.
+    fn is_success(&self) -> bool {
+        self.failed == 0 && self.fixture_failures.is_empty()
+    }
+
     fn current_test_count(&self) -> usize {
         self.passed + self.failed + self.ignored + self.measured + self.allowed_fail
     }
@@ -821,6 +1382,19 @@ pub fn fmt_bench_samples(bs: &BenchSamples) -> String {
             .write_fmt(format_args!(" = {} MB/s", bs.mb_s))
             .unwrap();
     }
+    if let Some(ref comparison) = bs.baseline {
+        let verdict = match (comparison.significant, comparison.change_pct > 0.0) {
+            (false, _) => "no significant change",
+            (true, true) => "regressed",
+            (true, false) => "improved",
+        };
+        output
+            .write_fmt(format_args!(
+                " ({:+.2}% vs baseline, {})",
+                comparison.change_pct, verdict
+            ))
+            .unwrap();
+    }
     output
 }
 
@@ -837,6 +1411,7 @@ pub fn list_tests_console(opts: &TestOpts, tests: Vec<TestDescAndFn>) -> io::Res
     let mut ntest = 0;
     let mut nbench = 0;
 
+    let (_, tests) = Fixtures::split(tests);
     for test in filter_tests(&opts, tests) {
         use crate::TestFn::*;
 
@@ -854,6 +1429,7 @@ pub fn list_tests_console(opts: &TestOpts, tests: Vec<TestDescAndFn>) -> io::Res
                 nbench += 1;
                 "benchmark"
             }
+            StaticSetupFn(..) | StaticTeardownFn(..) => unreachable!(),
         };
 
         writeln!(output, "{}: {}", name, fntype)?;
@@ -887,25 +1463,35 @@ pub fn list_tests_console(opts: &TestOpts, tests: Vec<TestDescAndFn>) -> io::Res
 pub fn run_tests_console(opts: &TestOpts, tests: Vec<TestDescAndFn>) -> io::Result<bool> {
     fn callback(
         event: &TestEvent,
+        opts: &TestOpts,
         st: &mut ConsoleTestState,
         out: &mut dyn OutputFormatter,
     ) -> io::Result<()> {
         match (*event).clone() {
-            TeFiltered(ref filtered_tests) => {
+            TeFiltered(ref filtered_tests, shuffle_seed) => {
                 st.total = filtered_tests.len();
-                out.write_run_start(filtered_tests.len())
+                out.write_run_start(filtered_tests.len(), shuffle_seed)
             }
             TeFilteredOut(filtered_out) => Ok(st.filtered_out = filtered_out),
             TeWait(ref test) => out.write_test_start(test),
             TeTimeout(ref test) => out.write_timeout(test),
-            TeResult(test, result, stdout) => {
+            TeResult(test, mut result, exec_time, output) => {
+                if let TrBench(ref mut bs) = result {
+                    st.process_bench_samples(&test, bs);
+                }
                 st.write_log_result(&test, &result)?;
-                out.write_result(&test, &result, &*stdout)?;
+                out.write_result(&test, &result, exec_time.as_ref(), &output)?;
+                let stdout = output.into_bytes();
                 match result {
                     TrOk => {
                         st.passed += 1;
                         st.not_failures.push((test, stdout));
                     }
+                    TrFlaky(_) => {
+                        st.passed += 1;
+                        st.flaky += 1;
+                        st.flaky_tests.push((test, stdout));
+                    }
                     TrIgnored => st.ignored += 1,
                     TrAllowedFail => st.allowed_fail += 1,
                     TrBench(bs) => {
@@ -926,6 +1512,35 @@ pub fn run_tests_console(opts: &TestOpts, tests: Vec<TestDescAndFn>) -> io::Resu
                         stdout.extend_from_slice(format!("note: {}", msg).as_bytes());
                         st.failures.push((test, stdout));
                     }
+                    TrTimedFail => {
+                        st.failed += 1;
+                        let mut stdout = stdout;
+                        if let (Some(time_opts), Some(exec_time)) = (&opts.time_options, exec_time)
+                        {
+                            stdout.extend_from_slice(
+                                format!(
+                                    "note: test took {}, exceeding the critical time limit of {}",
+                                    exec_time,
+                                    TestExecTime(time_opts.threshold.critical)
+                                )
+                                .as_bytes(),
+                            );
+                        }
+                        st.failures.push((test, stdout));
+                    }
+                }
+                Ok(())
+            }
+            TeFixtureResult(desc, kind, result, output) => {
+                let status = if result == TrOk { "ok" } else { "failed" };
+                st.write_log(format!("{} {} {}\n", kind, status, desc.name))?;
+                out.write_fixture_result(&desc, kind, &result, &output)?;
+                if result != TrOk {
+                    let mut stdout = output.into_bytes();
+                    if let TrFailedMsg(msg) = result {
+                        stdout.extend_from_slice(format!("note: {}", msg).as_bytes());
+                    }
+                    st.fixture_failures.push((desc, kind, stdout));
                 }
                 Ok(())
             }
@@ -951,16 +1566,27 @@ pub fn run_tests_console(opts: &TestOpts, tests: Vec<TestDescAndFn>) -> io::Resu
             use_color(opts),
             max_name_len,
             is_multithreaded,
+            opts.time_options,
         )),
         OutputFormat::Terse => Box::new(TerseFormatter::new(
             output,
             use_color(opts),
             max_name_len,
             is_multithreaded,
+            opts.time_options,
         )),
         OutputFormat::Json => Box::new(JsonFormatter::new(output)),
+        OutputFormat::Junit => Box::new(JunitFormatter::new(output)),
     };
     let mut st = ConsoleTestState::new(opts)?;
+    if opts.bench_benchmarks {
+        if let Some(ref name) = opts.baseline {
+            st.baseline = Some(Baseline::load(&Baseline::path(name)?)?);
+        }
+        if opts.save_baseline.is_some() {
+            st.new_baseline = Some(Baseline::new());
+        }
+    }
     fn len_if_padded(t: &TestDescAndFn) -> usize {
         match t.testfn.padding() {
             PadNone => 0,
@@ -968,10 +1594,14 @@ pub fn run_tests_console(opts: &TestOpts, tests: Vec<TestDescAndFn>) -> io::Resu
         }
     }
 
-    run_tests(opts, tests, |x| callback(&x, &mut st, &mut *out))?;
+    run_tests(opts, tests, |x| callback(&x, opts, &mut st, &mut *out))?;
 
     assert!(st.current_test_count() == st.total);
 
+    if let (Some(name), Some(new_baseline)) = (&opts.save_baseline, st.new_baseline.take()) {
+        new_baseline.save(&Baseline::path(name)?)?;
+    }
+
     return out.write_run_finish(&st);
 }
 
@@ -991,7 +1621,7 @@ fn should_sort_failures_before_printing_them() {
         allow_fail: false,
     };
 
-    let mut out = PrettyFormatter::new(Raw(Vec::new()), false, 10, false);
+    let mut out = PrettyFormatter::new(Raw(Vec::new()), false, 10, false, None);
 
     let st = ConsoleTestState {
         log_out: None,
@@ -1000,12 +1630,17 @@ fn should_sort_failures_before_printing_them() {
         failed: 0,
         ignored: 0,
         allowed_fail: 0,
+        flaky: 0,
         filtered_out: 0,
         measured: 0,
         metrics: MetricMap::new(),
         failures: vec![(test_b, Vec::new()), (test_a, Vec::new())],
         options: Options::new(),
         not_failures: Vec::new(),
+        flaky_tests: Vec::new(),
+        fixture_failures: Vec::new(),
+        baseline: None,
+        new_baseline: None,
     };
 
     out.write_failures(&st).unwrap();
@@ -1061,36 +1696,213 @@ fn stdout_isatty() -> bool {
 
 #[derive(Clone)]
 pub enum TestEvent {
-    TeFiltered(Vec<TestDesc>),
+    TeFiltered(Vec<TestDesc>, Option<u64>),
     TeWait(TestDesc),
-    TeResult(TestDesc, TestResult, Vec<u8>),
+    TeResult(TestDesc, TestResult, Option<TestExecTime>, TestOutput),
     TeTimeout(TestDesc),
     TeFilteredOut(usize),
+    TeFixtureResult(TestDesc, FixtureKind, TestResult, TestOutput),
 }
 
-pub type MonitorMsg = (TestDesc, TestResult, Vec<u8>);
+pub type MonitorMsg = (TestDesc, TestResult, Option<TestExecTime>, TestOutput);
+
+/// The standard output and standard error captured while running a test.
+///
+/// Both streams are kept in a single buffer, in the order they were written
+/// to, so that they can also be shown interleaved like on a terminal.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct TestOutput {
+    data: Vec<u8>,
+    /// The ranges of `data` which were written to standard error.
+    stderr_ranges: Vec<Range<usize>>,
+}
+
+impl TestOutput {
+    pub fn new() -> TestOutput {
+        TestOutput::default()
+    }
+
+    fn push(&mut self, buf: &[u8], is_stderr: bool) {
+        let start = self.data.len();
+        self.data.extend_from_slice(buf);
+        if is_stderr {
+            match self.stderr_ranges.last_mut() {
+                Some(range) if range.end == start => range.end = self.data.len(),
+                _ => self.stderr_ranges.push(start..self.data.len()),
+            }
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    /// Returns the output of both streams, interleaved.
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.data
+    }
+
+    pub fn into_bytes(self) -> Vec<u8> {
+        self.data
+    }
+
+    pub fn stdout(&self) -> Vec<u8> {
+        let mut stdout = Vec::new();
+        let mut start = 0;
+        for range in &self.stderr_ranges {
+            stdout.extend_from_slice(&self.data[start..range.start]);
+            start = range.end;
+        }
+        stdout.extend_from_slice(&self.data[start..]);
+        stdout
+    }
+
+    pub fn stderr(&self) -> Vec<u8> {
+        let mut stderr = Vec::new();
+        for range in &self.stderr_ranges {
+            stderr.extend_from_slice(&self.data[range.clone()]);
+        }
+        stderr
+    }
+}
+
+/// Captures one of the output streams of a test.
+struct Sink {
+    output: Arc<Mutex<TestOutput>>,
+    is_stderr: bool,
+}
+
+impl Sink {
+    fn stdout(output: Arc<Mutex<TestOutput>>) -> Sink {
+        Sink { output, is_stderr: false }
+    }
+
+    fn stderr(output: Arc<Mutex<TestOutput>>) -> Sink {
+        Sink { output, is_stderr: true }
+    }
+}
 
-struct Sink(Arc<Mutex<Vec<u8>>>);
 impl Write for Sink {
     fn write(&mut self, data: &[u8]) -> io::Result<usize> {
-        Write::write(&mut *self.0.lock().unwrap(), data)
+        self.output.lock().unwrap().push(data, self.is_stderr);
+        Ok(data.len())
     }
     fn flush(&mut self) -> io::Result<()> {
         Ok(())
     }
 }
 
+/// Keeps track of the tests which may be run again when they fail, for
+/// `--retries`.
+struct Retries {
+    max_retries: usize,
+    isolate: bool,
+    /// Copies of the tests which can be retried, with the number of times
+    /// they have been run and the output of their last failed attempt.
+    tests: HashMap<TestDesc, (TestFn, usize, TestOutput)>,
+}
+
+enum RetryDecision {
+    /// The test failed and should be run again.
+    Retry(TestDescAndFn),
+    /// The final result of the test, with the output to report.
+    Report(TestResult, TestOutput),
+}
+
+impl Retries {
+    fn new(opts: &TestOpts) -> Retries {
+        Retries { max_retries: opts.retries, isolate: opts.isolate, tests: HashMap::new() }
+    }
+
+    /// Returns a copy of `testfn` to run again, if it can be run more than
+    /// once.
+    fn copy_testfn(&self, testfn: &TestFn) -> Option<TestFn> {
+        match *testfn {
+            StaticTestFn(f) => Some(StaticTestFn(f)),
+            // Isolated tests are run again by spawning another process, which
+            // looks the test up by name, so its closure isn't needed.
+            DynTestFn(_) if self.isolate => Some(DynTestFn(Box::new(|| {}))),
+            _ => None,
+        }
+    }
+
+    /// Remembers a copy of `test`, to run it again if it fails.
+    fn track(&mut self, test: &TestDescAndFn) {
+        if self.max_retries == 0 || self.tests.contains_key(&test.desc) {
+            return;
+        }
+        if let Some(testfn) = self.copy_testfn(&test.testfn) {
+            self.tests.insert(test.desc.clone(), (testfn, 0, TestOutput::new()));
+        }
+    }
+
+    /// Returns whether `desc` has already been run before.
+    fn is_retry(&self, desc: &TestDesc) -> bool {
+        self.tests.get(desc).map_or(false, |&(_, attempts, _)| attempts > 0)
+    }
+
+    fn process(
+        &mut self,
+        desc: &TestDesc,
+        result: TestResult,
+        output: TestOutput,
+    ) -> RetryDecision {
+        let attempts = match self.tests.get_mut(desc) {
+            Some(entry) => {
+                entry.1 += 1;
+                entry.1
+            }
+            None => {
+                let mut output = output;
+                match result {
+                    // The test couldn't be copied to run it again.
+                    TrFailed | TrFailedMsg(_) | TrTimedFail if self.max_retries > 0 => {
+                        output.push(
+                            b"note: not retried, only #[test] functions can be run again \
+                              without --isolate\n",
+                            true,
+                        );
+                    }
+                    _ => {}
+                }
+                return RetryDecision::Report(result, output);
+            }
+        };
+
+        match result {
+            TrFailed | TrFailedMsg(_) | TrTimedFail if attempts <= self.max_retries => {
+                let mut output = output;
+                if let TrFailedMsg(ref msg) = result {
+                    output.push(format!("note: {}\n", msg).as_bytes(), true);
+                }
+                let testfn = self.copy_testfn(&self.tests[desc].0).unwrap();
+                self.tests.get_mut(desc).unwrap().2 = output;
+                RetryDecision::Retry(TestDescAndFn { desc: desc.clone(), testfn })
+            }
+            TrOk if attempts > 1 => {
+                let (_, _, failed_output) = self.tests.remove(desc).unwrap();
+                RetryDecision::Report(TrFlaky(attempts), failed_output)
+            }
+            result => {
+                self.tests.remove(desc);
+                RetryDecision::Report(result, output)
+            }
+        }
+    }
+}
+
 pub fn run_tests<F>(opts: &TestOpts, tests: Vec<TestDescAndFn>, mut callback: F) -> io::Result<()>
 where
     F: FnMut(TestEvent) -> io::Result<()>,
 {
-    use std::collections::{self, HashMap};
+    use std::collections;
     use std::hash::BuildHasherDefault;
     use std::sync::mpsc::RecvTimeoutError;
     // Use a deterministic hasher
     type TestMap =
         HashMap<TestDesc, Instant, BuildHasherDefault<collections::hash_map::DefaultHasher>>;
 
+    let (mut fixtures, tests) = Fixtures::split(tests);
     let tests_len = tests.len();
 
     let mut filtered_tests = filter_tests(opts, tests);
@@ -1107,12 +1919,22 @@ where
         filtered_tests
     };
 
+    let shuffle_seed = get_shuffle_seed(opts);
+    let filtered_tests = match shuffle_seed {
+        Some(seed) => {
+            let mut filtered_tests = filtered_tests;
+            shuffle_tests(seed, &mut filtered_tests);
+            filtered_tests
+        }
+        None => filtered_tests,
+    };
+
     let filtered_out = tests_len - filtered_tests.len();
     callback(TeFilteredOut(filtered_out))?;
 
     let filtered_descs = filtered_tests.iter().map(|t| t.desc.clone()).collect();
 
-    callback(TeFiltered(filtered_descs))?;
+    callback(TeFiltered(filtered_descs, shuffle_seed))?;
 
     let (filtered_tests, filtered_benchs): (Vec<_>, _) =
         filtered_tests.into_iter().partition(|e| match e.testfn {
@@ -1120,6 +1942,17 @@ where
             _ => false,
         });
 
+    // Only the tests which actually run are passed through the fixtures,
+    // ignored ones neither need them set up nor hold up their teardown.
+    let will_run = |desc: &TestDesc| !desc.ignore && opts.run_tests;
+    let will_bench = |desc: &TestDesc| !desc.ignore && opts.bench_benchmarks;
+    let run_names = filtered_tests
+        .iter()
+        .filter(|test| will_run(&test.desc))
+        .chain(filtered_benchs.iter().filter(|test| will_bench(&test.desc)))
+        .map(|test| test.desc.name.as_slice());
+    fixtures.retain_used(run_names);
+
     let concurrency = opts.test_threads.unwrap_or_else(get_concurrency);
 
     let mut remaining = filtered_tests;
@@ -1129,6 +1962,7 @@ where
     let (tx, rx) = channel::<MonitorMsg>();
 
     let mut running_tests: TestMap = HashMap::default();
+    let mut retries = Retries::new(opts);
 
     fn get_timed_out_tests(running_tests: &mut TestMap) -> Vec<TestDesc> {
         let now = Instant::now();
@@ -1159,25 +1993,80 @@ where
         })
     };
 
+    // Runs the setups a test needs right before it starts. Returns `false` if
+    // one of them failed, in which case the test is reported as failed
+    // without running it.
+    fn start_test<F>(
+        fixtures: &mut Fixtures,
+        desc: &TestDesc,
+        nocapture: bool,
+        callback: &mut F,
+    ) -> io::Result<bool>
+    where
+        F: FnMut(TestEvent) -> io::Result<()>,
+    {
+        let name = desc.name.as_slice();
+        fixtures.run_setups(name, nocapture, callback)?;
+        let msg = match fixtures.failed_setup(name) {
+            Some(setup) => format!("not run because setup `{}` failed", setup),
+            None => return Ok(true),
+        };
+        callback(TeWait(desc.clone()))?;
+        callback(TeResult(desc.clone(), TrFailedMsg(msg), None, TestOutput::new()))?;
+        fixtures.run_teardowns(name, nocapture, callback)?;
+        Ok(false)
+    }
+
     if concurrency == 1 {
         while !remaining.is_empty() {
             let test = remaining.pop().unwrap();
-            callback(TeWait(test.desc.clone()))?;
+            if !retries.is_retry(&test.desc) {
+                if will_run(&test.desc)
+                    && !start_test(&mut fixtures, &test.desc, opts.nocapture, &mut callback)?
+                {
+                    continue;
+                }
+                callback(TeWait(test.desc.clone()))?;
+            }
+            retries.track(&test);
             run_test(opts, !opts.run_tests, test, tx.clone(), Concurrent::No);
-            let (test, result, stdout) = rx.recv().unwrap();
-            callback(TeResult(test, result, stdout))?;
+            let (test, result, exec_time, output) = rx.recv().unwrap();
+            match retries.process(&test, result, output) {
+                RetryDecision::Retry(test) => remaining.push(test),
+                RetryDecision::Report(result, output) => {
+                    let name = if will_run(&test) { Some(test.name.clone()) } else { None };
+                    callback(TeResult(test, result, exec_time, output))?;
+                    if let Some(name) = name {
+                        fixtures.run_teardowns(name.as_slice(), opts.nocapture, &mut callback)?;
+                    }
+                }
+            }
         }
     } else {
         while pending > 0 || !remaining.is_empty() {
             while pending < concurrency && !remaining.is_empty() {
                 let test = remaining.pop().unwrap();
+                if !retries.is_retry(&test.desc)
+                    && will_run(&test.desc)
+                    && !start_test(&mut fixtures, &test.desc, opts.nocapture, &mut callback)?
+                {
+                    continue;
+                }
                 let timeout = Instant::now() + Duration::from_secs(TEST_WARN_TIMEOUT_S);
                 running_tests.insert(test.desc.clone(), timeout);
-                callback(TeWait(test.desc.clone()))?; //here no pad
+                if !retries.is_retry(&test.desc) {
+                    callback(TeWait(test.desc.clone()))?; //here no pad
+                }
+                retries.track(&test);
                 run_test(opts, !opts.run_tests, test, tx.clone(), Concurrent::Yes);
                 pending += 1;
             }
 
+            // Every remaining test may have been skipped by a failed setup.
+            if pending == 0 {
+                continue;
+            }
+
             let mut res;
             loop {
                 if let Some(timeout) = calc_timeout(&running_tests) {
@@ -1194,23 +2083,42 @@ where
                 }
             }
 
-            let (desc, result, stdout) = res.unwrap();
+            let (desc, result, exec_time, output) = res.unwrap();
             running_tests.remove(&desc);
-
-            callback(TeResult(desc, result, stdout))?;
             pending -= 1;
+
+            match retries.process(&desc, result, output) {
+                RetryDecision::Retry(test) => remaining.push(test),
+                RetryDecision::Report(result, output) => {
+                    let name = if will_run(&desc) { Some(desc.name.clone()) } else { None };
+                    callback(TeResult(desc, result, exec_time, output))?;
+                    if let Some(name) = name {
+                        fixtures.run_teardowns(name.as_slice(), opts.nocapture, &mut callback)?;
+                    }
+                }
+            }
         }
     }
 
     if opts.bench_benchmarks {
         // All benchmarks run at the end, in serial.
         for b in filtered_benchs {
+            if will_bench(&b.desc)
+                && !start_test(&mut fixtures, &b.desc, opts.nocapture, &mut callback)?
+            {
+                continue;
+            }
             callback(TeWait(b.desc.clone()))?;
             run_test(opts, false, b, tx.clone(), Concurrent::No);
-            let (test, result, stdout) = rx.recv().unwrap();
-            callback(TeResult(test, result, stdout))?;
+            let (test, result, exec_time, output) = rx.recv().unwrap();
+            let name = if will_bench(&test) { Some(test.name.clone()) } else { None };
+            callback(TeResult(test, result, exec_time, output))?;
+            if let Some(name) = name {
+                fixtures.run_teardowns(name.as_slice(), opts.nocapture, &mut callback)?;
+            }
         }
     }
+
     Ok(())
 }
 
@@ -1366,14 +2274,22 @@ pub fn filter_tests(opts: &TestOpts, tests: Vec<TestDescAndFn>) -> Vec<TestDescA
         }
     };
 
-    // Remove tests that don't match the test filter
-    if let Some(ref filter) = opts.filter {
-        filtered.retain(|test| matches_filter(test, filter));
+    // Remove tests that don't match any of the test filters
+    if !opts.filters.is_empty() {
+        filtered.retain(|test| opts.filters.iter().any(|filter| matches_filter(test, filter)));
     }
 
     // Skip tests that match any of the skip filters
     filtered.retain(|test| !opts.skip.iter().any(|sf| matches_filter(test, sf)));
 
+    // Remove tests that don't have any of the selected properties
+    if !opts.select.is_empty() {
+        filtered.retain(|test| opts.select.iter().any(|s| s.matches(&test.desc)));
+    }
+
+    // Skip tests that have any of the deselected properties
+    filtered.retain(|test| !opts.deselect.iter().any(|s| s.matches(&test.desc)));
+
     // Excludes #[should_panic] tests
     if opts.exclude_should_panic {
         filtered.retain(|test| test.desc.should_panic == ShouldPanic::No);
@@ -1398,6 +2314,17 @@ pub fn filter_tests(opts: &TestOpts, tests: Vec<TestDescAndFn>) -> Vec<TestDescA
     // Sort the tests alphabetically
     filtered.sort_by(|t1, t2| t1.desc.name.as_slice().cmp(t2.desc.name.as_slice()));
 
+    // Only keep the tests of the requested shard. This has to happen after
+    // sorting, so that every shard sees the same order.
+    if let Some(shard) = opts.shard {
+        filtered = filtered
+            .into_iter()
+            .enumerate()
+            .filter(|&(i, _)| shard.contains(i))
+            .map(|(_, test)| test)
+            .collect();
+    }
+
     filtered
 }
 
@@ -1423,6 +2350,55 @@ pub fn convert_benchmarks_to_tests(tests: Vec<TestDescAndFn>) -> Vec<TestDescAnd
         .collect()
 }
 
+/// Returns the seed the tests should be shuffled with, if shuffling was
+/// requested. Without an explicit seed, one is derived from the current time.
+fn get_shuffle_seed(opts: &TestOpts) -> Option<u64> {
+    opts.shuffle_seed.or_else(|| {
+        if opts.shuffle {
+            let now = SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .expect("system time is before the unix epoch");
+            Some(now.as_secs() ^ (now.subsec_nanos() as u64) << 32)
+        } else {
+            None
+        }
+    })
+}
+
+/// Shuffles `tests` in place. The resulting order only depends on `seed` and
+/// on the names of the tests, so a given order can be reproduced as long as
+/// the same set of tests is selected.
+pub fn shuffle_tests(seed: u64, tests: &mut [TestDescAndFn]) {
+    // FNV-1a over the test names, separated by a NUL byte.
+    let names_hash = tests
+        .iter()
+        .flat_map(|test| test.desc.name.as_slice().bytes().chain(Some(0)))
+        .fold(0xcbf2_9ce4_8422_2325_u64, |hash, byte| {
+            (hash ^ byte as u64).wrapping_mul(0x0100_0000_01b3)
+        });
+    let mut rng = SplitMix64(seed ^ names_hash);
+
+    // Fisher-Yates
+    for i in (1..tests.len()).rev() {
+        let j = (rng.next_u64() % (i as u64 + 1)) as usize;
+        tests.swap(i, j);
+    }
+}
+
+/// A tiny, portable PRNG; the generated sequence must never change between
+/// releases, otherwise printed shuffle seeds would stop being reproducible.
+struct SplitMix64(u64);
+
+impl SplitMix64 {
+    fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+}
+
 pub fn run_test(
     opts: &TestOpts,
     force_ignore: bool,
@@ -1437,7 +2413,7 @@ pub fn run_test(
         && desc.should_panic != ShouldPanic::No;
 
     if force_ignore || desc.ignore || ignore_because_panic_abort {
-        monitor_ch.send((desc, TrIgnored, Vec::new())).unwrap();
+        monitor_ch.send((desc, TrIgnored, None, TestOutput::new())).unwrap();
         return;
     }
 
@@ -1445,35 +2421,39 @@ pub fn run_test(
         desc: TestDesc,
         monitor_ch: Sender<MonitorMsg>,
         nocapture: bool,
+        report_time: bool,
         testfn: Box<dyn FnOnce() + Send>,
         concurrency: Concurrent,
+        time_opts: Option<TestTimeOptions>,
     ) {
         // Buffer for capturing standard I/O
-        let data = Arc::new(Mutex::new(Vec::new()));
+        let data = Arc::new(Mutex::new(TestOutput::new()));
         let data2 = data.clone();
 
         let name = desc.name.clone();
         let runtest = move || {
             let oldio = if !nocapture {
                 Some((
-                    io::set_print(Some(Box::new(Sink(data2.clone())))),
-                    io::set_panic(Some(Box::new(Sink(data2)))),
+                    io::set_print(Some(Box::new(Sink::stdout(data2.clone())))),
+                    io::set_panic(Some(Box::new(Sink::stderr(data2)))),
                 ))
             } else {
                 None
             };
 
+            let start = if report_time { Some(Instant::now()) } else { None };
             let result = catch_unwind(AssertUnwindSafe(testfn));
+            let exec_time = start.map(|start| TestExecTime(start.elapsed()));
 
             if let Some((printio, panicio)) = oldio {
                 io::set_print(printio);
                 io::set_panic(panicio);
             };
 
-            let test_result = calc_result(&desc, result);
-            let stdout = data.lock().unwrap().to_vec();
+            let test_result = calc_result(&desc, result, &time_opts, &exec_time);
+            let output = data.lock().unwrap().clone();
             monitor_ch
-                .send((desc.clone(), test_result, stdout))
+                .send((desc.clone(), test_result, exec_time, output))
                 .unwrap();
         };
 
@@ -1489,7 +2469,17 @@ pub fn run_test(
         }
     }
 
+    let report_time = opts.time_options.is_some() || opts.format.records_exec_time();
+
     match testfn {
+        DynTestFn(_) | StaticTestFn(_) if opts.isolate => run_test_in_process(
+            desc,
+            monitor_ch,
+            opts.nocapture,
+            report_time,
+            opts.time_options,
+            concurrency,
+        ),
         DynBenchFn(bencher) => {
             crate::bench::benchmark(desc, monitor_ch, opts.nocapture, |harness| {
                 bencher.run(harness)
@@ -1502,14 +2492,24 @@ pub fn run_test(
         }
         DynTestFn(f) => {
             let cb = move || __rust_begin_short_backtrace(f);
-            run_test_inner(desc, monitor_ch, opts.nocapture, Box::new(cb), concurrency)
+            run_test_inner(
+                desc,
+                monitor_ch,
+                opts.nocapture,
+                report_time,
+                Box::new(cb),
+                concurrency,
+                opts.time_options,
+            )
         }
-        StaticTestFn(f) => run_test_inner(
+        StaticTestFn(f) | StaticSetupFn(f) | StaticTeardownFn(f) => run_test_inner(
             desc,
             monitor_ch,
             opts.nocapture,
+            report_time,
             Box::new(move || __rust_begin_short_backtrace(f)),
             concurrency,
+            opts.time_options,
         ),
     }
 }
@@ -1520,7 +2520,163 @@ fn __rust_begin_short_backtrace<F: FnOnce()>(f: F) {
     f()
 }
 
-fn calc_result(desc: &TestDesc, task_result: Result<(), Box<dyn Any + Send>>) -> TestResult {
+/// Runs `desc` in a child process (a secondary invocation of the current
+/// executable), so that crashes, aborts and calls to `process::exit` only
+/// affect this test's result.
+fn run_test_in_process(
+    desc: TestDesc,
+    monitor_ch: Sender<MonitorMsg>,
+    nocapture: bool,
+    report_time: bool,
+    time_opts: Option<TestTimeOptions>,
+    concurrency: Concurrent,
+) {
+    let name = desc.name.clone();
+    let runtest = move || {
+        let start = if report_time { Some(Instant::now()) } else { None };
+        let output = spawn_test_subprocess(&desc, nocapture);
+        let exec_time = start.map(|start| TestExecTime(start.elapsed()));
+
+        let (test_result, test_output) = match output {
+            Ok(output) => {
+                // The streams of the child process are read separately, so
+                // their interleaving is lost.
+                let mut test_output = TestOutput::new();
+                test_output.push(&output.stdout, false);
+                test_output.push(&output.stderr, true);
+
+                (get_result_from_exit_status(&desc, output.status), test_output)
+            }
+            Err(e) => {
+                let msg = format!("failed to spawn the test process: {}", e);
+                (TrFailedMsg(msg), TestOutput::new())
+            }
+        };
+        let test_result = check_time_limit(test_result, &time_opts, &exec_time);
+
+        monitor_ch
+            .send((desc.clone(), test_result, exec_time, test_output))
+            .unwrap();
+    };
+
+    let supports_threads = !cfg!(target_os = "emscripten") && !cfg!(target_arch = "wasm32");
+    if concurrency == Concurrent::Yes && supports_threads {
+        let cfg = thread::Builder::new().name(name.as_slice().to_owned());
+        cfg.spawn(runtest).unwrap();
+    } else {
+        runtest();
+    }
+}
+
+fn spawn_test_subprocess(desc: &TestDesc, nocapture: bool) -> io::Result<process::Output> {
+    let mut command = process::Command::new(env::current_exe()?);
+    command.env(SECONDARY_TEST_INVOKER_VAR, desc.name.as_slice());
+    if nocapture {
+        command.stdout(Stdio::inherit()).stderr(Stdio::inherit());
+    }
+    command.output()
+}
+
+/// The secondary side of `--isolate`: runs the test called `name` and exits
+/// the process with `TR_OK` or `TR_FAILED`.
+///
+/// Fixtures don't run here: the main process runs them once around the
+/// processes of all tests in their scope.
+fn run_test_in_spawned_subprocess(name: &str, tests: Vec<TestDescAndFn>) -> ! {
+    let TestDescAndFn { desc, testfn } = tests
+        .into_iter()
+        .find(|test| test.desc.name.as_slice() == name)
+        .unwrap_or_else(|| panic!("couldn't find a test with the provided name '{}'", name));
+
+    let testfn: Box<dyn FnOnce() + Send> = match testfn {
+        StaticTestFn(f) | StaticSetupFn(f) | StaticTeardownFn(f) => {
+            Box::new(move || __rust_begin_short_backtrace(f))
+        }
+        DynTestFn(f) => Box::new(move || __rust_begin_short_backtrace(f)),
+        StaticBenchFn(benchfn) => {
+            Box::new(move || bench::run_once(|b| __rust_begin_short_backtrace(|| benchfn(b))))
+        }
+        DynBenchFn(bench) => {
+            Box::new(move || bench::run_once(|b| __rust_begin_short_backtrace(|| bench.run(b))))
+        }
+    };
+
+    let result = catch_unwind(AssertUnwindSafe(testfn));
+    let code = match calc_result_untimed(&desc, result) {
+        TrOk => TR_OK,
+        TrFailedMsg(msg) => {
+            eprintln!("note: {}", msg);
+            TR_FAILED
+        }
+        _ => TR_FAILED,
+    };
+    process::exit(code)
+}
+
+fn get_result_from_exit_status(desc: &TestDesc, status: process::ExitStatus) -> TestResult {
+    let result = match status.code() {
+        Some(TR_OK) => return TrOk,
+        Some(TR_FAILED) => TrFailed,
+        Some(code) => {
+            TrFailedMsg(format!("the test process exited unexpectedly with status code {}", code))
+        }
+        None => TrFailedMsg(describe_abnormal_exit(status)),
+    };
+
+    if desc.allow_fail { TrAllowedFail } else { result }
+}
+
+#[cfg(unix)]
+fn describe_abnormal_exit(status: process::ExitStatus) -> String {
+    use std::os::unix::process::ExitStatusExt;
+
+    match status.signal() {
+        Some(signal) => format!("the test process was terminated by signal {}", signal),
+        None => format!("the test process terminated abnormally ({})", status),
+    }
+}
+
+#[cfg(not(unix))]
+fn describe_abnormal_exit(status: process::ExitStatus) -> String {
+    format!("the test process terminated abnormally ({})", status)
+}
+
+fn calc_result(
+    desc: &TestDesc,
+    task_result: Result<(), Box<dyn Any + Send>>,
+    time_opts: &Option<TestTimeOptions>,
+    exec_time: &Option<TestExecTime>,
+) -> TestResult {
+    let result = calc_result_untimed(desc, task_result);
+    check_time_limit(result, time_opts, exec_time)
+}
+
+/// Turns a passing `result` into `TrTimedFail` if the test took longer than
+/// the critical time limit and `--ensure-time` is in effect.
+fn check_time_limit(
+    result: TestResult,
+    time_opts: &Option<TestTimeOptions>,
+    exec_time: &Option<TestExecTime>,
+) -> TestResult {
+    // If test is already failed (or allowed to fail), do not change the result.
+    if result != TrOk {
+        return result;
+    }
+
+    // Check if test is failed due to timeout.
+    if let (Some(opts), Some(time)) = (time_opts, exec_time) {
+        if opts.error_on_excess && opts.is_critical(time) {
+            return TrTimedFail;
+        }
+    }
+
+    result
+}
+
+fn calc_result_untimed(
+    desc: &TestDesc,
+    task_result: Result<(), Box<dyn Any + Send>>,
+) -> TestResult {
     match (&desc.should_panic, task_result) {
         (&ShouldPanic::No, Ok(())) | (&ShouldPanic::Yes, Err(_)) => TrOk,
         (&ShouldPanic::YesWithMessage(msg), Err(ref err)) => {
@@ -1596,7 +2752,9 @@ impl Bencher {
             return;
         }
 
-        self.summary = Some(iter(&mut inner));
+        let (summary, samples) = iter_with_samples(&mut inner);
+        self.summary = Some(summary);
+        self.samples = samples;
     }
 
     pub fn bench<F>(&mut self, mut f: F) -> Option<stats::Summary>
@@ -1624,6 +2782,15 @@ where
 }
 
 pub fn iter<T, F>(inner: &mut F) -> stats::Summary
+where
+    F: FnMut() -> T,
+{
+    iter_with_samples(inner).0
+}
+
+/// Like `iter`, but also returns the (winsorized) nanoseconds per iteration
+/// of each sample the summary was computed from.
+fn iter_with_samples<T, F>(inner: &mut F) -> (stats::Summary, Vec<f64>)
 where
     F: FnMut() -> T,
 {
@@ -1670,13 +2837,13 @@ where
             && summ.median_abs_dev_pct < 1.0
             && summ.median - summ5.median < summ5.median_abs_dev
         {
-            return summ5;
+            return (summ5, samples.to_vec());
         }
 
         total_run = total_run + loop_run;
         // Longest we ever run for is 3s.
         if total_run > Duration::from_secs(3) {
-            return summ5;
+            return (summ5, samples.to_vec());
         }
 
         // If we overflow here just return the results so far. We check a
@@ -1686,14 +2853,17 @@ where
         n = match n.checked_mul(10) {
             Some(_) => n * 2,
             None => {
-                return summ5;
+                return (summ5, samples.to_vec());
             }
         };
     }
 }
 
 pub mod bench {
-    use super::{BenchMode, BenchSamples, Bencher, MonitorMsg, Sender, Sink, TestDesc, TestResult};
+    use super::{
+        BenchMode, BenchSamples, Bencher, MonitorMsg, Sender, Sink, TestDesc, TestOutput,
+        TestResult,
+    };
     use crate::stats;
     use std::cmp;
     use std::io;
@@ -1707,16 +2877,17 @@ pub mod bench {
         let mut bs = Bencher {
             mode: BenchMode::Auto,
             summary: None,
+            samples: Vec::new(),
             bytes: 0,
         };
 
-        let data = Arc::new(Mutex::new(Vec::new()));
+        let data = Arc::new(Mutex::new(TestOutput::new()));
         let data2 = data.clone();
 
         let oldio = if !nocapture {
             Some((
-                io::set_print(Some(Box::new(Sink(data2.clone())))),
-                io::set_panic(Some(Box::new(Sink(data2)))),
+                io::set_print(Some(Box::new(Sink::stdout(data2.clone())))),
+                io::set_panic(Some(Box::new(Sink::stderr(data2)))),
             ))
         } else {
             None
@@ -1738,6 +2909,8 @@ pub mod bench {
                 let bs = BenchSamples {
                     ns_iter_summ,
                     mb_s: mb_s as usize,
+                    samples: bs.samples,
+                    baseline: None,
                 };
                 TestResult::TrBench(bs)
             }
@@ -1748,14 +2921,16 @@ pub mod bench {
                 let bs = BenchSamples {
                     ns_iter_summ: stats::Summary::new(samples),
                     mb_s: 0,
+                    samples: Vec::new(),
+                    baseline: None,
                 };
                 TestResult::TrBench(bs)
             }
             Err(_) => TestResult::TrFailed,
         };
 
-        let stdout = data.lock().unwrap().to_vec();
-        monitor_ch.send((desc, test_result, stdout)).unwrap();
+        let output = data.lock().unwrap().clone();
+        monitor_ch.send((desc, test_result, None, output)).unwrap();
     }
 
     pub fn run_once<F>(f: F)
@@ -1765,6 +2940,7 @@ pub mod bench {
         let mut bs = Bencher {
             mode: BenchMode::Single,
             summary: None,
+            samples: Vec::new(),
             bytes: 0,
         };
         bs.bench(f);
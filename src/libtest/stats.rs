@@ -315,6 +315,55 @@ pub fn winsorize(samples: &mut [f64], pct: f64) {
     }
 }
 
+/// Two-sided Mann-Whitney U test of whether samples `a` and `b` come from the
+/// same distribution. Returns the z-score of the U statistic of `a`, using the
+/// normal approximation with a correction for ties; it is positive when the
+/// values in `a` tend to be larger than those in `b`.
+///
+/// The approximation is only reasonable for more than about 20 samples per
+/// side, which holds for the 50 samples taken by `Bencher::iter`. At the 5%
+/// significance level, the samples differ if the absolute value of the result
+/// exceeds 1.96.
+///
+/// See: <https://en.wikipedia.org/wiki/Mann%E2%80%93Whitney_U_test>
+pub fn mann_whitney_u(a: &[f64], b: &[f64]) -> f64 {
+    let n1 = a.len() as f64;
+    let n2 = b.len() as f64;
+    let n = n1 + n2;
+    if a.is_empty() || b.is_empty() {
+        return 0.0;
+    }
+
+    let mut all: Vec<(f64, bool)> =
+        a.iter().map(|&x| (x, true)).chain(b.iter().map(|&x| (x, false))).collect();
+    all.sort_by(|x, y| local_cmp(x.0, y.0));
+
+    // Tied values all get the average of the ranks they span.
+    let mut rank_sum_a = 0.0;
+    let mut tie_correction = 0.0;
+    let mut i = 0;
+    while i < all.len() {
+        let mut j = i;
+        while j + 1 < all.len() && all[j + 1].0 == all[i].0 {
+            j += 1;
+        }
+        let ties = (j - i + 1) as f64;
+        let avg_rank = (i + j) as f64 / 2.0 + 1.0;
+        rank_sum_a += avg_rank * all[i..=j].iter().filter(|x| x.1).count() as f64;
+        tie_correction += ties * ties * ties - ties;
+        i = j + 1;
+    }
+
+    let u = rank_sum_a - n1 * (n1 + 1.0) / 2.0;
+    let mean = n1 * n2 / 2.0;
+    let var = n1 * n2 / 12.0 * ((n + 1.0) - tie_correction / (n * (n - 1.0)));
+    if var <= 0.0 {
+        // Every single sample is equal.
+        return 0.0;
+    }
+    (u - mean) / var.sqrt()
+}
+
 // Test vectors generated from R, using the script src/etc/stat-test-vectors.r.
 
 #[cfg(test)]
@@ -1,3 +1,4 @@
+use crate::stats::mann_whitney_u;
 use crate::stats::Stats;
 use crate::stats::Summary;
 use std::f64;
@@ -572,3 +573,22 @@ fn test_sum_f64s() {
 fn test_sum_f64_between_ints_that_sum_to_0() {
     assert_eq!([1e30f64, 1.2f64, -1e30f64].sum(), 1.2);
 }
+
+#[test]
+fn test_mann_whitney_u_identical() {
+    let a: Vec<f64> = (0..50).map(|x| x as f64).collect();
+    assert_approx_eq!(mann_whitney_u(&a, &a), 0.0);
+    assert_approx_eq!(mann_whitney_u(&[1.0; 30], &[1.0; 30]), 0.0);
+}
+
+#[test]
+fn test_mann_whitney_u_shifted() {
+    let a: Vec<f64> = (0..50).map(|x| x as f64).collect();
+    let b: Vec<f64> = (0..50).map(|x| x as f64 + 40.0).collect();
+    assert!(mann_whitney_u(&a, &b) < -1.96);
+    assert!(mann_whitney_u(&b, &a) > 1.96);
+
+    // A small shift relative to the spread isn't significant.
+    let c: Vec<f64> = (0..50).map(|x| x as f64 + 2.0).collect();
+    assert!(mann_whitney_u(&a, &c).abs() < 1.96);
+}
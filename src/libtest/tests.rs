@@ -1,12 +1,21 @@
+use crate::baseline::Baseline;
 use crate::bench;
 use crate::test::{
     filter_tests, parse_opts, run_test, DynTestFn, DynTestName, MetricMap, RunIgnored,
-    ShouldPanic, StaticTestName, TestDesc, TestDescAndFn, TestOpts, TrFailed, TrFailedMsg,
-    TrIgnored, TrOk,
+    ShouldPanic, StaticTestName, TestDesc, TestDescAndFn, TestExecTime, TestOpts, TestResult,
+    TestSelector, TestShard, TestTimeOptions, TimeThreshold, TrFailed, TrFailedMsg, TrIgnored,
+    TrOk, TrTimedFail,
 };
 use crate::Bencher;
 use crate::Concurrent;
+use crate::OutputFormat;
+use crate::shuffle_tests;
+use crate::formatters::{JsonFormatter, JunitFormatter, OutputFormatter};
+use crate::{run_tests, OutputLocation, StaticTestFn, TestEvent, TestOutput, TrFlaky};
+use crate::{ConsoleTestState, StaticSetupFn, StaticTeardownFn, TestFn};
+use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::mpsc::channel;
+use std::time::Duration;
 
 fn one_ignored_one_unignored_test() -> Vec<TestDescAndFn> {
     vec![
@@ -47,7 +56,7 @@ pub fn do_not_run_ignored_tests() {
     };
     let (tx, rx) = channel();
     run_test(&TestOpts::new(), false, desc, tx, Concurrent::No);
-    let (_, res, _) = rx.recv().unwrap();
+    let (_, res, _, _) = rx.recv().unwrap();
     assert!(res != TrOk);
 }
 
@@ -65,7 +74,7 @@ pub fn ignored_tests_result_in_ignored() {
     };
     let (tx, rx) = channel();
     run_test(&TestOpts::new(), false, desc, tx, Concurrent::No);
-    let (_, res, _) = rx.recv().unwrap();
+    let (_, res, _, _) = rx.recv().unwrap();
     assert!(res == TrIgnored);
 }
 
@@ -85,7 +94,7 @@ fn test_should_panic() {
     };
     let (tx, rx) = channel();
     run_test(&TestOpts::new(), false, desc, tx, Concurrent::No);
-    let (_, res, _) = rx.recv().unwrap();
+    let (_, res, _, _) = rx.recv().unwrap();
     assert!(res == TrOk);
 }
 
@@ -105,7 +114,7 @@ fn test_should_panic_good_message() {
     };
     let (tx, rx) = channel();
     run_test(&TestOpts::new(), false, desc, tx, Concurrent::No);
-    let (_, res, _) = rx.recv().unwrap();
+    let (_, res, _, _) = rx.recv().unwrap();
     assert!(res == TrOk);
 }
 
@@ -127,7 +136,7 @@ fn test_should_panic_bad_message() {
     };
     let (tx, rx) = channel();
     run_test(&TestOpts::new(), false, desc, tx, Concurrent::No);
-    let (_, res, _) = rx.recv().unwrap();
+    let (_, res, _, _) = rx.recv().unwrap();
     assert!(res == TrFailedMsg(format!("{} '{}'", failed_msg, expected)));
 }
 
@@ -145,10 +154,103 @@ fn test_should_panic_but_succeeds() {
     };
     let (tx, rx) = channel();
     run_test(&TestOpts::new(), false, desc, tx, Concurrent::No);
-    let (_, res, _) = rx.recv().unwrap();
+    let (_, res, _, _) = rx.recv().unwrap();
     assert!(res == TrFailed);
 }
 
+fn report_time_test_template(report_time: bool) -> Option<TestExecTime> {
+    fn f() {}
+    let desc = TestDescAndFn {
+        desc: TestDesc {
+            name: StaticTestName("whatever"),
+            ignore: false,
+            should_panic: ShouldPanic::No,
+            allow_fail: false,
+        },
+        testfn: DynTestFn(Box::new(f)),
+    };
+    let time_options = if report_time {
+        Some(TestTimeOptions::new_from_env(false, false))
+    } else {
+        None
+    };
+
+    let test_opts = TestOpts { time_options, ..TestOpts::new() };
+    let (tx, rx) = channel();
+    run_test(&test_opts, false, desc, tx, Concurrent::No);
+    let (_, _, exec_time, _) = rx.recv().unwrap();
+    exec_time
+}
+
+#[test]
+fn test_should_not_report_time() {
+    let exec_time = report_time_test_template(false);
+    assert!(exec_time.is_none());
+}
+
+#[test]
+fn test_should_report_time() {
+    let exec_time = report_time_test_template(true);
+    assert!(exec_time.is_some());
+}
+
+fn time_test_failure_template(error_on_excess: bool) -> TestResult {
+    fn f() {}
+    let desc = TestDescAndFn {
+        desc: TestDesc {
+            name: StaticTestName("whatever"),
+            ignore: false,
+            should_panic: ShouldPanic::No,
+            allow_fail: false,
+        },
+        testfn: DynTestFn(Box::new(f)),
+    };
+    let time_options = TestTimeOptions {
+        error_on_excess,
+        colored: false,
+        threshold: TimeThreshold::new(Duration::from_millis(0), Duration::from_millis(0)),
+    };
+
+    let test_opts = TestOpts { time_options: Some(time_options), ..TestOpts::new() };
+    let (tx, rx) = channel();
+    run_test(&test_opts, false, desc, tx, Concurrent::No);
+    let (_, result, _, _) = rx.recv().unwrap();
+    result
+}
+
+#[test]
+fn test_error_on_exceed() {
+    let result = time_test_failure_template(true);
+    assert!(result == TrTimedFail);
+
+    let result = time_test_failure_template(false);
+    assert!(result == TrOk);
+}
+
+#[test]
+fn parse_report_time_flags() {
+    let args = vec![
+        "progname".to_string(),
+        "-Zunstable-options".to_string(),
+        "--report-time=colored".to_string(),
+    ];
+    let opts = parse_opts(&args).unwrap().unwrap();
+    let time_options = opts.time_options.unwrap();
+    assert!(time_options.colored);
+    assert!(!time_options.error_on_excess);
+
+    let args = vec![
+        "progname".to_string(),
+        "-Zunstable-options".to_string(),
+        "--ensure-time".to_string(),
+    ];
+    let opts = parse_opts(&args).unwrap().unwrap();
+    assert!(opts.time_options.unwrap().error_on_excess);
+
+    let args = vec!["progname".to_string(), "--report-time".to_string()];
+    assert!(parse_opts(&args).unwrap().is_err());
+}
+
 #[test]
 fn parse_ignored_flag() {
     let args = vec![
@@ -172,6 +274,384 @@ fn parse_include_ignored_flag() {
     assert_eq!(opts.run_ignored, RunIgnored::Yes);
 }
 
+#[test]
+fn parse_junit_format() {
+    let args = vec![
+        "progname".to_string(),
+        "-Zunstable-options".to_string(),
+        "--format=junit".to_string(),
+    ];
+    let opts = parse_opts(&args).unwrap().unwrap();
+    assert_eq!(opts.format, OutputFormat::Junit);
+
+    let args = vec!["progname".to_string(), "--format=junit".to_string()];
+    assert!(parse_opts(&args).unwrap().is_err());
+}
+
+fn run_with_retries(retries: usize, test: TestDescAndFn) -> (TestResult, TestOutput) {
+    let opts = TestOpts { run_tests: true, retries, test_threads: Some(1), ..TestOpts::new() };
+    let mut waits = 0;
+    let mut results = Vec::new();
+    run_tests(&opts, vec![test], |event| {
+        match event {
+            TestEvent::TeWait(_) => waits += 1,
+            TestEvent::TeResult(_, result, _, output) => results.push((result, output)),
+            _ => {}
+        }
+        Ok(())
+    })
+    .unwrap();
+
+    // Retries aren't reported, only the final result is.
+    assert_eq!(waits, 1);
+    assert_eq!(results.len(), 1);
+    results.pop().unwrap()
+}
+
+fn static_test(name: &'static str, f: fn()) -> TestDescAndFn {
+    TestDescAndFn {
+        desc: TestDesc {
+            name: StaticTestName(name),
+            ignore: false,
+            should_panic: ShouldPanic::No,
+            allow_fail: false,
+        },
+        testfn: StaticTestFn(f),
+    }
+}
+
+#[test]
+fn test_retries_report_flaky_test() {
+    static RUNS: AtomicUsize = AtomicUsize::new(0);
+    fn f() {
+        if RUNS.fetch_add(1, Ordering::SeqCst) < 2 {
+            panic!("not yet");
+        }
+    }
+
+    let (result, _) = run_with_retries(3, static_test("flaky", f));
+    assert!(result == TrFlaky(3));
+    assert_eq!(RUNS.load(Ordering::SeqCst), 3);
+}
+
+#[test]
+fn test_retries_give_up_after_limit() {
+    static RUNS: AtomicUsize = AtomicUsize::new(0);
+    fn f() {
+        RUNS.fetch_add(1, Ordering::SeqCst);
+        panic!();
+    }
+
+    let (result, _) = run_with_retries(2, static_test("failing", f));
+    assert!(result == TrFailed);
+    assert_eq!(RUNS.load(Ordering::SeqCst), 3);
+}
+
+#[test]
+fn test_retries_do_not_rerun_passing_tests() {
+    static RUNS: AtomicUsize = AtomicUsize::new(0);
+    fn f() {
+        RUNS.fetch_add(1, Ordering::SeqCst);
+    }
+
+    let (result, _) = run_with_retries(2, static_test("passing", f));
+    assert!(result == TrOk);
+    assert_eq!(RUNS.load(Ordering::SeqCst), 1);
+}
+
+#[test]
+fn test_dyn_tests_are_not_retried() {
+    let test = TestDescAndFn {
+        desc: TestDesc {
+            name: StaticTestName("dyn"),
+            ignore: false,
+            should_panic: ShouldPanic::No,
+            allow_fail: false,
+        },
+        testfn: DynTestFn(Box::new(|| panic!())),
+    };
+
+    let (result, output) = run_with_retries(2, test);
+    assert!(result == TrFailed);
+    let stderr = String::from_utf8_lossy(&output.stderr()).into_owned();
+    assert!(stderr.contains("note: not retried"), "{}", stderr);
+}
+
+#[test]
+fn parse_retries_flag() {
+    let args = vec![
+        "progname".to_string(),
+        "-Zunstable-options".to_string(),
+        "--retries=3".to_string(),
+    ];
+    let opts = parse_opts(&args).unwrap().unwrap();
+    assert_eq!(opts.retries, 3);
+
+    let args = vec![
+        "progname".to_string(),
+        "-Zunstable-options".to_string(),
+        "--retries=many".to_string(),
+    ];
+    assert!(parse_opts(&args).unwrap().is_err());
+
+    let args = vec!["progname".to_string(), "--retries=3".to_string()];
+    assert!(parse_opts(&args).unwrap().is_err());
+}
+
+fn fixture(name: &'static str, testfn: TestFn) -> TestDescAndFn {
+    TestDescAndFn { testfn, ..static_test(name, || {}) }
+}
+
+/// Runs the given tests, returning the names of the fixtures and tests in the
+/// order they finished, along with their results.
+fn run_with_fixtures(filters: Vec<String>, tests: Vec<TestDescAndFn>) -> Vec<(String, TestResult)> {
+    let opts = TestOpts { run_tests: true, filters, test_threads: Some(1), ..TestOpts::new() };
+    let mut events = Vec::new();
+    run_tests(&opts, tests, |event| {
+        match event {
+            TestEvent::TeFixtureResult(desc, kind, result, _) => {
+                events.push((format!("{} {}", kind, desc.name), result))
+            }
+            TestEvent::TeResult(desc, result, _, _) => events.push((desc.name.to_string(), result)),
+            _ => {}
+        }
+        Ok(())
+    })
+    .unwrap();
+    events
+}
+
+#[test]
+fn test_fixtures_run_around_their_module() {
+    let tests = vec![
+        fixture("a::teardown", StaticTeardownFn(|| {})),
+        static_test("a::first", || {}),
+        static_test("b::test", || {}),
+        fixture("a::setup", StaticSetupFn(|| {})),
+        static_test("a::second", || {}),
+        fixture("setup", StaticSetupFn(|| {})),
+    ];
+    let names: Vec<_> = run_with_fixtures(vec![], tests)
+        .into_iter()
+        .map(|(name, result)| {
+            assert!(result == TrOk);
+            name
+        })
+        .collect();
+    assert_eq!(
+        names,
+        [
+            "setup setup",
+            "setup a::setup",
+            "a::first",
+            "a::second",
+            "teardown a::teardown",
+            "b::test",
+        ]
+    );
+}
+
+#[test]
+fn test_fixtures_run_when_their_module_is_reached() {
+    let tests = vec![
+        static_test("a::test", || {}),
+        fixture("b::setup", StaticSetupFn(|| {})),
+        fixture("b::teardown", StaticTeardownFn(|| {})),
+        static_test("b::c::test", || {}),
+        static_test("b::test", || {}),
+        static_test("d::test", || {}),
+    ];
+    let names: Vec<_> =
+        run_with_fixtures(vec![], tests).into_iter().map(|(name, _)| name).collect();
+    assert_eq!(
+        names,
+        ["a::test", "setup b::setup", "b::c::test", "b::test", "teardown b::teardown", "d::test"]
+    );
+}
+
+#[test]
+fn test_fixtures_skip_ignored_tests() {
+    let mut ignored = static_test("a::ignored", || {});
+    ignored.desc.ignore = true;
+    let tests = vec![
+        fixture("a::setup", StaticSetupFn(|| {})),
+        fixture("a::teardown", StaticTeardownFn(|| {})),
+        ignored,
+        static_test("a::test", || {}),
+        static_test("b::test", || {}),
+    ];
+    let names: Vec<_> =
+        run_with_fixtures(vec![], tests).into_iter().map(|(name, _)| name).collect();
+    assert_eq!(
+        names,
+        ["a::ignored", "setup a::setup", "a::test", "teardown a::teardown", "b::test"]
+    );
+}
+
+#[test]
+fn test_fixtures_of_filtered_out_modules_are_skipped() {
+    let tests = vec![
+        fixture("a::setup", StaticSetupFn(|| panic!())),
+        fixture("a::teardown", StaticTeardownFn(|| panic!())),
+        static_test("a::test", || {}),
+        static_test("b::test", || {}),
+    ];
+    let events = run_with_fixtures(vec!["b::".to_string()], tests);
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0].0, "b::test");
+}
+
+#[test]
+fn test_failed_setup_blocks_its_module() {
+    let tests = vec![
+        fixture("a::setup", StaticSetupFn(|| panic!())),
+        fixture("a::b::setup", StaticSetupFn(|| panic!())),
+        fixture("a::teardown", StaticTeardownFn(|| panic!())),
+        static_test("a::b::test", || {}),
+        static_test("a::test", || {}),
+        static_test("c::test", || {}),
+    ];
+    let events = run_with_fixtures(vec![], tests);
+    let blocked = TrFailedMsg("not run because setup `a::setup` failed".to_string());
+    // Neither the nested setup nor the teardown run once the setup failed.
+    assert_eq!(events.len(), 4);
+    assert_eq!(events[0].0, "setup a::setup");
+    assert!(events[0].1 == TrFailed);
+    assert_eq!(events[1].0, "a::b::test");
+    assert!(events[1].1 == blocked);
+    assert_eq!(events[2].0, "a::test");
+    assert!(events[2].1 == blocked);
+    assert_eq!(events[3].0, "c::test");
+    assert!(events[3].1 == TrOk);
+}
+
+#[test]
+fn test_output_splits_streams() {
+    let mut output = TestOutput::new();
+    assert!(output.is_empty());
+    output.push(b"out 1\n", false);
+    output.push(b"err 1\n", true);
+    output.push(b"err 2\n", true);
+    output.push(b"out 2\n", false);
+    output.push(b"err 3\n", true);
+
+    assert_eq!(output.as_bytes(), &b"out 1\nerr 1\nerr 2\nout 2\nerr 3\n"[..]);
+    assert_eq!(output.stdout(), b"out 1\nout 2\n");
+    assert_eq!(output.stderr(), b"err 1\nerr 2\nerr 3\n");
+}
+
+#[test]
+fn json_format_is_stable() {
+    let args = vec!["progname".to_string(), "--format=json".to_string()];
+    let opts = parse_opts(&args).unwrap().unwrap();
+    assert_eq!(opts.format, OutputFormat::Json);
+    // Every test event carries its duration, but without `--report-time` the
+    // thresholds are not read from the environment.
+    assert!(opts.time_options.is_none());
+}
+
+#[test]
+fn json_format_records_exec_time() {
+    let desc = TestDescAndFn {
+        desc: TestDesc {
+            name: StaticTestName("whatever"),
+            ignore: false,
+            should_panic: ShouldPanic::No,
+            allow_fail: false,
+        },
+        testfn: DynTestFn(Box::new(move || {})),
+    };
+    let test_opts = TestOpts { format: OutputFormat::Json, ..TestOpts::new() };
+    let (tx, rx) = channel();
+    run_test(&test_opts, false, desc, tx, Concurrent::No);
+    let (_, _, exec_time, _) = rx.recv().unwrap();
+    assert!(exec_time.is_some());
+}
+
+#[test]
+fn json_events_carry_captured_output() {
+    let desc = TestDesc {
+        name: StaticTestName("m::\"quoted\""),
+        ignore: false,
+        should_panic: ShouldPanic::No,
+        allow_fail: false,
+    };
+    let mut output = TestOutput::new();
+    output.push(b"printed\n", false);
+    output.push(b"panicked\n", true);
+    let exec_time = TestExecTime(Duration::from_millis(1500));
+
+    let mut out = JsonFormatter::new(OutputLocation::Raw(Vec::new()));
+    out.write_run_start(1, None).unwrap();
+    out.write_result(&desc, &TrFailed, Some(&exec_time), &output).unwrap();
+    out.write_result(&desc, &TrOk, None, &TestOutput::new()).unwrap();
+
+    let written = match out.output_location() {
+        &OutputLocation::Raw(ref m) => String::from_utf8_lossy(&m[..]).into_owned(),
+        &OutputLocation::Pretty(_) => unreachable!(),
+    };
+    let lines: Vec<_> = written.lines().collect();
+    assert_eq!(
+        lines,
+        [
+            r#"{ "type": "suite", "event": "started", "schema_version": 1, "test_count": 1 }"#,
+            concat!(
+                r#"{ "type": "test", "name": "m::\"quoted\"", "event": "failed", "#,
+                r#""exec_time": 1.500, "stdout": "printed\n", "stderr": "panicked\n" }"#,
+            ),
+            r#"{ "type": "test", "name": "m::\"quoted\"", "event": "ok" }"#,
+        ]
+    );
+}
+
+#[test]
+fn junit_format_escapes_line_breaks() {
+    let desc = TestDesc {
+        name: StaticTestName("m::two\nlines"),
+        ignore: false,
+        should_panic: ShouldPanic::YesWithMessage("first\nsecond"),
+        allow_fail: false,
+    };
+    let result = TrFailedMsg("expected\r\n\t'first\nsecond'".to_string());
+
+    let mut out = JunitFormatter::new(OutputLocation::Raw(Vec::new()));
+    out.write_run_start(1, None).unwrap();
+    out.write_result(&desc, &result, None, &TestOutput::new()).unwrap();
+    let state = ConsoleTestState::new(&TestOpts::new()).unwrap();
+    out.write_run_finish(&state).unwrap();
+
+    let written = match out.output_location() {
+        &OutputLocation::Raw(ref m) => String::from_utf8_lossy(&m[..]).into_owned(),
+        &OutputLocation::Pretty(_) => unreachable!(),
+    };
+    let lines: Vec<_> = written.lines().collect();
+    assert_eq!(
+        lines[3..6],
+        [
+            r#"<testcase classname="m" name="two&#10;lines" time="0.000">"#,
+            concat!(
+                r#"<failure type="assert" "#,
+                r#"message="expected&#13;&#10;&#9;&apos;first&#10;second&apos;"/>"#,
+            ),
+            "</testcase>",
+        ]
+    );
+}
+
+#[test]
+fn parse_isolate_flag() {
+    let args = vec![
+        "progname".to_string(),
+        "-Zunstable-options".to_string(),
+        "--isolate".to_string(),
+    ];
+    let opts = parse_opts(&args).unwrap().unwrap();
+    assert!(opts.isolate);
+
+    let args = vec!["progname".to_string(), "--isolate".to_string()];
+    assert!(parse_opts(&args).unwrap().is_err());
+}
+
 #[test]
 pub fn filter_for_ignored_option() {
     // When we run ignored tests the test filter should filter out all the
@@ -248,7 +728,7 @@ pub fn exact_filter_match() {
 
     let substr = filter_tests(
         &TestOpts {
-            filter: Some("base".into()),
+            filters: vec!["base".into()],
             ..TestOpts::new()
         },
         tests(),
@@ -257,7 +737,7 @@ pub fn exact_filter_match() {
 
     let substr = filter_tests(
         &TestOpts {
-            filter: Some("bas".into()),
+            filters: vec!["bas".into()],
             ..TestOpts::new()
         },
         tests(),
@@ -266,7 +746,7 @@ pub fn exact_filter_match() {
 
     let substr = filter_tests(
         &TestOpts {
-            filter: Some("::test".into()),
+            filters: vec!["::test".into()],
             ..TestOpts::new()
         },
         tests(),
@@ -275,7 +755,7 @@ pub fn exact_filter_match() {
 
     let substr = filter_tests(
         &TestOpts {
-            filter: Some("base::test".into()),
+            filters: vec!["base::test".into()],
             ..TestOpts::new()
         },
         tests(),
@@ -284,7 +764,7 @@ pub fn exact_filter_match() {
 
     let exact = filter_tests(
         &TestOpts {
-            filter: Some("base".into()),
+            filters: vec!["base".into()],
             filter_exact: true,
             ..TestOpts::new()
         },
@@ -294,7 +774,7 @@ pub fn exact_filter_match() {
 
     let exact = filter_tests(
         &TestOpts {
-            filter: Some("bas".into()),
+            filters: vec!["bas".into()],
             filter_exact: true,
             ..TestOpts::new()
         },
@@ -304,7 +784,7 @@ pub fn exact_filter_match() {
 
     let exact = filter_tests(
         &TestOpts {
-            filter: Some("::test".into()),
+            filters: vec!["::test".into()],
             filter_exact: true,
             ..TestOpts::new()
         },
@@ -314,7 +794,7 @@ pub fn exact_filter_match() {
 
     let exact = filter_tests(
         &TestOpts {
-            filter: Some("base::test".into()),
+            filters: vec!["base::test".into()],
             filter_exact: true,
             ..TestOpts::new()
         },
@@ -323,6 +803,168 @@ pub fn exact_filter_match() {
     assert_eq!(exact.len(), 1);
 }
 
+#[test]
+pub fn multiple_filters_are_ored() {
+    let tests = vec!["a::one", "a::two", "b::one", "c::three"]
+        .into_iter()
+        .map(|name| TestDescAndFn {
+            desc: TestDesc {
+                name: StaticTestName(name),
+                ignore: false,
+                should_panic: ShouldPanic::No,
+                allow_fail: false,
+            },
+            testfn: DynTestFn(Box::new(move || {})),
+        })
+        .collect();
+
+    let opts = TestOpts { filters: vec!["two".into(), "three".into()], ..TestOpts::new() };
+    let filtered = filter_tests(&opts, tests);
+
+    let names: Vec<_> = filtered.iter().map(|t| t.desc.name.to_string()).collect();
+    assert_eq!(names, ["a::two", "c::three"]);
+}
+
+#[test]
+pub fn select_and_deselect_options() {
+    fn tests() -> Vec<TestDescAndFn> {
+        vec![
+            ("foo::plain", false, ShouldPanic::No, false),
+            ("foo::panics", false, ShouldPanic::Yes, false),
+            ("foo::bar::ignored", true, ShouldPanic::No, false),
+            ("foobar::flaky", false, ShouldPanic::No, true),
+            ("baz::panics", false, ShouldPanic::YesWithMessage("boom"), false),
+        ]
+        .into_iter()
+        .map(|(name, ignore, should_panic, allow_fail)| TestDescAndFn {
+            desc: TestDesc { name: StaticTestName(name), ignore, should_panic, allow_fail },
+            testfn: DynTestFn(Box::new(move || {})),
+        })
+        .collect()
+    }
+    fn names(opts: &TestOpts) -> Vec<String> {
+        filter_tests(opts, tests()).iter().map(|t| t.desc.name.to_string()).collect()
+    }
+
+    let opts = TestOpts { select: vec![TestSelector::ShouldPanic], ..TestOpts::new() };
+    assert_eq!(names(&opts), ["baz::panics", "foo::panics"]);
+
+    let opts = TestOpts {
+        select: vec![TestSelector::Ignore, TestSelector::AllowFail],
+        ..TestOpts::new()
+    };
+    assert_eq!(names(&opts), ["foo::bar::ignored", "foobar::flaky"]);
+
+    let opts = TestOpts { select: vec![TestSelector::Module("foo".into())], ..TestOpts::new() };
+    assert_eq!(names(&opts), ["foo::bar::ignored", "foo::panics", "foo::plain"]);
+
+    let opts = TestOpts {
+        select: vec![TestSelector::Module("foo".into())],
+        deselect: vec![TestSelector::Module("foo::bar".into()), TestSelector::ShouldPanic],
+        ..TestOpts::new()
+    };
+    assert_eq!(names(&opts), ["foo::plain"]);
+
+    let opts = TestOpts {
+        filters: vec!["panics".into()],
+        select: vec![TestSelector::Module("baz".into())],
+        ..TestOpts::new()
+    };
+    assert_eq!(names(&opts), ["baz::panics"]);
+}
+
+#[test]
+fn parse_select_flags() {
+    let args = vec![
+        "progname".to_string(),
+        "-Zunstable-options".to_string(),
+        "--select=module=foo::bar::".to_string(),
+        "--select=should_panic".to_string(),
+        "--deselect=allow_fail".to_string(),
+        "filter1".to_string(),
+        "filter2".to_string(),
+    ];
+    let opts = parse_opts(&args).unwrap().unwrap();
+    assert_eq!(opts.filters, ["filter1", "filter2"]);
+    assert_eq!(
+        opts.select,
+        [TestSelector::Module("foo::bar".into()), TestSelector::ShouldPanic]
+    );
+    assert_eq!(opts.deselect, [TestSelector::AllowFail]);
+
+    let args = vec![
+        "progname".to_string(),
+        "-Zunstable-options".to_string(),
+        "--select=slow".to_string(),
+    ];
+    assert!(parse_opts(&args).unwrap().is_err());
+
+    let args = vec!["progname".to_string(), "--select=ignore".to_string()];
+    assert!(parse_opts(&args).unwrap().is_err());
+}
+
+#[test]
+pub fn shards_cover_every_test_once() {
+    fn tests() -> Vec<TestDescAndFn> {
+        (0..23)
+            .map(|i| TestDescAndFn {
+                desc: TestDesc {
+                    name: DynTestName(format!("m{}::t{}", i % 4, i)),
+                    ignore: false,
+                    should_panic: ShouldPanic::No,
+                    allow_fail: false,
+                },
+                testfn: DynTestFn(Box::new(move || {})),
+            })
+            .collect()
+    }
+
+    let all: Vec<String> = filter_tests(&TestOpts::new(), tests())
+        .iter()
+        .map(|t| t.desc.name.to_string())
+        .collect();
+
+    let count = 5;
+    let mut seen = Vec::new();
+    for index in 1..=count {
+        let opts = TestOpts { shard: Some(TestShard { index, count }), ..TestOpts::new() };
+        let shard = filter_tests(&opts, tests());
+        assert!(shard.len() == 4 || shard.len() == 5);
+        seen.extend(shard.iter().map(|t| t.desc.name.to_string()));
+
+        // The same shard always gets the same tests.
+        let again: Vec<_> = filter_tests(&opts, tests().into_iter().rev().collect())
+            .iter()
+            .map(|t| t.desc.name.to_string())
+            .collect();
+        assert_eq!(again, shard.iter().map(|t| t.desc.name.to_string()).collect::<Vec<_>>());
+    }
+    seen.sort();
+    assert_eq!(seen, all);
+}
+
+#[test]
+fn parse_shard_flag() {
+    let parse = |shard: &str| {
+        let args = vec![
+            "progname".to_string(),
+            "-Zunstable-options".to_string(),
+            format!("--shard={}", shard),
+        ];
+        parse_opts(&args).unwrap().map(|opts| opts.shard.unwrap())
+    };
+    assert_eq!(parse("3/8"), Ok(TestShard { index: 3, count: 8 }));
+    assert_eq!(parse("1/1"), Ok(TestShard { index: 1, count: 1 }));
+    assert!(parse("0/8").is_err());
+    assert!(parse("9/8").is_err());
+    assert!(parse("1/0").is_err());
+    assert!(parse("3").is_err());
+    assert!(parse("a/b").is_err());
+
+    let args = vec!["progname".to_string(), "--shard=1/2".to_string()];
+    assert!(parse_opts(&args).unwrap().is_err());
+}
+
 #[test]
 pub fn sort_tests() {
     let mut opts = TestOpts::new();
@@ -379,6 +1021,55 @@ pub fn sort_tests() {
     }
 }
 
+#[test]
+pub fn shuffle_tests_is_reproducible() {
+    fn tests() -> Vec<TestDescAndFn> {
+        (0..20)
+            .map(|i| TestDescAndFn {
+                desc: TestDesc {
+                    name: DynTestName(format!("test_{:02}", i)),
+                    ignore: false,
+                    should_panic: ShouldPanic::No,
+                    allow_fail: false,
+                },
+                testfn: DynTestFn(Box::new(move || {})),
+            })
+            .collect()
+    }
+    fn names(tests: &[TestDescAndFn]) -> Vec<String> {
+        tests.iter().map(|t| t.desc.name.to_string()).collect()
+    }
+
+    let mut a = tests();
+    let mut b = tests();
+    let mut c = tests();
+    shuffle_tests(42, &mut a);
+    shuffle_tests(42, &mut b);
+    shuffle_tests(43, &mut c);
+
+    assert_eq!(names(&a), names(&b));
+    assert_ne!(names(&a), names(&c));
+    assert_ne!(names(&a), names(&tests()));
+
+    let mut sorted = names(&a);
+    sorted.sort();
+    assert_eq!(sorted, names(&tests()));
+}
+
+#[test]
+fn parse_shuffle_flags() {
+    let args = vec![
+        "progname".to_string(),
+        "-Zunstable-options".to_string(),
+        "--shuffle-seed=1234".to_string(),
+    ];
+    let opts = parse_opts(&args).unwrap().unwrap();
+    assert_eq!(opts.shuffle_seed, Some(1234));
+
+    let args = vec!["progname".to_string(), "--shuffle".to_string()];
+    assert!(parse_opts(&args).unwrap().is_err());
+}
+
 #[test]
 pub fn test_metricmap_compare() {
     let mut m1 = MetricMap::new();
@@ -451,3 +1142,80 @@ pub fn test_bench_iter() {
     crate::bench::benchmark(desc, tx, true, f);
     rx.recv().unwrap();
 }
+
+#[test]
+pub fn test_baseline_roundtrip() {
+    let dir = std::env::temp_dir().join(format!("libtest-baseline-{}", std::process::id()));
+    let path = dir.join("test.baseline");
+
+    let samples: Vec<f64> = (0..50).map(|x| 1000.0 + x as f64).collect();
+    let slower: Vec<f64> = samples.iter().map(|x| x * 1.5).collect();
+
+    let mut baseline = Baseline::new();
+    baseline.insert("a", &samples);
+    baseline.save(&path).unwrap();
+
+    // Saving again only updates the benchmarks that were run.
+    let mut baseline = Baseline::new();
+    baseline.insert("b", &slower);
+    baseline.save(&path).unwrap();
+
+    let loaded = Baseline::load(&path).unwrap();
+    let missing = Baseline::load(&dir.join("missing.baseline"));
+    std::fs::remove_dir_all(&dir).unwrap();
+
+    let same = loaded.compare("a", &samples).unwrap();
+    assert_eq!(same.change_pct, 0.0);
+    assert!(!same.significant);
+
+    let regressed = loaded.compare("a", &slower).unwrap();
+    assert!(regressed.change_pct > 49.0 && regressed.change_pct < 51.0);
+    assert!(regressed.significant);
+
+    assert!(loaded.compare("b", &slower).is_some());
+    assert!(loaded.compare("c", &samples).is_none());
+    assert!(missing.is_err());
+}
+
+#[test]
+pub fn test_baseline_escapes_names() {
+    let dir = std::env::temp_dir().join(format!("libtest-baseline-names-{}", std::process::id()));
+    let path = dir.join("test.baseline");
+    let names = ["tab\there", "line\nbreak", "carriage\rreturn", "back\\slash\\t"];
+
+    let mut baseline = Baseline::new();
+    for (i, name) in names.iter().enumerate() {
+        baseline.insert(name, &[i as f64 + 1.0]);
+    }
+    baseline.save(&path).unwrap();
+
+    // Every benchmark stays on its own line after the header.
+    let contents = std::fs::read_to_string(&path).unwrap();
+    assert_eq!(contents.lines().count(), names.len() + 1);
+
+    let loaded = Baseline::load(&path).unwrap();
+    std::fs::remove_dir_all(&dir).unwrap();
+
+    for name in &names {
+        assert!(loaded.compare(name, &[1.0]).is_some(), "{:?} wasn't loaded", name);
+    }
+    assert!(loaded.compare("tab", &[1.0]).is_none());
+}
+
+#[test]
+fn parse_baseline_names() {
+    let args = vec![
+        "progname".to_string(),
+        "-Zunstable-options".to_string(),
+        "--save-baseline=main".to_string(),
+    ];
+    let opts = parse_opts(&args).unwrap().unwrap();
+    assert_eq!(opts.save_baseline, Some("main".to_string()));
+
+    let args = vec![
+        "progname".to_string(),
+        "-Zunstable-options".to_string(),
+        "--baseline=../main".to_string(),
+    ];
+    assert!(parse_opts(&args).unwrap().is_err());
+}
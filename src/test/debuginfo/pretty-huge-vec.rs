@@ -10,7 +10,7 @@
 // gdb-command: run
 
 // gdb-command: print vec
-// gdb-check:$1 = Vec<u8>(len: 1000000000, cap: 1000000000) = {[...]...}
+// gdb-check:$1 = Vec<u8, alloc::alloc::Global>(len: 1000000000, cap: 1000000000) = {[...]...}
 
 // gdb-command: print slice
 // gdb-check:$2 = &[u8](len: 1000000000) = {[...]...}
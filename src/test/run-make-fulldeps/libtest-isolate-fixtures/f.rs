@@ -0,0 +1,61 @@
+#![feature(test_fixtures)]
+
+use std::env;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+
+// Every fixture and test appends a line to this file, so the Makefile can
+// count how often each of them ran across all processes.
+fn log(line: &str) {
+    let path = env::var("FIXTURE_LOG").unwrap();
+    let mut file = OpenOptions::new().create(true).append(true).open(path).unwrap();
+    writeln!(file, "{}", line).unwrap();
+}
+
+fn logged() -> String {
+    fs::read_to_string(env::var("FIXTURE_LOG").unwrap()).unwrap()
+}
+
+#[test_setup]
+fn setup() {
+    log("setup root");
+}
+
+#[test_teardown]
+fn teardown() {
+    log("teardown root");
+}
+
+#[test]
+fn b_sees_root_setup() {
+    log("test b");
+    assert!(logged().contains("setup root"));
+}
+
+mod a {
+    use super::{log, logged};
+
+    #[test_setup]
+    fn setup() {
+        log("setup a");
+    }
+
+    #[test_teardown]
+    fn teardown() {
+        log("teardown a");
+    }
+
+    #[test]
+    fn first() {
+        log("test a::first");
+        assert!(logged().contains("setup a"));
+        assert!(!logged().contains("teardown a"));
+    }
+
+    #[test]
+    fn second() {
+        log("test a::second");
+        assert!(logged().contains("setup a"));
+        assert!(!logged().contains("teardown a"));
+    }
+}
@@ -0,0 +1,33 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+static COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+#[test]
+fn a_passes() {}
+
+#[test]
+fn b_exits() {
+    std::process::exit(0);
+}
+
+#[test]
+fn c_aborts() {
+    std::process::abort();
+}
+
+#[test]
+fn d_panics() {
+    panic!("d panicked");
+}
+
+// Each test runs in a fresh process, so global state set by one test
+// can't leak into another.
+#[test]
+fn e_global_state() {
+    assert_eq!(COUNTER.fetch_add(1, Ordering::SeqCst), 0);
+}
+
+#[test]
+fn f_global_state() {
+    assert_eq!(COUNTER.fetch_add(1, Ordering::SeqCst), 0);
+}
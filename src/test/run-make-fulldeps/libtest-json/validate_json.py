@@ -3,6 +3,12 @@
 import sys
 import json
 
-# Try to decode line in order to ensure it is a valid JSON document
+# Try to decode line in order to ensure it is a valid JSON document, and
+# check the fields every event of the schema carries
 for line in sys.stdin:
-    json.loads(line)
+    event = json.loads(line)
+    assert "type" in event
+    if event["type"] == "suite" and event["event"] == "started":
+        assert event["schema_version"] == 1
+    if event["type"] == "test":
+        assert "name" in event and "event" in event
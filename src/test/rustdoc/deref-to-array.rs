@@ -0,0 +1,26 @@
+#![crate_name = "foo"]
+
+use std::ops::Deref;
+
+pub struct Bytes([u8; 4]);
+
+impl Deref for Bytes {
+    type Target = [u8; 4];
+    fn deref(&self) -> &[u8; 4] { &self.0 }
+}
+
+// @has foo/struct.Bytes.html
+// @has - '//*[@class="sidebar-title"]' 'Methods from Deref<Target=[u8; 4]>'
+// @has - '//*[@id="method.len"]' 'fn len(&self) -> usize'
+// @has - '//*[@id="method.is_ascii"]' 'fn is_ascii(&self) -> bool'
+
+pub struct Words([u32; 4]);
+
+impl Deref for Words {
+    type Target = [u32; 4];
+    fn deref(&self) -> &[u32; 4] { &self.0 }
+}
+
+// @has foo/struct.Words.html
+// @has - '//*[@id="method.len"]' 'fn len(&self) -> usize'
+// @!has - '//*[@id="method.is_ascii"]' 'fn is_ascii(&self) -> bool'
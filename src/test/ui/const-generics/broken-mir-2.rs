@@ -1,3 +1,5 @@
+// check-pass
+
 #![feature(const_generics)]
 //~^ WARN the feature `const_generics` is incomplete and may cause the compiler to crash
 
@@ -5,6 +7,5 @@ use std::fmt::Debug;
 
 #[derive(Debug)]
 struct S<T: Debug, const N: usize>([T; N]);
-//~^ ERROR arrays only have std trait implementations for lengths 0..=32
 
 fn main() {}
@@ -0,0 +1,7 @@
+#[test_setup] //~ ERROR `#[test_setup]` and `#[test_teardown]` are an unstable feature
+fn setup() {}
+
+#[test_teardown] //~ ERROR `#[test_setup]` and `#[test_teardown]` are an unstable feature
+fn teardown() {}
+
+fn main() {}
@@ -0,0 +1,49 @@
+// compile-flags: --test
+// run-pass
+// ignore-emscripten no threads support
+
+#![feature(test_fixtures)]
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+static ROOT_SETUPS: AtomicUsize = AtomicUsize::new(0);
+static NESTED_SETUPS: AtomicUsize = AtomicUsize::new(0);
+
+#[test_setup]
+fn setup() {
+    ROOT_SETUPS.fetch_add(1, Ordering::SeqCst);
+}
+
+#[test]
+fn setup_ran_once() {
+    assert_eq!(ROOT_SETUPS.load(Ordering::SeqCst), 1);
+}
+
+mod nested {
+    use super::{NESTED_SETUPS, ROOT_SETUPS};
+    use std::sync::atomic::Ordering;
+
+    #[test_setup]
+    fn setup() -> Result<(), String> {
+        // The setup of the parent module runs first.
+        assert_eq!(ROOT_SETUPS.load(Ordering::SeqCst), 1);
+        assert_eq!(NESTED_SETUPS.fetch_add(1, Ordering::SeqCst), 0);
+        Ok(())
+    }
+
+    // The teardown only runs once both tests of the module finished.
+    #[test]
+    fn first() {
+        assert_eq!(NESTED_SETUPS.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn second() {
+        assert_eq!(NESTED_SETUPS.load(Ordering::SeqCst), 1);
+    }
+
+    #[test_teardown]
+    fn teardown() {
+        NESTED_SETUPS.store(0, Ordering::SeqCst);
+    }
+}
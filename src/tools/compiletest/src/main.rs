@@ -542,7 +542,7 @@ pub fn run_tests(config: &Config) {
 pub fn test_opts(config: &Config) -> test::TestOpts {
     test::TestOpts {
         exclude_should_panic: false,
-        filter: config.filter.clone(),
+        filters: config.filter.clone().into_iter().collect(),
         filter_exact: config.filter_exact,
         run_ignored: if config.run_ignored {
             test::RunIgnored::Yes
@@ -564,7 +564,17 @@ pub fn test_opts(config: &Config) -> test::TestOpts {
         color: config.color,
         test_threads: None,
         skip: vec![],
+        select: vec![],
+        deselect: vec![],
+        shard: None,
         list: false,
+        time_options: None,
+        shuffle: false,
+        shuffle_seed: None,
+        isolate: false,
+        retries: 0,
+        save_baseline: None,
+        baseline: None,
         options: test::Options::new(),
     }
 }